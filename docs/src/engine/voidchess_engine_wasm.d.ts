@@ -2,11 +2,197 @@
 /* eslint-disable */
 export function main_js(): void;
 export function get_greeting_for(name: string): any;
-export function get_concatenated_allowed_moves(game_config: string): any;
-export function get_fen(game_config: string): any;
-export function evaluate_position_after(game_config: string): any;
-export function evaluate_move_after(game_config: string, move_str: string): any;
-export function pick_move_to_play(game_eval_result_array_str: string): any;
+/**
+ *
+ * * the legal moves (of the promotions only the one to a queen)
+ * 
+ */
+export function get_allowed_move_list(game_config: string): MoveListResult;
+export function get_fen(game_config: string): FenResult;
+export function evaluate_position_after(game_config: string): GameEvaluation;
+/**
+ *
+ * * like evaluate_position_after, but the evaluation weights are given as json,
+ * * e.g. '{"queen_value": 9.5}'. Weights that aren't mentioned keep their default value.
+ * 
+ */
+export function evaluate_position_after_with_params(game_config: string, eval_params_json: string): GameEvaluation;
+/**
+ *
+ * * like evaluate_position_after, but the engine plays like a player of the given skill level (1 weakest - 20 strongest)
+ * 
+ */
+export function evaluate_position_with_skill(game_config: string, skill_level: number): GameEvaluation;
+export function evaluate_move_after(game_config: string, move_str: string): GameEvaluation;
+/**
+ *
+ * * like evaluate_move_after, but the evaluation weights are given as json (see evaluate_position_after_with_params).
+ * 
+ */
+export function evaluate_move_after_with_params(game_config: string, move_str: string, eval_params_json: string): GameEvaluation;
+/**
+ *
+ * * picks the move to play from the evaluations of the single moves (see evaluate_move_after).
+ * * If one of the evaluations is GameEnded or Err, that one is returned.
+ * 
+ */
+export function pick_move_to_play(game_evaluations: GameEvaluation[]): GameEvaluation;
+/**
+ *
+ * * splits the search of the position into one work item per legal move.
+ * * Evaluate them with evaluate_work_item (in any order, on any worker) and hand the results to merge_evaluations.
+ * 
+ */
+export function split_position(game_config: string): RootSplit;
+/**
+ *
+ * * evaluates the move of a work item (see split_position). The options are the ones of GameSession.evaluate without skill_level.
+ * 
+ */
+export function evaluate_work_item(work_item: WorkItem, opt_options?: EvaluateOptions | null): GameEvaluation;
+/**
+ *
+ * * picks the move to play from the evaluations of all work items of split_position(game_config).
+ * * The options have to be the ones the work items were evaluated with.
+ * * If one of the evaluations is GameEnded or Err, that one is returned.
+ * 
+ */
+export function merge_evaluations(game_config: string, game_evaluations: GameEvaluation[], opt_options?: EvaluateOptions | null): GameEvaluation;
+
+export type DrawReason = "StaleMate" | "InsufficientMaterial" | "ThreeTimesRepetition" | "NoChangeIn50Moves" | "EndgameTablebase";
+
+/** the numeric evaluations are in pawns from the point of view of the engine, EngineGetsCheckMatedIn also contains the numeric evaluation as tie-break */
+export type MoveEvaluation =
+    | { EngineCheckMatesIn: number }
+    | { Numeric: number }
+    | { Draw: DrawReason }
+    | { EngineGetsCheckMatedIn: [number, number] };
+
+/** GameEnded if the game_config is an already ended game (or checkmate/stalemate), else an evaluation or an error */
+export type GameEvaluation =
+    | { result_type: "MoveToPlay", move_to_play: string, eval: MoveEvaluation, fen: string }
+    | { result_type: "GameEnded", msg: string }
+    | { result_type: "Err", kind: ErrorKind, msg: string };
+
+/** moves like "e2-e4", "e1cg1" (castling) or "a7Qa8" (promotion) */
+export type MoveList = string[];
+
+/** GameEnded if a move of the game_config ends the game, so that no move can follow it */
+export type ErrorKind = "IllegalFormat" | "IllegalConfig" | "IllegalMove" | "GameEnded";
+
+export interface WasmError {
+    kind: ErrorKind;
+    msg: string;
+}
+
+/** the exported functions don't throw, they return the error instead */
+export type WasmResult<T> = { is_ok: true, value: T } | { is_ok: false, error: WasmError };
+
+export type FenResult = WasmResult<string>;
+
+export type MoveListResult = WasmResult<MoveList>;
+
+
+
+/** one legal move of a position, it can be evaluated on its own (e.g. in a web worker) */
+export interface WorkItem {
+    game_config: string;
+    move_to_evaluate: string;
+}
+
+/** GameEnded/Err if there is nothing to evaluate, else the moves to evaluate */
+export type RootSplit =
+    | { result_type: "WorkItems", work_items: WorkItem[] }
+    | { result_type: "GameEnded", msg: string }
+    | { result_type: "Err", kind: ErrorKind, msg: string };
+
+
+
+/** the draw_reason is only set for a Draw */
+export interface SessionStatus {
+    status: "Ongoing" | "Check" | "Checkmate" | "Draw";
+    draw_reason: DrawReason | null;
+}
+
+/** with a skill_level the eval_params are ignored */
+export interface EvaluateOptions {
+    skill_level?: number;
+    eval_params?: object;
+    contempt_in_centipawns?: number;
+}
+
+
+/**
+ *
+ * * A game that stays alive in the browser between the moves, so that playing, undoing, redoing
+ * * and evaluating a move doesn't re-parse (and replay) the whole game every time.
+ * * The methods return the same typescript types as the free functions of this crate.
+ * 
+ */
+export class GameSession {
+  free(): void;
+  /**
+   *
+   *     * the game_config is anything the free functions accept: moves from the start, figures on the board or a fen.
+   *     * Throws a WasmError if the game_config can't be parsed.
+   *     
+   */
+  constructor(game_config: string);
+  /**
+   *
+   *     * plays the move (e.g. "e2-e4", "a7Qa8") and returns the fen after the move
+   *     * or the reason why the move couldn't be played
+   *     
+   */
+  play(move_str: string): FenResult;
+  /**
+   *
+   *     * takes back the latest move. Returns false if there was no move to take back.
+   *     
+   */
+  undo(): boolean;
+  /**
+   *
+   *     * plays the latest undone move again. Returns false if there is none.
+   *     
+   */
+  redo(): boolean;
+  /**
+   *
+   *     * goes to the position after the given number of moves (0 is the initial position),
+   *     * the moves after it can still be redone. Returns false if there is no such position.
+   *     
+   */
+  go_to_ply(ply: number): boolean;
+  /**
+   *
+   *     * the number of moves played to reach the current position
+   *     
+   */
+  ply(): number;
+  /**
+   *
+   *     * the legal moves (of the promotions only the one to a queen), empty if the game has ended
+   *     
+   */
+  legal_moves(): MoveList;
+  fen(): string;
+  /**
+   *
+   *     * the moves played since the session started up to the current position, separated by spaces
+   *     
+   */
+  moves_played(): string;
+  status(): SessionStatus;
+  /**
+   *
+   *     * evaluates the current position like evaluate_position_after,
+   *     * e.g. with { skill_level: 5 } or { eval_params: { queen_value: 9.5 }, contempt_in_centipawns: 20 }.
+   *     * All options are optional.
+   *     
+   */
+  evaluate(opt_options?: EvaluateOptions | null): GameEvaluation;
+}
 
 export type InitInput = RequestInfo | URL | Response | BufferSource | WebAssembly.Module;
 
@@ -14,17 +200,36 @@ export interface InitOutput {
   readonly memory: WebAssembly.Memory;
   readonly main_js: () => void;
   readonly get_greeting_for: (a: number, b: number) => any;
-  readonly get_concatenated_allowed_moves: (a: number, b: number) => any;
+  readonly get_allowed_move_list: (a: number, b: number) => any;
   readonly get_fen: (a: number, b: number) => any;
   readonly evaluate_position_after: (a: number, b: number) => any;
+  readonly evaluate_position_after_with_params: (a: number, b: number, c: number, d: number) => any;
+  readonly evaluate_position_with_skill: (a: number, b: number, c: number) => any;
   readonly evaluate_move_after: (a: number, b: number, c: number, d: number) => any;
-  readonly pick_move_to_play: (a: number, b: number) => any;
+  readonly evaluate_move_after_with_params: (a: number, b: number, c: number, d: number, e: number, f: number) => any;
+  readonly pick_move_to_play: (a: any) => any;
+  readonly split_position: (a: number, b: number) => any;
+  readonly evaluate_work_item: (a: any, b: number) => any;
+  readonly merge_evaluations: (a: number, b: number, c: any, d: number) => any;
+  readonly __wbg_gamesession_free: (a: number, b: number) => void;
+  readonly gamesession_new: (a: number, b: number) => [number, number, number];
+  readonly gamesession_play: (a: number, b: number, c: number) => any;
+  readonly gamesession_undo: (a: number) => number;
+  readonly gamesession_redo: (a: number) => number;
+  readonly gamesession_go_to_ply: (a: number, b: number) => number;
+  readonly gamesession_ply: (a: number) => number;
+  readonly gamesession_legal_moves: (a: number) => any;
+  readonly gamesession_fen: (a: number) => [number, number];
+  readonly gamesession_moves_played: (a: number) => [number, number];
+  readonly gamesession_status: (a: number) => any;
+  readonly gamesession_evaluate: (a: number, b: number) => any;
+  readonly __wbindgen_malloc: (a: number, b: number) => number;
+  readonly __wbindgen_realloc: (a: number, b: number, c: number, d: number) => number;
   readonly __wbindgen_exn_store: (a: number) => void;
   readonly __externref_table_alloc: () => number;
-  readonly __wbindgen_export_2: WebAssembly.Table;
+  readonly __wbindgen_export_4: WebAssembly.Table;
+  readonly __externref_table_dealloc: (a: number) => void;
   readonly __wbindgen_free: (a: number, b: number, c: number) => void;
-  readonly __wbindgen_malloc: (a: number, b: number) => number;
-  readonly __wbindgen_realloc: (a: number, b: number, c: number, d: number) => number;
   readonly __wbindgen_start: () => void;
 }
 
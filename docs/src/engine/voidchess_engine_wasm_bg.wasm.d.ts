@@ -3,15 +3,34 @@
 export const memory: WebAssembly.Memory;
 export const main_js: () => void;
 export const get_greeting_for: (a: number, b: number) => any;
-export const get_concatenated_allowed_moves: (a: number, b: number) => any;
+export const get_allowed_move_list: (a: number, b: number) => any;
 export const get_fen: (a: number, b: number) => any;
 export const evaluate_position_after: (a: number, b: number) => any;
+export const evaluate_position_after_with_params: (a: number, b: number, c: number, d: number) => any;
+export const evaluate_position_with_skill: (a: number, b: number, c: number) => any;
 export const evaluate_move_after: (a: number, b: number, c: number, d: number) => any;
-export const pick_move_to_play: (a: number, b: number) => any;
+export const evaluate_move_after_with_params: (a: number, b: number, c: number, d: number, e: number, f: number) => any;
+export const pick_move_to_play: (a: any) => any;
+export const split_position: (a: number, b: number) => any;
+export const evaluate_work_item: (a: any, b: number) => any;
+export const merge_evaluations: (a: number, b: number, c: any, d: number) => any;
+export const __wbg_gamesession_free: (a: number, b: number) => void;
+export const gamesession_new: (a: number, b: number) => [number, number, number];
+export const gamesession_play: (a: number, b: number, c: number) => any;
+export const gamesession_undo: (a: number) => number;
+export const gamesession_redo: (a: number) => number;
+export const gamesession_go_to_ply: (a: number, b: number) => number;
+export const gamesession_ply: (a: number) => number;
+export const gamesession_legal_moves: (a: number) => any;
+export const gamesession_fen: (a: number) => [number, number];
+export const gamesession_moves_played: (a: number) => [number, number];
+export const gamesession_status: (a: number) => any;
+export const gamesession_evaluate: (a: number, b: number) => any;
+export const __wbindgen_malloc: (a: number, b: number) => number;
+export const __wbindgen_realloc: (a: number, b: number, c: number, d: number) => number;
 export const __wbindgen_exn_store: (a: number) => void;
 export const __externref_table_alloc: () => number;
-export const __wbindgen_export_2: WebAssembly.Table;
+export const __wbindgen_export_4: WebAssembly.Table;
+export const __externref_table_dealloc: (a: number) => void;
 export const __wbindgen_free: (a: number, b: number, c: number) => void;
-export const __wbindgen_malloc: (a: number, b: number) => number;
-export const __wbindgen_realloc: (a: number, b: number, c: number, d: number) => number;
 export const __wbindgen_start: () => void;
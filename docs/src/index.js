@@ -30,8 +30,7 @@ async function init_wasm() {
 }
 
 async function getFenResult(arrayOfMoveStr) {
-    let fen = await wasm.get_fen(arrayOfMoveStr.join(' '));
-    return JSON.parse(fen);
+    return wasm.get_fen(arrayOfMoveStr.join(' '));
 }
 
 /**
@@ -39,8 +38,11 @@ async function getFenResult(arrayOfMoveStr) {
  * @returns {Promise<Array<string>>}
  */
 async function getAllowedMovesAsArray(arrayOfMoveStr) {
-    let movesJson = await wasm.get_concatenated_allowed_moves(arrayOfMoveStr.join(' '));
-    return JSON.parse(movesJson);
+    let moveListResult = await wasm.get_allowed_move_list(arrayOfMoveStr.join(' '));
+    if (!moveListResult.is_ok) {
+        throw `${moveListResult.error.kind}: ${moveListResult.error.msg}`;
+    }
+    return moveListResult.value;
 }
 
 function arrayOfMovesToMoveMap(arrayOfMoveStr) {
@@ -145,7 +147,7 @@ function BoardModel(gameModel) {
                         let fen = fenResult.value;
                         self.board.setPosition(fen);
                     } else {
-                        log(fenResult.value);
+                        log(`${fenResult.error.kind}: ${fenResult.error.msg}`);
                     }
                 }, reason => {
                     log(`error when invoking getFenResult: ${reason}`);
@@ -189,7 +191,7 @@ function GameModel() {
                 }
                 if (gameEval.result_type === gameEvalTypes.MOVE_TO_PLAY) {
                     self.moveStrPlayed.push(gameEval.move_to_play);
-                    self.evaluation(gameEval.eval);
+                    self.evaluation(JSON.stringify(gameEval.eval));
                     let fen = gameEval.fen;
                     self.boardModel.board.setPosition(fen);
 
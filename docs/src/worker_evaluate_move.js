@@ -5,12 +5,11 @@ let initPromise = init();
 let hasNotBeenInitialized = true;
 
 onmessage = function (messageEvent) {
-    let gameConfig = messageEvent.data.gameConfig;
-    let moveStr = messageEvent.data.moveStr;
+    let workItem = messageEvent.data.workItem;
     let workerIndex = messageEvent.data.workerIndex;
-    evaluateMove(gameConfig, moveStr).then(gameEvalJson=>{
+    evaluateWorkItem(workItem).then(gameEval=>{
         postMessage({
-            "gameEvalJson": gameEvalJson,
+            "gameEval": gameEval,
             "workerIndex": workerIndex,
         });
     }, reason => {
@@ -21,11 +20,11 @@ onmessage = function (messageEvent) {
     });
 }
 
-async function evaluateMove(gameConfig, moveStr) {
+async function evaluateWorkItem(workItem) {
     if (hasNotBeenInitialized) {
         await initPromise;
         hasNotBeenInitialized = false;
     }
-    let gameEvaluationJson = await wasm.evaluate_position_after(gameConfig);
-    return gameEvaluationJson;
+    let gameEvaluation = await wasm.evaluate_work_item(workItem);
+    return gameEvaluation;
 }
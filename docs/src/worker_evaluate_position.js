@@ -5,9 +5,9 @@ let initPromise = init();
 let hasNotBeenInitialized = true;
 
 onmessage = function (messageEvent) {
-    evaluatePositionAfter(messageEvent.data.gameConfig).then(gameEvalJson=>{
+    evaluatePositionAfter(messageEvent.data.gameConfig).then(gameEval=>{
         postMessage({
-            "gameEvalJson": gameEvalJson,
+            "gameEval": gameEval,
         });
     }, reason => {
         postMessage({
@@ -22,6 +22,6 @@ async function evaluatePositionAfter(gameConfig) {
         await initPromise;
         hasNotBeenInitialized = false;
     }
-    let gameEvaluationJson = await wasm.evaluate_position_after(gameConfig);
-    return gameEvaluationJson;
+    let gameEvaluation = await wasm.evaluate_position_after(gameConfig);
+    return gameEvaluation;
 }
@@ -40,8 +40,7 @@ async function evaluateGameEvalByPositionOnMainThread(gameConfigStr, _possibleMo
     await new Promise(function(resolve) {
         setTimeout(resolve, 310);
     });
-    let gameEvaluationStr = await wasm.evaluate_position_after(gameConfigStr);
-    let gameEval = JSON.parse(gameEvaluationStr);
+    let gameEval = await wasm.evaluate_position_after(gameConfigStr);
     updateStatus("waiting for player")
     return gameEval;
 }
@@ -50,7 +49,7 @@ function evaluateGameEvalByPositionWorker(gameConfigStr, _possibleMoveArray, upd
     updateStatus("computing ...")
     return new Promise(resolve => {
         positionEvaluatorWorker.onmessage = function (messageEvent) {
-            let gameEval = JSON.parse(messageEvent.data.gameEvalJson);
+            let gameEval = messageEvent.data.gameEval;
             updateStatus("waiting for player")
             resolve(gameEval);
         }
@@ -60,21 +59,31 @@ function evaluateGameEvalByPositionWorker(gameConfigStr, _possibleMoveArray, upd
     });
 }
 
-function evaluateGameEvalByMoveWorker(gameConfigStr, possibleMoveArray, updateStatus){
-    const nrOfMoves = possibleMoveArray.length;
-    let computedGameEvalJsonArray = []; // array of GameEvaluation
+async function evaluateGameEvalByMoveWorker(gameConfigStr, _possibleMoveArray, updateStatus){
+    if (hasNotBeenInitialized) {
+        await initPromise;
+        hasNotBeenInitialized = false;
+    }
+    // the position is split, evaluated and merged by the engine, the workers only evaluate single work items
+    let rootSplit = wasm.split_position(gameConfigStr);
+    if (rootSplit.result_type !== "WorkItems") {
+        return rootSplit;
+    }
+    let workItemArray = rootSplit.work_items;
+    const nrOfMoves = workItemArray.length;
+    let computedGameEvalArray = []; // array of GameEvaluation
     updateStatus(`0/${nrOfMoves}`);
     return new Promise(resolve => {
-        let workersToStart = Math.min(moveEvaluatorWorkerArray.length, possibleMoveArray.length);
-        console.log(`start ${workersToStart} workers to compute next move from ${possibleMoveArray.length} possible ones`);
+        let workersToStart = Math.min(moveEvaluatorWorkerArray.length, nrOfMoves);
+        console.log(`start ${workersToStart} workers to compute next move from ${nrOfMoves} possible ones`);
         moveEvaluatorWorkerArray.forEach((_, workerIndex)=>{
             if (workerIndex < workersToStart) {
                 startWorker(
                     gameConfigStr,
                     workerIndex,
                     nrOfMoves,
-                    possibleMoveArray,
-                    computedGameEvalJsonArray,
+                    workItemArray,
+                    computedGameEvalArray,
                     updateStatus,
                     resolve,
                 );
@@ -87,8 +96,8 @@ function evaluateGameEvalByMoveWorker(gameConfigStr, possibleMoveArray, updateSt
  * @param {string} gameConfigStr the game so far (moves divided by space)
  * @param {number} workerIndex
  * @param {number} nrOfMoves
- * @param {Array<string>} movesLeftToComputeArray
- * @param {Array<string>} computedGameEvalJsonArray
+ * @param {Array<WorkItem>} workItemsLeftToComputeArray
+ * @param {Array<GameEvaluation>} computedGameEvalArray
  * @param updateStatus invoke with a string to indicate progress
  * @param resolve resolves the main promise that is expected to return the next gameEval to play (or end the game)
  */
@@ -96,54 +105,43 @@ function startWorker(
     gameConfigStr,
     workerIndex,
     nrOfMoves,
-    movesLeftToComputeArray,
-    computedGameEvalJsonArray,
+    workItemsLeftToComputeArray,
+    computedGameEvalArray,
     updateStatus,
     resolve,
 ) {
-    let moveStr = movesLeftToComputeArray.pop();
-    console.log(`start worker ${workerIndex} on move ${moveStr}. (${movesLeftToComputeArray.length} other move(s) left to compute)`);
+    let workItem = workItemsLeftToComputeArray.pop();
+    let moveStr = workItem.move_to_evaluate;
+    console.log(`start worker ${workerIndex} on move ${moveStr}. (${workItemsLeftToComputeArray.length} other move(s) left to compute)`);
     let moveEvaluatorWorker = moveEvaluatorWorkerArray[workerIndex];
     moveEvaluatorWorker.onmessage = function (messageEvent) {
-        let gameEvalJson = messageEvent.data.gameEvalJson;
-        console.log(`result for move ${moveStr} -> eval: ${gameEvalJson}`);
-        computedGameEvalJsonArray.push(gameEvalJson);
-        let nrOfEvaluatedMoves = computedGameEvalJsonArray.length;
+        let gameEval = messageEvent.data.gameEval;
+        console.log(`result for move ${moveStr} -> eval: ${JSON.stringify(gameEval)}`);
+        computedGameEvalArray.push(gameEval);
+        let nrOfEvaluatedMoves = computedGameEvalArray.length;
         updateStatus(`${nrOfEvaluatedMoves}/${nrOfMoves}`)
-        // either start a new worker (if moves are left to compute) or select/resolve the move to play
-        if(movesLeftToComputeArray.length!==0) {
+        // either start a new worker (if moves are left to compute) or merge the evaluations into the move to play
+        if(workItemsLeftToComputeArray.length!==0) {
             startWorker(
                 gameConfigStr,
                 workerIndex,
                 nrOfMoves,
-                movesLeftToComputeArray,
-                computedGameEvalJsonArray,
+                workItemsLeftToComputeArray,
+                computedGameEvalArray,
                 updateStatus,
                 resolve,
             )
         } else {
             if(nrOfEvaluatedMoves===nrOfMoves) {
-                console.log(`pick one of these moves: ${computedGameEvalJsonArray}`)
-                pickMoveToPlay(computedGameEvalJsonArray).then(chosenGameEval => {
-                    resolve(chosenGameEval);
-                });
+                console.log(`pick one of these moves: ${JSON.stringify(computedGameEvalArray)}`)
+                let chosenGameEval = wasm.merge_evaluations(gameConfigStr, computedGameEvalArray);
+                console.log(`picked game eval: ${JSON.stringify(chosenGameEval)}`)
+                resolve(chosenGameEval);
             }
         }
     }
     moveEvaluatorWorker.postMessage({
-        "gameConfig": gameConfigStr,
-        "moveStr": moveStr,
+        "workItem": workItem,
         "workerIndex": workerIndex,
     });
 }
-
-async function pickMoveToPlay(gameEvalArray){
-    if (hasNotBeenInitialized) {
-        await initPromise;
-        hasNotBeenInitialized = false;
-    }
-    let gameEvalArrayStr = gameEvalArray.join('|');
-    let gameEvaluationStr = await wasm.pick_move_to_play(gameEvalArrayStr);
-    console.log(`picked game eval: ${gameEvaluationStr}`)
-    return JSON.parse(gameEvaluationStr);
-}
@@ -0,0 +1,44 @@
+use std::hint::black_box;
+use criterion::{criterion_group, criterion_main, Criterion};
+use engine_core::base::{Color, Move};
+use engine_core::engine::static_eval::{static_eval, StaticEvalType};
+use engine_core::game::{Game, MoveResult};
+
+const MIDDLEGAME_FEN: &str = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+
+fn middlegame() -> Game {
+    MIDDLEGAME_FEN.parse::<Game>().unwrap()
+}
+
+fn bench_get_reachable_moves(c: &mut Criterion) {
+    let game_state = middlegame().get_game_state().clone();
+    c.bench_function("get_reachable_moves", |b| b.iter(|| black_box(&game_state).get_reachable_moves()));
+}
+
+fn bench_play(c: &mut Criterion) {
+    let game = middlegame();
+    let a_move = Move::from_code("e5-f7");
+    c.bench_function("Game::play", |b| b.iter(|| {
+        match black_box(&game).play(black_box(a_move)) {
+            MoveResult::Ongoing(new_game, _) => new_game.get_reachable_moves().len(),
+            MoveResult::Stopped(_, _) => 0,
+        }
+    }));
+}
+
+fn bench_is_active_king_checkmate(c: &mut Criterion) {
+    // the scholar's mate, all escapes have to be tried before it's clear that there is none
+    let scholars_mate = "r1bqkbnr/pppp1ppp/2n5/4p2Q/2B1P3/8/PPPP1PPP/RNB1K1NR w KQkq - 4 4".parse::<Game>().unwrap();
+    let MoveResult::Ongoing(checked_game, _) = scholars_mate.play(Move::from_code("h5-f7")) else {
+        panic!("Qxf7# should be playable");
+    };
+    c.bench_function("is_active_king_checkmate", |b| b.iter(|| black_box(&checked_game).is_active_king_checkmate()));
+}
+
+fn bench_static_eval(c: &mut Criterion) {
+    let game_state = middlegame().get_game_state().clone();
+    c.bench_function("static_eval", |b| b.iter(|| static_eval(black_box(&game_state), StaticEvalType::Default, Color::White)));
+}
+
+criterion_group!(benches, bench_get_reachable_moves, bench_play, bench_is_active_king_checkmate, bench_static_eval);
+criterion_main!(benches);
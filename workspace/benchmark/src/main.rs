@@ -0,0 +1,100 @@
+use std::time::{Duration, Instant};
+use engine_core::engine::evaluate_game_with_config;
+use engine_core::engine::evaluations::frontend::GameEvaluation;
+use engine_core::engine::min_max::get_nodes_searched;
+use engine_core::engine::min_max::pruner::{Pruner, PRUNER_1_2_3_4};
+use engine_core::engine::search_config::{MoveSelection, SearchConfig};
+use engine_core::game::Game;
+
+/**
+ * a mix of opening, middlegame and endgame positions. Changing them changes the signature!
+ */
+const BENCH_POSITIONS: [&str; 8] = [
+    "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+    "r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3",
+    "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+    "r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10",
+    "2rr3k/pp3pp1/1nnqbN1p/3pN3/2pP4/2P3Q1/PPB4P/R4RK1 w - - 0 1",
+    "r1bq1rk1/ppp2ppp/2np1n2/2b1p3/2B1P3/2NP1N2/PPP2PPP/R1BQ1RK1 b - - 0 7",
+    "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+    "8/5pk1/6p1/8/8/6P1/5PK1/3R4 w - - 0 1",
+];
+
+/**
+ * the settings of the benchmark. Changing them changes the signature!
+ */
+const BENCH_PRUNER: Pruner = PRUNER_1_2_3_4;
+
+struct PositionBench {
+    fen: &'static str,
+    evaluation: GameEvaluation,
+    nodes: u64,
+    duration: Duration,
+}
+
+/**
+ * searches the positions one after the other (single threaded and deterministic, so the number of nodes is reproducible)
+ */
+fn run_bench(positions: &[&'static str], pruner: Pruner) -> Vec<PositionBench> {
+    let config = SearchConfig::new(pruner).with_move_selection(MoveSelection::Deterministic);
+    positions.iter().map(|fen| {
+        let game = fen.parse::<Game>().unwrap_or_else(|err| panic!("invalid bench position {}: {}", fen, err));
+        let nodes_before = get_nodes_searched();
+        let start = Instant::now();
+        let evaluation = evaluate_game_with_config(&game, &config);
+        let duration = start.elapsed();
+        PositionBench { fen, evaluation, nodes: get_nodes_searched() - nodes_before, duration }
+    }).collect()
+}
+
+fn nodes_per_second(nodes: u64, duration: Duration) -> u64 {
+    (nodes as f64 / duration.as_secs_f64().max(f64::EPSILON)) as u64
+}
+
+fn main() {
+    println!("searching {} positions with {:?}", BENCH_POSITIONS.len(), BENCH_PRUNER);
+    let position_benches = run_bench(&BENCH_POSITIONS, BENCH_PRUNER);
+    for (index, position_bench) in position_benches.iter().enumerate() {
+        let move_played = match &position_bench.evaluation {
+            GameEvaluation::MoveToPlay(a_move, _) => a_move.to_string(),
+            other => format!("{:?}", other),
+        };
+        println!(
+            "position {}/{}: {} nodes in {:.3}s ({} nps), plays {} in {}",
+            index + 1, position_benches.len(), position_bench.nodes, position_bench.duration.as_secs_f64(),
+            nodes_per_second(position_bench.nodes, position_bench.duration), move_played, position_bench.fen,
+        );
+    }
+    let total_nodes: u64 = position_benches.iter().map(|position_bench| position_bench.nodes).sum();
+    let total_duration: Duration = position_benches.iter().map(|position_bench| position_bench.duration).sum();
+    println!("\ntotal time: {:.3}s", total_duration.as_secs_f64());
+    println!("nodes searched: {}", total_nodes);
+    println!("nodes per second: {}", nodes_per_second(total_nodes, total_duration));
+    // like the bench of other engines: the node count only changes if the search does, so it identifies a version
+    println!("signature: {}", total_nodes);
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use engine_core::engine::min_max::pruner::PRUNER_L1;
+
+    #[test]
+    fn test_bench_positions_are_valid() {
+        for fen in BENCH_POSITIONS {
+            assert!(fen.parse::<Game>().is_ok(), "{}", fen);
+        }
+    }
+
+    #[test]
+    fn test_signature_is_reproducible() {
+        let count_nodes = || -> Vec<u64> {
+            run_bench(&BENCH_POSITIONS, PRUNER_L1).iter().map(|position_bench| position_bench.nodes).collect()
+        };
+        let nodes = count_nodes();
+        assert!(nodes.iter().all(|position_nodes| *position_nodes > 0), "{:?}", nodes);
+        assert_eq!(count_nodes(), nodes);
+    }
+}
@@ -0,0 +1,182 @@
+use engine_core::base::Color;
+use engine_core::engine::evaluate_game_with_config;
+use engine_core::engine::evaluations::frontend::{GameEvaluation, MoveEvaluation};
+use engine_core::engine::search_config::{MoveSelection, SearchConfig};
+use engine_core::engine::skill::SkillLevel;
+use engine_core::figure::functions::allowed::get_allowed_moves_of;
+use engine_core::game::{perft_divide, Game, MoveResult, StoppedReason};
+
+/**
+ * the game of a fen, moves like "e2-e4 e7-e5" or figures like "white ♔e1 ♚e8 ♖a1"
+ */
+pub fn parse_game(game_config: &str) -> Result<Game, String> {
+    game_config.parse::<Game>().map_err(|err| format!("couldn't read the game {}: {}", game_config, err))
+}
+
+/**
+ * None if the active color can still move
+ */
+pub fn get_game_end(game: &Game) -> Option<String> {
+    if !get_allowed_moves_of(game).is_empty() {
+        return None;
+    }
+    let active_color = game.get_game_state().turn_by;
+    if game.is_active_king_in_check() {
+        Some(format!("checkmate, {} wins", active_color.toggle()))
+    } else {
+        Some("draw by stalemate".to_string())
+    }
+}
+
+pub fn describe_stopped_reason(stopped_reason: StoppedReason) -> String {
+    let description = match stopped_reason {
+        StoppedReason::KingInCheckAfterMove => "the move would leave the own king in check",
+        StoppedReason::InsufficientMaterial => "draw by insufficient material",
+        StoppedReason::ThreeTimesRepetition => "draw by three-fold repetition",
+        StoppedReason::NoChangeIn50Moves => "draw because of no progress in 50 moves",
+    };
+    description.to_string()
+}
+
+/**
+ * a move evaluation is from the point of view of the color to move
+ */
+pub fn describe_evaluation(evaluation: MoveEvaluation, active_color: Color) -> String {
+    match evaluation {
+        MoveEvaluation::EngineCheckMatesIn(full_moves) => format!("{} mates in {}", active_color, full_moves + 1),
+        MoveEvaluation::EngineGetsCheckMatedIn(full_moves, _) => format!("{} mates in {}", active_color.toggle(), full_moves),
+        MoveEvaluation::Draw(draw_reason) => format!("draw ({:?})", draw_reason),
+        MoveEvaluation::Numeric(pawns) => {
+            let (favored_color, pawns) = if pawns >= 0.0 { (active_color, pawns) } else { (active_color.toggle(), -pawns) };
+            format!("{:.2} pawns better for {}", pawns, favored_color)
+        }
+    }
+}
+
+/**
+ * the evaluation of the position and the best line of play of the given number of half moves
+ */
+pub fn analyse(game_config: &str, skill_level: SkillLevel, line_length: usize) -> Result<String, String> {
+    let game = parse_game(game_config)?;
+    if let Some(game_end) = get_game_end(&game) {
+        return Ok(format!("the game is over: {}", game_end));
+    }
+    let config = SearchConfig::new(skill_level.get_pruner()).with_move_selection(MoveSelection::Deterministic);
+    let active_color = game.get_game_state().turn_by;
+    let (best_move, evaluation) = match evaluate_game_with_config(&game, &config) {
+        GameEvaluation::MoveToPlay(best_move, evaluation) => (best_move, evaluation),
+        GameEvaluation::GameEnded(game_end_result) => { return Ok(format!("the game is over: {:?}", game_end_result)); }
+        GameEvaluation::Err(err) => { return Err(err.to_string()); }
+    };
+
+    let mut line = vec![best_move];
+    let mut current_game = game;
+    while line.len() < line_length {
+        let latest_move = *line.last().expect("the line starts with the best move");
+        let MoveResult::Ongoing(next_game, _) = current_game.play(latest_move) else {
+            break;
+        };
+        current_game = *next_game;
+        if get_game_end(&current_game).is_some() {
+            break;
+        }
+        match evaluate_game_with_config(&current_game, &config) {
+            GameEvaluation::MoveToPlay(next_move, _) => line.push(next_move),
+            _ => break,
+        }
+    }
+    let line: Vec<String> = line.iter().map(|a_move| a_move.to_string()).collect();
+    Ok(format!(
+        "best move: {}\nevaluation: {}\nbest line: {}",
+        best_move,
+        describe_evaluation(evaluation, active_color),
+        line.join(" "),
+    ))
+}
+
+pub fn list_legal_moves(game_config: &str) -> Result<String, String> {
+    let game = parse_game(game_config)?;
+    let moves: Vec<String> = get_allowed_moves_of(&game).iter().map(|a_move| a_move.to_string()).collect();
+    Ok(moves.join(" "))
+}
+
+pub fn get_fen(game_config: &str) -> Result<String, String> {
+    parse_game(game_config).map(|game| game.get_fen())
+}
+
+/**
+ * the perft numbers per first move followed by the total
+ */
+pub fn perft_report(game_config: &str, depth: usize) -> Result<(String, u64), String> {
+    let game = parse_game(game_config)?;
+    let divided = perft_divide(game.get_game_state(), depth);
+    let total_nodes: u64 = divided.iter().map(|(_, nodes)| nodes).sum();
+    let mut report: Vec<String> = divided.iter().map(|(a_move, nodes)| format!("{}: {}", a_move, nodes)).collect();
+    report.push(format!("total: {}", total_nodes));
+    Ok((report.join("\n"), total_nodes))
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    //♔♕♗♘♖♙♚♛♝♞♜♟
+
+    #[rstest(
+    game_config, expected_opt_end,
+    case("", None),
+    case("black ♔b6 ♙a7 ♚a8", Some("draw by stalemate")),
+    case("f2-f3 e7-e5 g2-g4 d8-h4", Some("checkmate, black wins")),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_get_game_end(game_config: &str, expected_opt_end: Option<&str>) {
+        let game = parse_game(game_config).unwrap();
+        assert_eq!(get_game_end(&game).as_deref(), expected_opt_end);
+    }
+
+    #[rstest(
+    evaluation, active_color, expected_description,
+    case(MoveEvaluation::EngineCheckMatesIn(0), Color::White, "white mates in 1"),
+    case(MoveEvaluation::EngineGetsCheckMatedIn(2, 0.0), Color::White, "black mates in 2"),
+    case(MoveEvaluation::Numeric(0.5), Color::White, "0.50 pawns better for white"),
+    case(MoveEvaluation::Numeric(0.5), Color::Black, "0.50 pawns better for black"),
+    case(MoveEvaluation::Numeric(-0.25), Color::Black, "0.25 pawns better for white"),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_describe_evaluation(evaluation: MoveEvaluation, active_color: Color, expected_description: &str) {
+        assert_eq!(describe_evaluation(evaluation, active_color), expected_description);
+    }
+
+    #[test]
+    fn test_analyse_finds_the_mate() {
+        let analysis = analyse("white ♔g3 ♖d2 ♚g1 ♙c2 ♙d3", SkillLevel::new(5).unwrap(), 3).unwrap();
+        assert_eq!(analysis, "best move: d2-d1\nevaluation: white mates in 1\nbest line: d2-d1");
+    }
+
+    #[rstest(
+    game_config, expected_moves,
+    case("white ♔e1 ♖h1 ♚g8 ♝c3", "e1-e2 e1-f2 e1-f1 e1-d1"),
+    case("black ♔b6 ♙a7 ♚a8", ""),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_list_legal_moves(game_config: &str, expected_moves: &str) {
+        assert_eq!(list_legal_moves(game_config).unwrap(), expected_moves);
+    }
+
+    #[test]
+    fn test_get_fen() {
+        assert_eq!(get_fen("e2-e4").unwrap(), "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1");
+        assert!(get_fen("e2-e5").is_err());
+    }
+
+    #[test]
+    fn test_perft_report() {
+        let (report, total_nodes) = perft_report("", 2).unwrap();
+        assert_eq!(total_nodes, 400);
+        assert_eq!(report.lines().count(), 21);
+        assert_eq!(report.lines().last(), Some("total: 400"));
+    }
+}
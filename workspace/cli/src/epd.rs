@@ -0,0 +1,149 @@
+use std::io::Write;
+use engine_core::engine::epd_suite::solve_epd_record;
+use engine_core::engine::evaluations::frontend::GameEvaluation;
+use engine_core::engine::limits::SearchLimits;
+use engine_core::engine::min_max::pruner::Pruner;
+use engine_core::engine::search_config::{MoveSelection, SearchConfig};
+use engine_core::game::{to_san, EpdRecord};
+use crate::analysis::describe_evaluation;
+
+/**
+ * the search goes depth full moves deep, one more after checks and two more after captures
+ */
+pub fn get_epd_search_config(depth: usize) -> SearchConfig {
+    SearchConfig::new(Pruner::new(depth, depth, depth + 1, depth + 2)).with_move_selection(MoveSelection::Deterministic)
+}
+
+/**
+ * one epd per line, empty lines and lines starting with # are ignored.
+ * A line that can't be read is an error for that position only, so that the rest of the suite can still run.
+ */
+pub fn parse_epd_records(epd_text: &str) -> Vec<Result<EpdRecord, String>> {
+    epd_text.lines().enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.trim().starts_with('#'))
+        .map(|(line_index, line)| {
+            line.parse::<EpdRecord>().map_err(|err| format!("couldn't read line {}: {}", line_index + 1, err.msg))
+        })
+        .collect()
+}
+
+/**
+ * writes a line per position as soon as it's searched (or skipped, if it couldn't be read) and the overall score at the end
+ */
+pub fn run_epd_records<W: Write>(records: &[Result<EpdRecord, String>], config: &SearchConfig, limits: SearchLimits, output: &mut W) -> Result<(), String> {
+    let mut nr_solved = 0;
+    let mut nr_skipped = 0;
+    let mut total_seconds = 0.0;
+    for (record_index, record_or_err) in records.iter().enumerate() {
+        let record = match record_or_err {
+            Ok(record) => record,
+            Err(err) => {
+                nr_skipped += 1;
+                writeln!(output, "position {}: skipped, {}", record_index + 1, err).map_err(to_msg)?;
+                continue;
+            }
+        };
+        let result = solve_epd_record(record, config, limits);
+        let id = record.opt_id.clone().unwrap_or_else(|| format!("position {}", record_index + 1));
+        let engine_answer = match &result.evaluation {
+            GameEvaluation::MoveToPlay(a_move, evaluation) => {
+                format!("played {} ({})", to_san(&record.game, *a_move), describe_evaluation(*evaluation, record.game.get_game_state().turn_by))
+            }
+            GameEvaluation::GameEnded(game_end_result) => format!("no move, the game is over: {:?}", game_end_result),
+            GameEvaluation::Err(err) => format!("no move: {}", err),
+        };
+        if result.is_solved {
+            nr_solved += 1;
+        }
+        total_seconds += result.duration.as_secs_f64();
+        writeln!(
+            output,
+            "{}: {}, {}, expected {} in {:.2}s",
+            id, if result.is_solved { "solved" } else { "failed" }, engine_answer, describe_expectation(record), result.duration.as_secs_f64(),
+        ).map_err(to_msg)?;
+    }
+    let nr_searched = records.len() - nr_skipped;
+    let percentage = if nr_searched == 0 { 0.0 } else { nr_solved as f64 * 100.0 / nr_searched as f64 };
+    write!(output, "solved {} of {} positions ({:.1}%) in {:.2}s", nr_solved, nr_searched, percentage, total_seconds).map_err(to_msg)?;
+    if nr_skipped > 0 {
+        write!(output, ", skipped {} unreadable positions", nr_skipped).map_err(to_msg)?;
+    }
+    writeln!(output).map_err(to_msg)?;
+    Ok(())
+}
+
+fn describe_expectation(record: &EpdRecord) -> String {
+    let to_sans = |moves: &[_]| moves.iter().map(|a_move| to_san(&record.game, *a_move)).collect::<Vec<String>>().join(" ");
+    let mut expectations: Vec<String> = vec![];
+    if !record.best_moves.is_empty() {
+        expectations.push(format!("bm {}", to_sans(&record.best_moves)));
+    }
+    if !record.avoid_moves.is_empty() {
+        expectations.push(format!("am {}", to_sans(&record.avoid_moves)));
+    }
+    if let Some(mate_in) = record.opt_mate_in {
+        expectations.push(format!("dm {}", mate_in));
+    }
+    if expectations.is_empty() {
+        "nothing (no bm, am or dm given)".to_string()
+    } else {
+        expectations.join("; ")
+    }
+}
+
+fn to_msg(err: std::io::Error) -> String {
+    format!("couldn't write the results: {}", err)
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    const SUITE: &str = "# a small suite
+6k1/5ppp/8/8/8/8/5PPP/R5K1 w - - bm Ra8; id \"back rank\";
+
+r5k1/5ppp/8/8/8/8/5PPP/6K1 b - - am Ra2; dm 1;
+6k1/5ppp/8/8/8/8/5PPP/R5K1 w - - bm Kf1;
+";
+
+    #[rstest(
+    epd_text, expected_readable,
+    case(SUITE, vec![true, true, true]),
+    case("", vec![]),
+    case("6k1/5ppp/8/8/8/8/5PPP/R5K1 w - - bm Ra8;\n6k1/5ppp/8/8/8/8/5PPP/R5K1 w - - bm Ra9;", vec![true, false]),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_parse_epd_records(epd_text: &str, expected_readable: Vec<bool>) {
+        let readable: Vec<bool> = parse_epd_records(epd_text).iter().map(Result::is_ok).collect();
+        assert_eq!(readable, expected_readable);
+    }
+
+    #[test]
+    fn test_run_epd_records_skips_unreadable_records() {
+        let records = parse_epd_records("6k1/5ppp/8/8/8/8/5PPP/R5K1 w - - bm Ra9;\n6k1/5ppp/8/8/8/8/5PPP/R5K1 w - - bm Ra8;");
+        let mut output: Vec<u8> = vec![];
+        run_epd_records(&records, &get_epd_search_config(1), SearchLimits::default(), &mut output).unwrap();
+        let lines: Vec<String> = String::from_utf8(output).unwrap().lines().map(str::to_string).collect();
+        assert_eq!(lines.len(), 3, "{:?}", lines);
+        assert!(lines[0].starts_with("position 1: skipped, couldn't read line 1: "), "{}", lines[0]);
+        assert!(lines[1].starts_with("position 2: solved"), "{}", lines[1]);
+        assert!(lines[2].starts_with("solved 1 of 1 positions (100.0%) in "), "{}", lines[2]);
+        assert!(lines[2].ends_with(", skipped 1 unreadable positions"), "{}", lines[2]);
+    }
+
+    #[test]
+    fn test_run_epd_records() {
+        let records = parse_epd_records(SUITE);
+        let mut output: Vec<u8> = vec![];
+        run_epd_records(&records, &get_epd_search_config(1), SearchLimits::default(), &mut output).unwrap();
+        let lines: Vec<String> = String::from_utf8(output).unwrap().lines().map(str::to_string).collect();
+        assert_eq!(lines.len(), 4, "{:?}", lines);
+        assert!(lines[0].starts_with("back rank: solved, played Ra8# (white mates in 1), expected bm Ra8# in "), "{}", lines[0]);
+        assert!(lines[1].starts_with("position 2: solved, played Ra1# (black mates in 1), expected am Ra2; dm 1 in "), "{}", lines[1]);
+        assert!(lines[2].starts_with("position 3: failed, played Ra8#"), "{}", lines[2]);
+        assert!(lines[3].starts_with("solved 2 of 3 positions (66.7%) in "), "{}", lines[3]);
+    }
+}
@@ -0,0 +1,149 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+use engine_core::base::Color;
+use engine_core::engine::limits::SearchLimits;
+use engine_core::engine::skill::SkillLevel;
+use crate::analysis::{analyse, get_fen, list_legal_moves, perft_report};
+use crate::epd::{get_epd_search_config, parse_epd_records, run_epd_records};
+use crate::play::{play, PlaySettings};
+
+mod analysis;
+mod epd;
+mod play;
+
+const USAGE: &str = "usage: voidchess <command> [<options>]
+
+commands:
+  play [<game>] [--black] [--skill <1-20>]       play against the engine (as white unless --black is given)
+  analyse <game> [--skill <1-20>] [--line <n>]   the evaluation and the best line of n half moves (default 4)
+  legal <game>                                   the legal moves
+  fen <game>                                     the fen of the game
+  perft <depth> [<game>]                         the number of legal move sequences of the given length
+  epd <file> [--depth <n>] [--time <ms>] [--nodes <n>]
+                                                 runs a test suite of epd positions (with bm, am or dm operations),
+                                                 searching n full moves deep (default 2) or less if a limit is reached
+
+A game is a fen, moves from the starting position (\"e2-e4 e7-e5\") or figures (\"white ♔e1 ♚e8 ♖a1\").
+Without a game the starting position is used.";
+
+const DEFAULT_LINE_LENGTH: usize = 4;
+const DEFAULT_EPD_DEPTH: usize = 2;
+
+enum Command {
+    Play(PlaySettings),
+    Analyse { game_config: String, skill_level: SkillLevel, line_length: usize },
+    Legal { game_config: String },
+    Fen { game_config: String },
+    Perft { game_config: String, depth: usize },
+    Epd { path: String, depth: usize, limits: SearchLimits },
+}
+
+fn parse_args(args: &[String]) -> Result<Command, String> {
+    let Some((command, command_args)) = args.split_first() else {
+        return Err("no command given".to_string());
+    };
+
+    let mut game_config_parts: Vec<&str> = vec![];
+    let mut human_color = Color::White;
+    let mut skill_level = SkillLevel::strongest();
+    let mut line_length = DEFAULT_LINE_LENGTH;
+    let mut epd_depth = DEFAULT_EPD_DEPTH;
+    let mut limits = SearchLimits::default();
+    let mut arg_iter = command_args.iter();
+    while let Some(arg) = arg_iter.next() {
+        let mut next_value = |option: &str| arg_iter.next().cloned().ok_or_else(|| format!("missing value for {}", option));
+        match arg.as_str() {
+            "--black" => { human_color = Color::Black; }
+            "--skill" => { skill_level = next_value("--skill")?.parse::<SkillLevel>().map_err(|err| err.msg)?; }
+            "--line" => {
+                let line_str = next_value("--line")?;
+                line_length = line_str.parse::<usize>().map_err(|_| format!("line has to be a positive number but was {}", line_str))?;
+            }
+            "--depth" => {
+                let depth_str = next_value("--depth")?;
+                epd_depth = depth_str.parse::<usize>().map_err(|_| format!("depth has to be a positive number but was {}", depth_str))?;
+            }
+            "--time" => {
+                let time_str = next_value("--time")?;
+                let millis = time_str.parse::<u64>().map_err(|_| format!("time has to be a positive number of milliseconds but was {}", time_str))?;
+                limits = limits.with_time_limit(Duration::from_millis(millis));
+            }
+            "--nodes" => {
+                let nodes_str = next_value("--nodes")?;
+                limits = limits.with_node_limit(nodes_str.parse::<u64>().map_err(|_| format!("nodes has to be a positive number but was {}", nodes_str))?);
+            }
+            _ if arg.starts_with("--") => { return Err(format!("unknown option {}", arg)); }
+            // the moves of a game can be given as separate arguments
+            _ => { game_config_parts.push(arg); }
+        }
+    }
+    let game_config = game_config_parts.join(" ");
+
+    match command.as_str() {
+        "play" => Ok(Command::Play(PlaySettings { game_config, human_color, skill_level })),
+        "analyse" | "analyze" => Ok(Command::Analyse { game_config, skill_level, line_length }),
+        "legal" => Ok(Command::Legal { game_config }),
+        "fen" => Ok(Command::Fen { game_config }),
+        "perft" => {
+            let (depth_str, game_config) = game_config.split_once(' ').unwrap_or((game_config.as_str(), ""));
+            let depth = depth_str.parse::<usize>().map_err(|_| format!("depth has to be a positive number but was {}", depth_str))?;
+            Ok(Command::Perft { game_config: game_config.to_string(), depth })
+        }
+        "epd" => Ok(Command::Epd { path: game_config, depth: epd_depth, limits }),
+        _ => Err(format!("unknown command {}", command)),
+    }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let command = match parse_args(&args) {
+        Ok(command) => command,
+        Err(msg) => {
+            eprintln!("{}\n\n{}", msg, USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match run(command) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(msg) => {
+            eprintln!("{}", msg);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(command: Command) -> Result<(), String> {
+    match command {
+        Command::Play(settings) => {
+            play(&settings, &mut io::stdin().lock(), &mut io::stdout())
+        }
+        Command::Analyse { game_config, skill_level, line_length } => {
+            println!("{}", analyse(&game_config, skill_level, line_length)?);
+            Ok(())
+        }
+        Command::Legal { game_config } => {
+            println!("{}", list_legal_moves(&game_config)?);
+            Ok(())
+        }
+        Command::Fen { game_config } => {
+            println!("{}", get_fen(&game_config)?);
+            Ok(())
+        }
+        Command::Perft { game_config, depth } => {
+            let start = Instant::now();
+            let (report, total_nodes) = perft_report(&game_config, depth)?;
+            let seconds = start.elapsed().as_secs_f64();
+            println!("{}\n{:.3}s ({:.0} nodes per second)", report, seconds, total_nodes as f64 / seconds.max(f64::EPSILON));
+            Ok(())
+        }
+        Command::Epd { path, depth, limits } => {
+            let epd_text = fs::read_to_string(&path).map_err(|err| format!("couldn't read {}: {}", path, err))?;
+            let records = parse_epd_records(&epd_text);
+            run_epd_records(&records, &get_epd_search_config(depth), limits, &mut io::stdout())
+        }
+    }
+}
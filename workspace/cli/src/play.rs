@@ -0,0 +1,137 @@
+use std::io::{BufRead, Write};
+use engine_core::base::{Color, Move};
+use engine_core::engine::evaluations::frontend::GameEvaluation;
+use engine_core::engine::search_config::MoveSelection;
+use engine_core::engine::skill::{evaluate_game_with_skill, SkillLevel};
+use engine_core::figure::functions::allowed::get_allowed_moves_of;
+use engine_core::game::{Game, MoveResult};
+use crate::analysis::{describe_evaluation, describe_stopped_reason, get_game_end, parse_game};
+
+pub struct PlaySettings {
+    pub game_config: String,
+    pub human_color: Color,
+    pub skill_level: SkillLevel,
+}
+
+/**
+ * a game of a human (reading moves from input) against the engine until the game ends or the human quits
+ */
+pub fn play<R: BufRead, W: Write>(settings: &PlaySettings, input: &mut R, output: &mut W) -> Result<(), String> {
+    let mut game = parse_game(&settings.game_config)?;
+    writeln!(
+        output,
+        "you play {} against skill level {} (about {} Elo). Enter moves like e2-e4, \"moves\" to list the legal ones, \"fen\" or \"quit\".",
+        settings.human_color, settings.skill_level, settings.skill_level.get_elo(),
+    ).map_err(to_msg)?;
+    loop {
+        writeln!(output, "{}", game.get_game_state().board).map_err(to_msg)?;
+        if let Some(game_end) = get_game_end(&game) {
+            writeln!(output, "{}", game_end).map_err(to_msg)?;
+            return Ok(());
+        }
+        let active_color = game.get_game_state().turn_by;
+        let next_move = if active_color == settings.human_color {
+            match read_human_move(&game, input, output)? {
+                Some(human_move) => human_move,
+                None => { return Ok(()); }
+            }
+        } else {
+            let skill_level = settings.skill_level.with_time_limit(settings.skill_level.get_suggested_time_limit());
+            match evaluate_game_with_skill(&game, skill_level, MoveSelection::Random) {
+                GameEvaluation::MoveToPlay(engine_move, evaluation) => {
+                    writeln!(output, "engine plays {} ({})", engine_move, describe_evaluation(evaluation, active_color)).map_err(to_msg)?;
+                    engine_move
+                }
+                GameEvaluation::GameEnded(game_end_result) => {
+                    writeln!(output, "the game is over: {:?}", game_end_result).map_err(to_msg)?;
+                    return Ok(());
+                }
+                GameEvaluation::Err(err) => { return Err(err.to_string()); }
+            }
+        };
+        match game.play(next_move) {
+            MoveResult::Ongoing(next_game, _) => { game = *next_game; }
+            MoveResult::Stopped(stopped_reason, game_state) => {
+                writeln!(output, "{}\n{}", game_state.board, describe_stopped_reason(stopped_reason)).map_err(to_msg)?;
+                return Ok(());
+            }
+        }
+    }
+}
+
+/**
+ * None if the human quits (or the input ends)
+ */
+fn read_human_move<R: BufRead, W: Write>(game: &Game, input: &mut R, output: &mut W) -> Result<Option<Move>, String> {
+    let legal_moves = get_allowed_moves_of(game);
+    loop {
+        write!(output, "{} to move> ", game.get_game_state().turn_by).map_err(to_msg)?;
+        output.flush().map_err(to_msg)?;
+        let mut line = String::new();
+        if input.read_line(&mut line).map_err(to_msg)? == 0 {
+            return Ok(None);
+        }
+        match line.trim() {
+            "" => {}
+            "quit" | "exit" => { return Ok(None); }
+            "moves" => {
+                let moves: Vec<String> = legal_moves.iter().map(|a_move| a_move.to_string()).collect();
+                writeln!(output, "{}", moves.join(" ")).map_err(to_msg)?;
+            }
+            "fen" => { writeln!(output, "{}", game.get_fen()).map_err(to_msg)?; }
+            move_str => match move_str.parse::<Move>() {
+                Ok(human_move) if legal_moves.contains(&human_move) => { return Ok(Some(human_move)); }
+                Ok(human_move) => { writeln!(output, "{} isn't a legal move (enter \"moves\" to list them)", human_move).map_err(to_msg)?; }
+                Err(err) => { writeln!(output, "{}", err.msg).map_err(to_msg)?; }
+            },
+        }
+    }
+}
+
+fn to_msg(err: std::io::Error) -> String {
+    format!("couldn't read or write the terminal: {}", err)
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    //♔♕♗♘♖♙♚♛♝♞♜♟
+
+    #[rstest(
+    game_config, human_input, expected_last_line,
+    case("white ♔g3 ♖d2 ♚g1 ♙c2 ♙d3", "d2-d8\nd2-d1\n", "checkmate, white wins"),
+    case("white ♔g3 ♖d2 ♚g1 ♙c2 ♙d3", "moves\nquit\n", "white to move> "),
+    case("white ♔g3 ♖d2 ♚g1 ♙c2 ♙d3", "", "white to move> "),
+    case("black ♔b6 ♙a7 ♚a8", "", "draw by stalemate"),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_play(game_config: &str, human_input: &str, expected_last_line: &str) {
+        let settings = PlaySettings {
+            game_config: game_config.to_string(),
+            human_color: Color::White,
+            skill_level: SkillLevel::new(1).unwrap(),
+        };
+        let mut output: Vec<u8> = Vec::new();
+        play(&settings, &mut human_input.as_bytes(), &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(output.lines().last(), Some(expected_last_line), "{}", output);
+    }
+
+    #[test]
+    fn test_play_rejects_illegal_moves() {
+        let settings = PlaySettings {
+            game_config: "white ♔g3 ♖d2 ♚g1 ♙c2 ♙d3".to_string(),
+            human_color: Color::White,
+            skill_level: SkillLevel::new(1).unwrap(),
+        };
+        let mut output: Vec<u8> = Vec::new();
+        play(&settings, &mut "d2-e3\ne2e4\nmoves\n".as_bytes(), &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("d2-e3 isn't a legal move"), "{}", output);
+        assert!(output.contains("d2-d1"), "{}", output);
+    }
+}
@@ -21,8 +21,9 @@ impl Hash for Move {
 
 impl Move {
     fn combine_state(from_index: usize, to_index: usize, move_type_state: usize) -> Move {
+        // 6 bits for the from-index, 6 bits for the to-index and 3 bits for the move type
         Move {
-            state: (((move_type_state << 3) | to_index) << 6) | from_index
+            state: (((move_type_state << 6) | to_index) << 6) | from_index
         }
     }
 
@@ -60,6 +61,12 @@ impl str::FromStr for Move {
     type Err = ChessError;
 
     fn from_str(code: &str) -> Result<Self, Self::Err> {
+        if code.len() != 5 || !code.is_ascii() {
+            return Err(ChessError {
+                msg: format!("a move has the format e2-e4 (or e.g. a7Qa8, e1cg1) but was {}", code),
+                kind: ErrorKind::IllegalFormat,
+            });
+        }
         Ok(Move::new (
             code[0..2].parse::<Position>()?,
             code[3..5].parse::<Position>()?,
@@ -119,6 +126,27 @@ impl tinyvec::Array for MoveArray {
 
 pub type Moves = TinyVec<MoveArray>;
 
+/**
+ * finds the reachable move going from from to to (castling moves go from the king to its destination, e.g. e1cg1)
+ * with the given pawn promotion, e.g. to map moves in the notation of another library or file format.
+ */
+pub(crate) fn find_reachable_move(
+    reachable_moves: &[Move],
+    from: Position,
+    to: Position,
+    opt_promotion_type: Option<PromotionType>,
+) -> Option<Move> {
+    reachable_moves.iter().find(|a_move| {
+        if a_move.from() != from || a_move.to() != to {
+            return false;
+        }
+        match a_move.move_type() {
+            MoveType::PawnPromotion(promotion_type) => opt_promotion_type == Some(promotion_type),
+            _ => opt_promotion_type.is_none(),
+        }
+    }).copied()
+}
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum PromotionType {
     Rook,
@@ -269,6 +297,30 @@ mod tests {
         assert_eq!(a_move.move_type(), MoveType::PawnPromotion(PromotionType::Bishop), "move_type");
     }
 
+    #[rstest(
+    from_str, to_str, move_type, other_to_str, other_move_type,
+    // the to-index used to overlap with the move type, so these moves had the same state
+    case("b2", "a2", MoveType::Normal, "a1", MoveType::PawnPromotion(PromotionType::Rook)),
+    case("b7", "b8", MoveType::Normal, "b8", MoveType::PawnPromotion(PromotionType::Knight)),
+    case("e1", "g7", MoveType::Normal, "g1", MoveType::Castling(CastlingType::KingSide)),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_moves_with_different_to_or_move_type_differ(
+        from_str: &str,
+        to_str: &str,
+        move_type: MoveType,
+        other_to_str: &str,
+        other_move_type: MoveType,
+    ) {
+        let from = Position::from_code(from_str);
+        let a_move = Move::new(from, Position::from_code(to_str), Some(move_type));
+        let other_move = Move::new(from, Position::from_code(other_to_str), Some(other_move_type));
+        assert_ne!(a_move, other_move);
+        assert_eq!((other_move.to(), other_move.move_type()), (Position::from_code(other_to_str), other_move_type));
+        let moves: std::collections::HashSet<Move> = [a_move, other_move].into_iter().collect();
+        assert_eq!(moves.len(), 2);
+    }
+
     #[test]
     fn test_move_new_and_getter() {
         let a_move = Move::new(
@@ -1,7 +1,7 @@
 use std::fmt::*;
 use crate::game::StoppedReason;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Eq, PartialEq)]
 pub struct ChessError {
     pub msg: String,
     pub kind: ErrorKind,
@@ -13,7 +13,7 @@ impl Display for ChessError {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum ErrorKind {
     HighLevelErr(StoppedReason),
     IllegalConfig,
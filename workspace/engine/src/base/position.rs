@@ -234,8 +234,15 @@ impl str::FromStr for Position {
 
     fn from_str(code: &str) -> Result<Self, Self::Err> {
         let mut char_iter = code.chars();
-        let column = ((char_iter.next().unwrap() as u8) - 97) as i8;
-        let row = ((char_iter.next().unwrap() as u8) - 49) as i8;
+        let (Some(column_char), Some(row_char)) = (char_iter.next(), char_iter.next()) else {
+            return Err(ChessError{
+                msg: format!("2 chars expected for Position: {}", code),
+                kind: ErrorKind::IllegalFormat
+            });
+        };
+        // chars outside of a-h and 1-8 end up outside of I8_RANGE_07 (or in i8::MIN if they're far off)
+        let column = i8::try_from(column_char as i64 - 'a' as i64).unwrap_or(i8::MIN);
+        let row = i8::try_from(row_char as i64 - '1' as i64).unwrap_or(i8::MIN);
         if char_iter.next().is_some()  {
             return Err(ChessError{
                 msg: format!("only 2 chars expected for Position: {}", code),
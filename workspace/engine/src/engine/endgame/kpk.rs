@@ -0,0 +1,326 @@
+use std::sync::OnceLock;
+use crate::base::{Color, Position};
+use crate::figure::FigureType;
+use crate::game::{FiguresWithPosArray, GameState};
+
+// the pawn is normalized to be white and on the columns a-d, so it can stand on 4 * 6 positions
+const NR_OF_PAWN_POSITIONS: usize = 24;
+const NR_OF_POSITIONS: usize = 2 * 64 * 64 * NR_OF_PAWN_POSITIONS;
+
+static KPK_BITBASE: OnceLock<KpkBitbase> = OnceLock::new();
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum KpkResult {
+    Win(Color),
+    Draw,
+}
+
+/**
+ * one bit per position (white king, black king, white pawn, color to move): set if white wins.
+ */
+pub struct KpkBitbase {
+    wins: Vec<u64>,
+}
+
+/**
+ * returns None if the material isn't exactly king and pawn versus king.
+ * The bitbase is generated on first use.
+ */
+pub fn probe_kpk(game_state: &GameState) -> Option<KpkResult> {
+    let (white_figures, black_figures) = game_state.board.get_white_and_black_figures();
+    probe_kpk_figures(&white_figures, &black_figures, game_state.turn_by)
+}
+
+/**
+ * like probe_kpk, but for callers that already collected the figures of the board
+ */
+pub(crate) fn probe_kpk_figures(white_figures: &FiguresWithPosArray, black_figures: &FiguresWithPosArray, turn_by: Color) -> Option<KpkResult> {
+    let nr_of_white_figures = white_figures.iter().flatten().count();
+    let nr_of_black_figures = black_figures.iter().flatten().count();
+    let (strong_figures, weak_figures, strong_color) = match (nr_of_white_figures, nr_of_black_figures) {
+        (2, 1) => (white_figures, black_figures, Color::White),
+        (1, 2) => (black_figures, white_figures, Color::Black),
+        _ => { return None; }
+    };
+    let pawn_pos = strong_figures.iter().flatten()
+        .find(|(fig_type, _)| *fig_type == FigureType::Pawn)
+        .map(|(_, pos)| *pos)?;
+    let strong_king_pos = strong_figures.iter().flatten()
+        .find(|(fig_type, _)| *fig_type == FigureType::King)
+        .map(|(_, pos)| *pos)?;
+    let (_, weak_king_pos) = weak_figures[0]?;
+
+    // the bitbase only contains positions with a white pawn
+    let normalize = |pos: Position| if strong_color == Color::White { pos } else { Position::new_unchecked(pos.column(), 7 - pos.row()) };
+    let turn_by = if strong_color == Color::White { turn_by } else { turn_by.toggle() };
+
+    let is_win = get_kpk_bitbase().is_win(normalize(strong_king_pos), normalize(weak_king_pos), normalize(pawn_pos), turn_by)?;
+    Some(if is_win { KpkResult::Win(strong_color) } else { KpkResult::Draw })
+}
+
+pub fn get_kpk_bitbase() -> &'static KpkBitbase {
+    KPK_BITBASE.get_or_init(KpkBitbase::generate)
+}
+
+impl KpkBitbase {
+    /**
+     * retrograde analysis: starting from the positions whose outcome is known immediately
+     * (promotions, captures of the pawn, stalemates) the outcome is propagated backwards
+     * until no position changes anymore. All positions that are still unknown then are draws.
+     */
+    pub fn generate() -> KpkBitbase {
+        let mut classifications: Vec<Classification> = (0..NR_OF_POSITIONS)
+            .map(|index| KpkPosition::from_index(index).initial_classification())
+            .collect();
+
+        let mut has_changed = true;
+        while has_changed {
+            has_changed = false;
+            for index in 0..NR_OF_POSITIONS {
+                if classifications[index] != Classification::Unknown {
+                    continue;
+                }
+                let new_classification = KpkPosition::from_index(index).classify(&classifications);
+                if new_classification != Classification::Unknown {
+                    classifications[index] = new_classification;
+                    has_changed = true;
+                }
+            }
+        }
+
+        let mut wins: Vec<u64> = vec![0; NR_OF_POSITIONS.div_ceil(64)];
+        for (index, classification) in classifications.iter().enumerate() {
+            if *classification == Classification::Win {
+                wins[index / 64] |= 1 << (index % 64);
+            }
+        }
+        KpkBitbase { wins }
+    }
+
+    /**
+     * whether white (the side with the pawn) wins. Returns None for positions that can't occur
+     * (e.g. the pawn on the first or last row).
+     */
+    pub fn is_win(&self, white_king_pos: Position, black_king_pos: Position, white_pawn_pos: Position, turn_by: Color) -> Option<bool> {
+        if !(1..=6).contains(&white_pawn_pos.row()) {
+            return None;
+        }
+        // the columns e-h are mirrored onto d-a
+        let mirror = |pos: Position| if white_pawn_pos.column() > 3 {
+            Position::new_unchecked(7 - pos.column(), pos.row())
+        } else {
+            pos
+        };
+        let index = KpkPosition {
+            white_king: mirror(white_king_pos),
+            black_king: mirror(black_king_pos),
+            pawn: mirror(white_pawn_pos),
+            turn_by,
+        }.to_index();
+        Some(self.wins[index / 64] & (1 << (index % 64)) != 0)
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Classification {
+    Invalid,
+    Unknown,
+    Draw,
+    Win,
+}
+
+#[derive(Debug, Copy, Clone)]
+struct KpkPosition {
+    white_king: Position,
+    black_king: Position,
+    pawn: Position,
+    turn_by: Color,
+}
+
+impl KpkPosition {
+    fn from_index(index: usize) -> KpkPosition {
+        let turn_by = if index & 1 == 0 { Color::White } else { Color::Black };
+        let black_king = Position::from_index_unchecked((index >> 1) & 63);
+        let white_king = Position::from_index_unchecked((index >> 7) & 63);
+        let pawn_index = index >> 13;
+        let pawn = Position::new_unchecked((pawn_index % 4) as i8, (pawn_index / 4 + 1) as i8);
+        KpkPosition { white_king, black_king, pawn, turn_by }
+    }
+
+    fn to_index(self) -> usize {
+        let pawn_index = (self.pawn.row() as usize - 1) * 4 + self.pawn.column() as usize;
+        let turn_index = if self.turn_by == Color::White { 0 } else { 1 };
+        (((((pawn_index << 6) | self.white_king.index) << 6) | self.black_king.index) << 1) | turn_index
+    }
+
+    fn initial_classification(&self) -> Classification {
+        let KpkPosition { white_king, black_king, pawn, turn_by } = *self;
+        if white_king == black_king || white_king == pawn || black_king == pawn || distance(white_king, black_king) <= 1 {
+            return Classification::Invalid;
+        }
+        let is_black_king_attacked_by_pawn = is_attacked_by_pawn(black_king, pawn);
+
+        match turn_by {
+            Color::White => {
+                if is_black_king_attacked_by_pawn {
+                    return Classification::Invalid;
+                }
+                // the pawn can be promoted to a queen that can't be captured immediately
+                if pawn.row() == 6 {
+                    let promotion_pos = Position::new_unchecked(pawn.column(), 7);
+                    if white_king != promotion_pos && (distance(black_king, promotion_pos) > 1 || distance(white_king, promotion_pos) == 1) {
+                        return Classification::Win;
+                    }
+                }
+                Classification::Unknown
+            }
+            Color::Black => {
+                // the pawn can be captured
+                if distance(black_king, pawn) == 1 && distance(white_king, pawn) > 1 {
+                    return Classification::Draw;
+                }
+                if self.black_king_moves().next().is_none() {
+                    return if is_black_king_attacked_by_pawn { Classification::Win } else { Classification::Draw };
+                }
+                Classification::Unknown
+            }
+        }
+    }
+
+    fn classify(&self, classifications: &[Classification]) -> Classification {
+        match self.turn_by {
+            Color::White => {
+                let mut are_all_draws = true;
+                for next_position in self.white_moves() {
+                    match classifications[next_position.to_index()] {
+                        Classification::Win => { return Classification::Win; }
+                        Classification::Draw => {}
+                        _ => { are_all_draws = false; }
+                    }
+                }
+                if are_all_draws { Classification::Draw } else { Classification::Unknown }
+            }
+            Color::Black => {
+                let mut are_all_wins = true;
+                for next_position in self.black_king_moves() {
+                    match classifications[next_position.to_index()] {
+                        Classification::Draw => { return Classification::Draw; }
+                        Classification::Win => {}
+                        _ => { are_all_wins = false; }
+                    }
+                }
+                if are_all_wins { Classification::Win } else { Classification::Unknown }
+            }
+        }
+    }
+
+    /**
+     * all positions reachable by white, except for promotions (which are handled by initial_classification)
+     */
+    fn white_moves(&self) -> Vec<KpkPosition> {
+        let KpkPosition { white_king, black_king, pawn, .. } = *self;
+        let mut next_positions: Vec<KpkPosition> = king_steps(white_king)
+            .filter(|pos| *pos != pawn && distance(*pos, black_king) > 1)
+            .map(|pos| KpkPosition { white_king: pos, black_king, pawn, turn_by: Color::Black })
+            .collect();
+
+        if pawn.row() < 6 {
+            let one_step = Position::new_unchecked(pawn.column(), pawn.row() + 1);
+            if one_step != white_king && one_step != black_king {
+                next_positions.push(KpkPosition { white_king, black_king, pawn: one_step, turn_by: Color::Black });
+
+                let two_steps = Position::new_unchecked(pawn.column(), pawn.row() + 2);
+                if pawn.row() == 1 && two_steps != white_king && two_steps != black_king {
+                    next_positions.push(KpkPosition { white_king, black_king, pawn: two_steps, turn_by: Color::Black });
+                }
+            }
+        }
+        next_positions
+    }
+
+    /**
+     * all positions reachable by black, except for capturing the pawn (which is handled by initial_classification)
+     */
+    fn black_king_moves(&self) -> impl Iterator<Item=KpkPosition> {
+        let KpkPosition { white_king, black_king, pawn, .. } = *self;
+        king_steps(black_king)
+            .filter(move |pos| *pos != pawn && distance(*pos, white_king) > 1 && !is_attacked_by_pawn(*pos, pawn))
+            .map(move |pos| KpkPosition { white_king, black_king: pos, pawn, turn_by: Color::White })
+    }
+}
+
+fn king_steps(pos: Position) -> impl Iterator<Item=Position> {
+    (-1..=1_i8).flat_map(move |column_step| (-1..=1_i8).filter_map(move |row_step| {
+        if column_step == 0 && row_step == 0 {
+            None
+        } else {
+            Position::new_checked(pos.column() + column_step, pos.row() + row_step)
+        }
+    }))
+}
+
+fn distance(pos1: Position, pos2: Position) -> i8 {
+    (pos1.column() - pos2.column()).abs().max((pos1.row() - pos2.row()).abs())
+}
+
+fn is_attacked_by_pawn(pos: Position, white_pawn: Position) -> bool {
+    pos.row() == white_pawn.row() + 1 && (pos.column() - white_pawn.column()).abs() == 1
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    //♔♕♗♘♖♙♚♛♝♞♜♟
+
+    #[rstest(
+    game_config, expected_result,
+    case("white ♔e6 ♙e5 ♚e8", KpkResult::Win(Color::White)), // king on the 6th row in front of its pawn
+    case("black ♔e6 ♙e5 ♚e8", KpkResult::Win(Color::White)),
+    case("white ♔e5 ♙e4 ♚e7", KpkResult::Draw), // black has the opposition
+    case("black ♔e5 ♙e4 ♚e7", KpkResult::Win(Color::White)), // white has the opposition
+    case("black ♔e1 ♙e4 ♚e5", KpkResult::Draw), // black captures the pawn
+    case("white ♔a6 ♙a5 ♚a8", KpkResult::Draw), // rook pawn with the defending king in the corner
+    case("black ♔b5 ♙a4 ♚a8", KpkResult::Draw),
+    case("white ♔h1 ♙a5 ♚h8", KpkResult::Win(Color::White)), // black king is outside the square of the pawn
+    case("black ♔h1 ♙a5 ♚h8", KpkResult::Win(Color::White)),
+    case("black ♔h1 ♙a4 ♚f5", KpkResult::Draw), // black king reaches the square of the pawn
+    case("white ♔h1 ♙a4 ♚f5", KpkResult::Win(Color::White)),
+    case("white ♔b6 ♙a7 ♚a8", KpkResult::Draw),
+    case("white ♔c6 ♙d7 ♚d8", KpkResult::Draw), // white either stalemates black or loses the pawn
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_probe_kpk(game_config: &str, expected_result: KpkResult) {
+        let game_state = game_config.parse::<GameState>().unwrap();
+        assert_eq!(probe_kpk(&game_state), Some(expected_result), "white pawn");
+
+        let expected_toggled_result = match expected_result {
+            KpkResult::Win(color) => KpkResult::Win(color.toggle()),
+            KpkResult::Draw => KpkResult::Draw,
+        };
+        assert_eq!(probe_kpk(&game_state.toggle_colors()), Some(expected_toggled_result), "black pawn");
+    }
+
+    #[rstest(
+    game_config,
+    case("white ♔e1 ♚e8"),
+    case("white ♔e1 ♙e2 ♙d2 ♚e8"),
+    case("white ♔e1 ♖e2 ♚e8"),
+    case("white ♔e1 ♙e2 ♚e8 ♟d7"),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_probe_kpk_only_applies_to_kpk(game_config: &str) {
+        let game_state = game_config.parse::<GameState>().unwrap();
+        assert_eq!(probe_kpk(&game_state), None);
+    }
+
+    #[test]
+    fn test_index_round_trip() {
+        for index in [0, 1, 8191, 8192, NR_OF_POSITIONS - 1] {
+            assert_eq!(KpkPosition::from_index(index).to_index(), index);
+        }
+    }
+}
@@ -0,0 +1,798 @@
+use std::{fmt, fs, str};
+use std::path::Path;
+use crate::base::{ChessError, Color, ErrorKind, Position};
+use crate::figure::{Figure, FigureAndPosition, FigureType};
+use crate::game::GameState;
+
+/**
+ * tables with up to 4 figures (kings included) still fit comfortably into memory
+ */
+pub const MAX_NR_OF_FIGURES: usize = 4;
+
+const FILE_MAGIC: &[u8; 4] = b"VCET";
+const FILE_VERSION: u8 = 1;
+
+// values of the table: a win in n half moves is stored as n, a loss in n half moves as -n-1
+const DRAW: i16 = 0;
+const ILLEGAL: i16 = i16::MIN;
+const UNKNOWN: i16 = i16::MAX;
+
+// marks the positions in the queue that are won for the active color
+const WIN_FLAG: u32 = 1 << 31;
+// the position can't be lost because a capture leads to a draw or a win
+const NO_LOSS: u8 = u8::MAX;
+// the square of a figure that has been captured
+const CAPTURED: usize = 64;
+
+const ALL_DIRECTIONS: [(i8, i8); 8] = [(-1, -1), (-1, 0), (-1, 1), (0, -1), (0, 1), (1, -1), (1, 0), (1, 1)];
+const STRAIGHT_DIRECTIONS: [(i8, i8); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+const DIAGONAL_DIRECTIONS: [(i8, i8); 4] = [(-1, -1), (-1, 1), (1, -1), (1, 1)];
+const KNIGHT_JUMPS: [(i8, i8); 8] = [(-2, -1), (-2, 1), (-1, -2), (-1, 2), (1, -2), (1, 2), (2, -1), (2, 1)];
+
+type Squares = [usize; MAX_NR_OF_FIGURES];
+
+/**
+ * the figures of both sides, e.g. KRK or KBNK. Pawns aren't supported (see the KPK bitbase for that).
+ */
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct MaterialSignature {
+    // both start with the king, followed by the other figures ordered by decreasing value
+    white_figures: Vec<FigureType>,
+    black_figures: Vec<FigureType>,
+}
+
+impl MaterialSignature {
+    fn new(mut white_figures: Vec<FigureType>, mut black_figures: Vec<FigureType>) -> MaterialSignature {
+        white_figures.sort_by_key(|fig_type| figure_order(*fig_type));
+        black_figures.sort_by_key(|fig_type| figure_order(*fig_type));
+        MaterialSignature { white_figures, black_figures }
+    }
+
+    pub fn swap_colors(&self) -> MaterialSignature {
+        MaterialSignature {
+            white_figures: self.black_figures.clone(),
+            black_figures: self.white_figures.clone(),
+        }
+    }
+
+    pub fn nr_of_figures(&self) -> usize {
+        self.white_figures.len() + self.black_figures.len()
+    }
+
+    /**
+     * the material doesn't allow a checkmate at all
+     */
+    fn is_insufficient_material(&self) -> bool {
+        let mut other_figures = self.white_figures.iter().chain(self.black_figures.iter())
+            .filter(|fig_type| **fig_type != FigureType::King);
+        matches!(
+            (other_figures.next(), other_figures.next()),
+            (None, _) | (Some(FigureType::Bishop | FigureType::Knight), None)
+        )
+    }
+
+    /**
+     * the signatures remaining after one of the figures (other than the kings) has been captured
+     */
+    fn sub_signatures(&self) -> Vec<MaterialSignature> {
+        let mut sub_signatures = Vec::new();
+        for index in 1..self.white_figures.len() {
+            let mut white_figures = self.white_figures.clone();
+            white_figures.remove(index);
+            sub_signatures.push(MaterialSignature::new(white_figures, self.black_figures.clone()));
+        }
+        for index in 1..self.black_figures.len() {
+            let mut black_figures = self.black_figures.clone();
+            black_figures.remove(index);
+            sub_signatures.push(MaterialSignature::new(self.white_figures.clone(), black_figures));
+        }
+        sub_signatures
+    }
+
+    fn figures(&self) -> Vec<Figure> {
+        self.white_figures.iter().map(|fig_type| Figure { fig_type: *fig_type, color: Color::White })
+            .chain(self.black_figures.iter().map(|fig_type| Figure { fig_type: *fig_type, color: Color::Black }))
+            .collect()
+    }
+
+    fn nr_of_positions(&self) -> usize {
+        2 << (6 * self.nr_of_figures())
+    }
+
+    /**
+     * returns None if the figures don't match this signature
+     */
+    fn index_of(&self, white_figures: &[(FigureType, usize)], black_figures: &[(FigureType, usize)], turn_by: Color) -> Option<usize> {
+        let mut squares: Squares = [CAPTURED; MAX_NR_OF_FIGURES];
+        for (figures, fig_types, offset) in [
+            (white_figures, &self.white_figures, 0),
+            (black_figures, &self.black_figures, self.white_figures.len()),
+        ] {
+            if figures.len() != fig_types.len() {
+                return None;
+            }
+            let mut is_slot_used = [false; MAX_NR_OF_FIGURES];
+            for (fig_type, square) in figures {
+                // figures of the same type are interchangeable, so the first free slot is taken
+                let slot = (0..fig_types.len()).find(|slot| fig_types[*slot] == *fig_type && !is_slot_used[*slot])?;
+                is_slot_used[slot] = true;
+                squares[offset + slot] = *square;
+            }
+        }
+        Some(to_index(&squares[..self.nr_of_figures()], turn_by))
+    }
+}
+
+impl str::FromStr for MaterialSignature {
+    type Err = ChessError;
+
+    fn from_str(desc: &str) -> Result<Self, Self::Err> {
+        let mut white_figures: Vec<FigureType> = Vec::new();
+        let mut black_figures: Vec<FigureType> = Vec::new();
+        let mut nr_of_kings = 0;
+        for letter in desc.trim().chars() {
+            let fig_type = match letter.to_ascii_uppercase() {
+                'K' => {
+                    nr_of_kings += 1;
+                    FigureType::King
+                }
+                'Q' => FigureType::Queen,
+                'R' => FigureType::Rook,
+                'B' => FigureType::Bishop,
+                'N' => FigureType::Knight,
+                'P' => {
+                    return Err(ChessError {
+                        msg: format!("material signature {} contains a pawn, but only figures are supported", desc),
+                        kind: ErrorKind::IllegalConfig,
+                    });
+                }
+                _ => {
+                    return Err(ChessError {
+                        msg: format!("unexpected character {} in material signature {}, something like KRK expected", letter, desc),
+                        kind: ErrorKind::IllegalFormat,
+                    });
+                }
+            };
+            match nr_of_kings {
+                1 => white_figures.push(fig_type),
+                2 => black_figures.push(fig_type),
+                _ => {
+                    return Err(ChessError {
+                        msg: format!("material signature {} has to start with the white king and contain exactly two kings, e.g. KRK", desc),
+                        kind: ErrorKind::IllegalFormat,
+                    });
+                }
+            }
+        }
+        if nr_of_kings != 2 {
+            return Err(ChessError {
+                msg: format!("material signature {} has to contain exactly two kings, e.g. KRK", desc),
+                kind: ErrorKind::IllegalFormat,
+            });
+        }
+        let signature = MaterialSignature::new(white_figures, black_figures);
+        if signature.nr_of_figures() > MAX_NR_OF_FIGURES {
+            return Err(ChessError {
+                msg: format!("material signature {} contains more than {} figures", desc, MAX_NR_OF_FIGURES),
+                kind: ErrorKind::IllegalConfig,
+            });
+        }
+        Ok(signature)
+    }
+}
+
+impl fmt::Display for MaterialSignature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let desc: String = self.white_figures.iter().chain(self.black_figures.iter())
+            .map(|fig_type| Figure { fig_type: *fig_type, color: Color::White }.get_fen_char())
+            .collect();
+        write!(f, "{}", desc)
+    }
+}
+
+/**
+ * distance to mate in half moves from the perspective of the active color.
+ * Win(1) means the active color can checkmate with its next move, Loss(0) that it is checkmate.
+ */
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Dtm {
+    Win(u16),
+    Loss(u16),
+    Draw,
+}
+
+/**
+ * the distance to mate of every legal position of a material signature,
+ * computed by retrograde analysis (castling isn't considered).
+ */
+pub struct EndgameTable {
+    signature: MaterialSignature,
+    values: Vec<i16>,
+}
+
+impl EndgameTable {
+    /**
+     * enumerates all positions of the signature and resolves them starting from the checkmates.
+     * The tables of the material remaining after a capture are generated as well.
+     */
+    pub fn generate(signature: &MaterialSignature) -> EndgameTable {
+        let mut sub_tables: Vec<EndgameTable> = Vec::new();
+        generate_with_sub_tables(signature, &mut sub_tables)
+    }
+
+    pub fn signature(&self) -> &MaterialSignature {
+        &self.signature
+    }
+
+    /**
+     * returns None if the material doesn't match the signature (the colors may be swapped)
+     * or castling is still possible.
+     */
+    pub fn probe(&self, game_state: &GameState) -> Option<Dtm> {
+        if game_state.is_white_king_side_castling_still_possible.get_value()
+            || game_state.is_white_queen_side_castling_still_possible.get_value()
+            || game_state.is_black_king_side_castling_still_possible.get_value()
+            || game_state.is_black_queen_side_castling_still_possible.get_value() {
+            return None;
+        }
+        let (white_figures, black_figures) = game_state.board.get_white_and_black_figures();
+        let to_squares = |figures: &[Option<(FigureType, Position)>]| -> Vec<(FigureType, usize)> {
+            figures.iter().flatten().map(|(fig_type, pos)| (*fig_type, pos.index)).collect()
+        };
+        self.lookup(&to_squares(&white_figures), &to_squares(&black_figures), game_state.turn_by)
+    }
+
+    /**
+     * all legal positions of the table together with their distance to mate, e.g. for an endgame trainer
+     */
+    pub fn positions(&self) -> impl Iterator<Item=(GameState, Dtm)> + '_ {
+        let figures = self.signature.figures();
+        self.values.iter().enumerate().filter_map(move |(index, value)| {
+            let dtm = to_dtm(*value)?;
+            let (squares, turn_by) = from_index(index, figures.len());
+            let figures_and_positions: Vec<FigureAndPosition> = figures.iter().zip(squares.iter())
+                .map(|(figure, square)| FigureAndPosition { figure: *figure, pos: Position::from_index_unchecked(*square) })
+                .collect();
+            let mut game_state = GameState::from_manual_config(turn_by, None, figures_and_positions).ok()?;
+            game_state.is_white_king_side_castling_still_possible.deactivate();
+            game_state.is_white_queen_side_castling_still_possible.deactivate();
+            game_state.is_black_king_side_castling_still_possible.deactivate();
+            game_state.is_black_queen_side_castling_still_possible.deactivate();
+            Some((game_state, dtm))
+        })
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), ChessError> {
+        let path = path.as_ref();
+        fs::write(path, self.to_bytes()).map_err(|err| ChessError {
+            msg: format!("couldn't write endgame table {}: {}", path.display(), err),
+            kind: ErrorKind::IllegalConfig,
+        })
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<EndgameTable, ChessError> {
+        let path = path.as_ref();
+        let bytes = fs::read(path).map_err(|err| ChessError {
+            msg: format!("couldn't read endgame table {}: {}", path.display(), err),
+            kind: ErrorKind::IllegalConfig,
+        })?;
+        EndgameTable::from_bytes(bytes.as_slice())
+    }
+
+    /**
+     * format: the magic bytes "VCET", the version, the length of the signature, the signature
+     * and then one little endian i16 per position
+     */
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let signature = self.signature.to_string();
+        let mut bytes: Vec<u8> = Vec::with_capacity(6 + signature.len() + 2 * self.values.len());
+        bytes.extend_from_slice(FILE_MAGIC);
+        bytes.push(FILE_VERSION);
+        bytes.push(signature.len() as u8);
+        bytes.extend_from_slice(signature.as_bytes());
+        for value in &self.values {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<EndgameTable, ChessError> {
+        let format_error = |msg: &str| ChessError {
+            msg: format!("illegal endgame table: {}", msg),
+            kind: ErrorKind::IllegalFormat,
+        };
+        if bytes.len() < 6 || &bytes[..4] != FILE_MAGIC {
+            return Err(format_error("magic bytes are missing"));
+        }
+        if bytes[4] != FILE_VERSION {
+            return Err(format_error(&format!("unsupported version {}", bytes[4])));
+        }
+        let signature_end = 6 + bytes[5] as usize;
+        let signature_desc = bytes.get(6..signature_end)
+            .and_then(|signature_bytes| str::from_utf8(signature_bytes).ok())
+            .ok_or_else(|| format_error("signature is missing"))?;
+        let signature = signature_desc.parse::<MaterialSignature>()?;
+
+        let value_bytes = &bytes[signature_end..];
+        if value_bytes.len() != 2 * signature.nr_of_positions() {
+            return Err(format_error(&format!(
+                "expected {} positions for {} but found {} bytes", signature.nr_of_positions(), signature, value_bytes.len()
+            )));
+        }
+        let values: Vec<i16> = value_bytes.chunks_exact(2)
+            .map(|value| i16::from_le_bytes([value[0], value[1]]))
+            .collect();
+        Ok(EndgameTable { signature, values })
+    }
+
+    fn lookup(&self, white_figures: &[(FigureType, usize)], black_figures: &[(FigureType, usize)], turn_by: Color) -> Option<Dtm> {
+        let index = self.signature.index_of(white_figures, black_figures, turn_by)
+            // without pawns the board is symmetric, so the same material with swapped colors can be looked up as well
+            .or_else(|| self.signature.index_of(black_figures, white_figures, turn_by.toggle()))?;
+        to_dtm(self.values[index])
+    }
+}
+
+fn generate_with_sub_tables(signature: &MaterialSignature, sub_tables: &mut Vec<EndgameTable>) -> EndgameTable {
+    for sub_signature in signature.sub_signatures() {
+        let is_known = sub_tables.iter().any(|table| table.signature == sub_signature || table.signature == sub_signature.swap_colors());
+        if sub_signature.is_insufficient_material() || is_known {
+            continue;
+        }
+        let sub_table = generate_with_sub_tables(&sub_signature, sub_tables);
+        sub_tables.push(sub_table);
+    }
+
+    let figures = signature.figures();
+    let generator = Generator {
+        black_king_slot: signature.white_figures.len(),
+        figures,
+        sub_tables,
+    };
+    EndgameTable {
+        signature: signature.clone(),
+        values: generator.generate(signature.nr_of_positions()),
+    }
+}
+
+struct Generator<'a> {
+    figures: Vec<Figure>,
+    black_king_slot: usize,
+    sub_tables: &'a [EndgameTable],
+}
+
+impl Generator<'_> {
+    /**
+     * Every position gets a counter of its moves that stay in the table. Starting from the checkmates,
+     * the positions are resolved in the order of their distance to mate: the predecessors of a lost position
+     * are won, a predecessor of a won position is lost as soon as all its moves lead to won positions.
+     * Captures are resolved upfront with the help of the sub tables. Unresolved positions are draws.
+     */
+    fn generate(&self, nr_of_positions: usize) -> Vec<i16> {
+        let mut values: Vec<i16> = vec![UNKNOWN; nr_of_positions];
+        let mut nr_of_open_moves: Vec<u8> = vec![0; nr_of_positions];
+        let mut min_loss_distance: Vec<u8> = vec![NO_LOSS; nr_of_positions];
+        let mut queue: Vec<Vec<u32>> = Vec::new();
+
+        for index in 0..nr_of_positions {
+            let (squares, turn_by) = from_index(index, self.figures.len());
+            if !self.is_legal(&squares, turn_by) {
+                values[index] = ILLEGAL;
+                continue;
+            }
+            let mut has_moves = false;
+            let mut nr_of_quiet_moves: u8 = 0;
+            let mut opt_capture_win: Option<u16> = None;
+            let mut longest_capture_loss: u16 = 0;
+            let mut has_drawing_capture = false;
+            self.for_each_move(&squares, turn_by, |new_squares, is_capture| {
+                has_moves = true;
+                if !is_capture {
+                    nr_of_quiet_moves += 1;
+                    return;
+                }
+                match self.lookup_after_capture(new_squares, turn_by.toggle()) {
+                    Dtm::Loss(plies) => {
+                        opt_capture_win = Some(opt_capture_win.map_or(plies + 1, |win| win.min(plies + 1)));
+                    }
+                    Dtm::Win(plies) => { longest_capture_loss = longest_capture_loss.max(plies + 1); }
+                    Dtm::Draw => { has_drawing_capture = true; }
+                }
+            });
+
+            if !has_moves {
+                if self.is_attacked(squares[self.king_slot(turn_by)], turn_by.toggle(), &squares) {
+                    enqueue(&mut queue, 0, index as u32);
+                } else {
+                    values[index] = DRAW;
+                }
+                continue;
+            }
+            nr_of_open_moves[index] = nr_of_quiet_moves;
+            if let Some(plies) = opt_capture_win {
+                enqueue(&mut queue, plies as usize, index as u32 | WIN_FLAG);
+            } else if !has_drawing_capture {
+                debug_assert!(longest_capture_loss < NO_LOSS as u16);
+                min_loss_distance[index] = longest_capture_loss as u8;
+                if nr_of_quiet_moves == 0 {
+                    enqueue(&mut queue, longest_capture_loss as usize, index as u32);
+                }
+            }
+        }
+
+        let mut plies: usize = 0;
+        while plies < queue.len() {
+            let entries = std::mem::take(&mut queue[plies]);
+            for entry in entries {
+                let index = (entry & !WIN_FLAG) as usize;
+                if values[index] != UNKNOWN {
+                    continue;
+                }
+                let is_win = entry & WIN_FLAG != 0;
+                values[index] = if is_win { plies as i16 } else { -(plies as i16) - 1 };
+
+                let (squares, turn_by) = from_index(index, self.figures.len());
+                self.for_each_predecessor(&squares, turn_by, |predecessor_index| {
+                    if values[predecessor_index] != UNKNOWN {
+                        return;
+                    }
+                    if is_win {
+                        nr_of_open_moves[predecessor_index] -= 1;
+                        let min_loss = min_loss_distance[predecessor_index];
+                        if nr_of_open_moves[predecessor_index] == 0 && min_loss != NO_LOSS {
+                            enqueue(&mut queue, (plies + 1).max(min_loss as usize), predecessor_index as u32);
+                        }
+                    } else {
+                        // the predecessor might still be in the queue, but it can't become a loss anymore
+                        min_loss_distance[predecessor_index] = NO_LOSS;
+                        enqueue(&mut queue, plies + 1, predecessor_index as u32 | WIN_FLAG);
+                    }
+                });
+            }
+            plies += 1;
+        }
+
+        for value in values.iter_mut() {
+            if *value == UNKNOWN {
+                *value = DRAW;
+            }
+        }
+        values
+    }
+
+    fn king_slot(&self, color: Color) -> usize {
+        match color {
+            Color::White => 0,
+            Color::Black => self.black_king_slot,
+        }
+    }
+
+    fn figure_slot_at(&self, squares: &Squares, square: usize) -> Option<usize> {
+        (0..self.figures.len()).find(|slot| squares[*slot] == square)
+    }
+
+    /**
+     * no two figures on the same square and the passive king isn't attacked
+     */
+    fn is_legal(&self, squares: &Squares, turn_by: Color) -> bool {
+        let nr_of_figures = self.figures.len();
+        for slot in 1..nr_of_figures {
+            if squares[..slot].contains(&squares[slot]) {
+                return false;
+            }
+        }
+        !self.is_attacked(squares[self.king_slot(turn_by.toggle())], turn_by, squares)
+    }
+
+    fn is_attacked(&self, target: usize, by_color: Color, squares: &Squares) -> bool {
+        (0..self.figures.len()).any(|slot| {
+            let figure = self.figures[slot];
+            figure.color == by_color && squares[slot] != CAPTURED && self.attacks(figure.fig_type, squares[slot], target, squares)
+        })
+    }
+
+    fn attacks(&self, fig_type: FigureType, from: usize, target: usize, squares: &Squares) -> bool {
+        let column_diff = (target & 7) as i8 - (from & 7) as i8;
+        let row_diff = (target >> 3) as i8 - (from >> 3) as i8;
+        let is_straight = (column_diff == 0) != (row_diff == 0);
+        let is_diagonal = column_diff != 0 && column_diff.abs() == row_diff.abs();
+        match fig_type {
+            FigureType::King => from != target && column_diff.abs() <= 1 && row_diff.abs() <= 1,
+            FigureType::Knight => column_diff.abs() * row_diff.abs() == 2,
+            FigureType::Rook => is_straight && self.is_path_free(from, target, squares),
+            FigureType::Bishop => is_diagonal && self.is_path_free(from, target, squares),
+            FigureType::Queen => (is_straight || is_diagonal) && self.is_path_free(from, target, squares),
+            FigureType::Pawn => unreachable!("material signatures don't contain pawns"),
+        }
+    }
+
+    /**
+     * all squares between from and target are empty (from and target have to be on a line)
+     */
+    fn is_path_free(&self, from: usize, target: usize, squares: &Squares) -> bool {
+        let column_step = ((target & 7) as i8 - (from & 7) as i8).signum();
+        let row_step = ((target >> 3) as i8 - (from >> 3) as i8).signum();
+        let step = (row_step * 8 + column_step) as isize;
+        let mut square = (from as isize + step) as usize;
+        while square != target {
+            if self.figure_slot_at(squares, square).is_some() {
+                return false;
+            }
+            square = (square as isize + step) as usize;
+        }
+        true
+    }
+
+    /**
+     * calls handle_square for all squares the figure attacks, no matter if they are empty or not
+     */
+    fn for_each_attacked_square<F: FnMut(usize)>(&self, fig_type: FigureType, from: usize, squares: &Squares, mut handle_square: F) {
+        let (directions, is_sliding): (&[(i8, i8)], bool) = match fig_type {
+            FigureType::King => (&ALL_DIRECTIONS, false),
+            FigureType::Knight => (&KNIGHT_JUMPS, false),
+            FigureType::Rook => (&STRAIGHT_DIRECTIONS, true),
+            FigureType::Bishop => (&DIAGONAL_DIRECTIONS, true),
+            FigureType::Queen => (&ALL_DIRECTIONS, true),
+            FigureType::Pawn => unreachable!("material signatures don't contain pawns"),
+        };
+        for (column_step, row_step) in directions {
+            let mut column = (from & 7) as i8 + column_step;
+            let mut row = (from >> 3) as i8 + row_step;
+            while (0..8).contains(&column) && (0..8).contains(&row) {
+                let square = (row * 8 + column) as usize;
+                handle_square(square);
+                if !is_sliding || self.figure_slot_at(squares, square).is_some() {
+                    break;
+                }
+                column += column_step;
+                row += row_step;
+            }
+        }
+    }
+
+    /**
+     * calls handle_move with the squares after every legal move of the active color and whether it was a capture
+     */
+    fn for_each_move<F: FnMut(&Squares, bool)>(&self, squares: &Squares, turn_by: Color, mut handle_move: F) {
+        for slot in 0..self.figures.len() {
+            let figure = self.figures[slot];
+            if figure.color != turn_by {
+                continue;
+            }
+            self.for_each_attacked_square(figure.fig_type, squares[slot], squares, |to| {
+                let opt_captured_slot = self.figure_slot_at(squares, to);
+                if let Some(captured_slot) = opt_captured_slot {
+                    if self.figures[captured_slot].color == turn_by {
+                        return;
+                    }
+                }
+                let mut new_squares = *squares;
+                new_squares[slot] = to;
+                if let Some(captured_slot) = opt_captured_slot {
+                    new_squares[captured_slot] = CAPTURED;
+                }
+                if self.is_attacked(new_squares[self.king_slot(turn_by)], turn_by.toggle(), &new_squares) {
+                    return;
+                }
+                handle_move(&new_squares, opt_captured_slot.is_some());
+            });
+        }
+    }
+
+    /**
+     * calls handle_predecessor with the index of every legal position from which a move
+     * without capture of the passive color leads to the given position
+     */
+    fn for_each_predecessor<F: FnMut(usize)>(&self, squares: &Squares, turn_by: Color, mut handle_predecessor: F) {
+        let moved_by = turn_by.toggle();
+        for slot in 0..self.figures.len() {
+            let figure = self.figures[slot];
+            if figure.color != moved_by {
+                continue;
+            }
+            // the figures move symmetrically, so the squares a figure could have come from are the ones it attacks
+            self.for_each_attacked_square(figure.fig_type, squares[slot], squares, |from| {
+                if self.figure_slot_at(squares, from).is_some() {
+                    return;
+                }
+                let mut previous_squares = *squares;
+                previous_squares[slot] = from;
+                if self.is_attacked(previous_squares[self.king_slot(turn_by)], moved_by, &previous_squares) {
+                    return;
+                }
+                handle_predecessor(to_index(&previous_squares[..self.figures.len()], moved_by));
+            });
+        }
+    }
+
+    fn lookup_after_capture(&self, squares: &Squares, turn_by: Color) -> Dtm {
+        let mut white_figures: Vec<(FigureType, usize)> = Vec::new();
+        let mut black_figures: Vec<(FigureType, usize)> = Vec::new();
+        for (figure, square) in self.figures.iter().zip(squares.iter()) {
+            if *square == CAPTURED {
+                continue;
+            }
+            match figure.color {
+                Color::White => white_figures.push((figure.fig_type, *square)),
+                Color::Black => black_figures.push((figure.fig_type, *square)),
+            }
+        }
+        // insufficient material has no table
+        self.sub_tables.iter()
+            .find_map(|table| table.lookup(&white_figures, &black_figures, turn_by))
+            .unwrap_or(Dtm::Draw)
+    }
+}
+
+fn enqueue(queue: &mut Vec<Vec<u32>>, plies: usize, entry: u32) {
+    if queue.len() <= plies {
+        queue.resize_with(plies + 1, Vec::new);
+    }
+    queue[plies].push(entry);
+}
+
+fn to_index(squares: &[usize], turn_by: Color) -> usize {
+    let turn_index = if turn_by == Color::White { 0 } else { 1 };
+    squares.iter().enumerate()
+        .fold(turn_index, |index, (slot, square)| index | (square << (1 + 6 * slot)))
+}
+
+fn from_index(index: usize, nr_of_figures: usize) -> (Squares, Color) {
+    let turn_by = if index & 1 == 0 { Color::White } else { Color::Black };
+    let mut squares: Squares = [CAPTURED; MAX_NR_OF_FIGURES];
+    for (slot, square) in squares.iter_mut().enumerate().take(nr_of_figures) {
+        *square = (index >> (1 + 6 * slot)) & 63;
+    }
+    (squares, turn_by)
+}
+
+fn to_dtm(value: i16) -> Option<Dtm> {
+    match value {
+        ILLEGAL => None,
+        DRAW => Some(Dtm::Draw),
+        plies if plies > 0 => Some(Dtm::Win(plies as u16)),
+        negative_plies => Some(Dtm::Loss((-negative_plies - 1) as u16)),
+    }
+}
+
+fn figure_order(fig_type: FigureType) -> u8 {
+    match fig_type {
+        FigureType::King => 0,
+        FigureType::Queen => 1,
+        FigureType::Rook => 2,
+        FigureType::Bishop => 3,
+        FigureType::Knight => 4,
+        FigureType::Pawn => 5,
+    }
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+    use std::sync::OnceLock;
+    use crate::engine::evaluate;
+    use crate::engine::evaluations::frontend::{GameEvaluation, MoveEvaluation};
+    use crate::engine::min_max::pruner::{PRUNER_L0, PRUNER_L2};
+
+    //♔♕♗♘♖♙♚♛♝♞♜♟
+
+    static KRK_TABLE: OnceLock<EndgameTable> = OnceLock::new();
+
+    fn get_krk_table() -> &'static EndgameTable {
+        KRK_TABLE.get_or_init(|| EndgameTable::generate(&"KRK".parse::<MaterialSignature>().unwrap()))
+    }
+
+    #[rstest(
+    desc, expected_signature,
+    case("KRK", "KRK"),
+    case("KQKR", "KQKR"),
+    case("KNBK", "KBNK"),
+    case("krk", "KRK"),
+    case("KKQ", "KKQ"),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_parse_signature(desc: &str, expected_signature: &str) {
+        let signature = desc.parse::<MaterialSignature>().unwrap();
+        assert_eq!(signature.to_string(), expected_signature);
+    }
+
+    #[rstest(
+    desc, expected_kind,
+    case("RK", ErrorKind::IllegalFormat),
+    case("KR", ErrorKind::IllegalFormat),
+    case("KRKK", ErrorKind::IllegalFormat),
+    case("KXK", ErrorKind::IllegalFormat),
+    case("KPK", ErrorKind::IllegalConfig),
+    case("KQRKR", ErrorKind::IllegalConfig),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_parse_signature_fails(desc: &str, expected_kind: ErrorKind) {
+        let err = desc.parse::<MaterialSignature>().unwrap_err();
+        assert_eq!(std::mem::discriminant(&err.kind), std::mem::discriminant(&expected_kind), "error kind: {:?}", err.kind);
+    }
+
+    #[rstest(
+    game_config, expected_dtm,
+    case("white ♔b6 ♖h1 ♚b8", Some(Dtm::Win(1))),
+    case("black ♔b6 ♖h8 ♚b8", Some(Dtm::Loss(0))),
+    case("black ♔c8 ♖h7 ♚a8", Some(Dtm::Draw)), // stalemate
+    case("black ♔e1 ♖b7 ♚a8", Some(Dtm::Draw)), // the rook gets captured
+    case("white ♔b8 ♚b6 ♜h8", Some(Dtm::Loss(0))), // the colors are swapped
+    case("white ♔b6 ♖h1 ♚b8 ♟a2", None),
+    case("white ♔e1 ♕h1 ♚e8", None),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_probe_krk(game_config: &str, expected_dtm: Option<Dtm>) {
+        let game_state = game_config.parse::<GameState>().unwrap();
+        assert_eq!(get_krk_table().probe(&game_state), expected_dtm);
+    }
+
+    #[rstest(
+    signature, expected_longest_win,
+    case("KRK", 31),
+    case("KQK", 19),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_longest_win(signature: &str, expected_longest_win: i16) {
+        let table = EndgameTable::generate(&signature.parse::<MaterialSignature>().unwrap());
+        let longest_win = table.values.iter().copied().filter(|value| *value != UNKNOWN).max().unwrap();
+        assert_eq!(longest_win, expected_longest_win);
+    }
+
+    #[rstest(
+    plies, pruner,
+    case(1, PRUNER_L0),
+    case(3, PRUNER_L2),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_min_max_finds_the_mates_of_the_table(plies: u16, pruner: crate::engine::min_max::pruner::Pruner) {
+        let mates = get_krk_table().positions()
+            .filter(|(_, dtm)| *dtm == Dtm::Win(plies))
+            .step_by(997)
+            .take(5);
+        for (game_state, _) in mates {
+            let fen = format!("{} 0 1", game_state.get_fen_part1to4());
+            match evaluate(&fen, pruner) {
+                GameEvaluation::MoveToPlay(_, MoveEvaluation::EngineCheckMatesIn(moves)) => {
+                    assert_eq!(moves as u16, (plies - 1) / 2, "fen: {}", fen);
+                }
+                game_eval => panic!("fen: {}, unexpected evaluation: {:?}", fen, game_eval),
+            }
+        }
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let table = get_krk_table();
+        let path = std::env::temp_dir().join(format!("voidchess_krk_{}.vcet", std::process::id()));
+        table.save(&path).unwrap();
+        let loaded_table = EndgameTable::load(&path);
+        fs::remove_file(&path).unwrap();
+
+        let loaded_table = loaded_table.unwrap();
+        assert_eq!(loaded_table.signature(), table.signature());
+        assert!(loaded_table.values == table.values);
+    }
+
+    #[rstest(
+    bytes,
+    case(b"".as_slice()),
+    case(b"VCET\x01\x03KRK\x00\x00".as_slice()),
+    case(b"VCET\x02\x03KRK".as_slice()),
+    case(b"XXXX\x01\x03KRK".as_slice()),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_from_bytes_fails(bytes: &[u8]) {
+        let err = EndgameTable::from_bytes(bytes).err().unwrap();
+        assert!(matches!(err.kind, ErrorKind::IllegalFormat), "error kind: {:?}", err.kind);
+    }
+
+    #[test]
+    fn test_load_fails_on_missing_file() {
+        let err = EndgameTable::load("this/file/does/not/exist.vcet").err().unwrap();
+        assert!(matches!(err.kind, ErrorKind::IllegalConfig), "error kind: {:?}", err.kind);
+    }
+}
@@ -0,0 +1,107 @@
+use web_time::{Duration, Instant};
+use crate::base::Move;
+use crate::engine::evaluations::frontend::{GameEvaluation, MoveEvaluation};
+use crate::engine::limits::{evaluate_game_with_limits, SearchLimits};
+use crate::engine::search_config::SearchConfig;
+use crate::game::EpdRecord;
+
+/**
+ * the outcome of a single position of a test suite
+ */
+#[derive(Debug, Clone)]
+pub struct EpdResult {
+    pub evaluation: GameEvaluation,
+    pub is_solved: bool,
+    pub duration: Duration,
+}
+
+impl EpdResult {
+    pub fn get_move_played(&self) -> Option<Move> {
+        match self.evaluation {
+            GameEvaluation::MoveToPlay(a_move, _) => Some(a_move),
+            _ => None,
+        }
+    }
+}
+
+/**
+ * lets the engine search the position of the record (as deep as the pruner and the limits allow).
+ * Use MoveSelection::Deterministic in the config to get reproducible results.
+ */
+pub fn solve_epd_record(record: &EpdRecord, config: &SearchConfig, limits: SearchLimits) -> EpdResult {
+    let start = Instant::now();
+    let evaluation = evaluate_game_with_limits(&record.game, config, limits);
+    EpdResult {
+        is_solved: is_solved_by(record, &evaluation),
+        evaluation,
+        duration: start.elapsed(),
+    }
+}
+
+/**
+ * the positions of the suite one after the other
+ */
+pub fn run_epd_suite(records: &[EpdRecord], config: &SearchConfig, limits: SearchLimits) -> Vec<EpdResult> {
+    records.iter().map(|record| solve_epd_record(record, config, limits)).collect()
+}
+
+/**
+ * the engine has to play one of the best moves, none of the moves to avoid and mate in at most dm moves
+ * (as far as the record specifies them). A record that specifies none of them can't be solved.
+ */
+pub fn is_solved_by(record: &EpdRecord, evaluation: &GameEvaluation) -> bool {
+    let GameEvaluation::MoveToPlay(a_move, move_evaluation) = evaluation else {
+        return false;
+    };
+    let has_criteria = !record.best_moves.is_empty() || !record.avoid_moves.is_empty() || record.opt_mate_in.is_some();
+    let plays_best_move = record.best_moves.is_empty() || record.best_moves.contains(a_move);
+    let avoids_moves = !record.avoid_moves.contains(a_move);
+    let mates_in_time = match (record.opt_mate_in, move_evaluation) {
+        (None, _) => true,
+        // EngineCheckMatesIn counts the full moves after the one played
+        (Some(mate_in), MoveEvaluation::EngineCheckMatesIn(moves_after)) => (*moves_after as usize) < mate_in,
+        (Some(_), _) => false,
+    };
+    has_criteria && plays_best_move && avoids_moves && mates_in_time
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+    use crate::engine::min_max::pruner::*;
+    use crate::engine::search_config::MoveSelection;
+
+    #[rstest(
+    epd, expected_is_solved,
+    case("6k1/5ppp/8/8/8/8/5PPP/R5K1 w - - bm Ra8; id \"back rank\";", true),
+    case("6k1/5ppp/8/8/8/8/5PPP/R5K1 w - - dm 1;", true),
+    case("6k1/5ppp/8/8/8/8/5PPP/R5K1 w - - am Ra8;", false),
+    case("6k1/5ppp/8/8/8/8/5PPP/R5K1 w - - bm Ra7;", false),
+    case("6k1/5ppp/8/8/8/8/5PPP/R5K1 w - -", false),
+    case("6k1/8/8/8/8/8/5PPP/q5K1 w - - dm 1;", false),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_solve_epd_record(epd: &str, expected_is_solved: bool) {
+        let record = epd.parse::<EpdRecord>().unwrap();
+        let config = SearchConfig::new(PRUNER_L1).with_move_selection(MoveSelection::Deterministic);
+        let result = solve_epd_record(&record, &config, SearchLimits::default());
+        assert_eq!(result.is_solved, expected_is_solved, "{:?}", result);
+    }
+
+    #[test]
+    fn test_run_epd_suite() {
+        let records: Vec<EpdRecord> = [
+            "6k1/5ppp/8/8/8/8/5PPP/R5K1 w - - bm Ra8;",
+            "r5k1/5ppp/8/8/8/8/5PPP/6K1 b - - bm Ra1;",
+            "6k1/5ppp/8/8/8/8/5PPP/R5K1 w - - bm Kf1;",
+        ].iter().map(|epd| epd.parse::<EpdRecord>().unwrap()).collect();
+        let config = SearchConfig::new(PRUNER_L1).with_move_selection(MoveSelection::Deterministic);
+        let results = run_epd_suite(&records, &config, SearchLimits::default().with_node_limit(1_000_000));
+        let solved: Vec<bool> = results.iter().map(|result| result.is_solved).collect();
+        assert_eq!(solved, vec![true, true, false]);
+        assert_eq!(results[0].get_move_played(), Some(Move::from_code("a1-a8")));
+    }
+}
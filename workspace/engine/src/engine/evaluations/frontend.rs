@@ -1,4 +1,6 @@
 use super::*;
+use crate::base::ChessError;
+use crate::engine::evaluations::score::Score;
 use crate::engine::evaluations::frontend::MoveEvaluation::*;
 use crate::engine::evaluations::frontend::GameEvaluation::*;
 
@@ -6,7 +8,7 @@ use crate::engine::evaluations::frontend::GameEvaluation::*;
 pub enum GameEvaluation {
     GameEnded(GameEndResult),
     MoveToPlay(Move, MoveEvaluation),
-    Err(String),
+    Err(ChessError),
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
@@ -47,9 +49,9 @@ impl PartialEq for GameEvaluation {
                     false
                 }
             }
-            Err(msg) => {
-                if let Err(other_msg) = other {
-                    msg == other_msg
+            Err(err) => {
+                if let Err(other_err) = other {
+                    err == other_err
                 } else {
                     false
                 }
@@ -62,10 +64,16 @@ impl Eq for GameEvaluation {}
 
 impl PartialEq for MoveEvaluation {
     fn eq(&self, other: &Self) -> bool {
-        // this is clearly a hack, but the non-hacky version failed for some reason
-        let debug_self = format!("{:?}", self);
-        let debug_other = format!("{:?}", other);
-        debug_self == debug_other
+        // the numeric values are compared bitwise, so that Eq holds (and -0.0 != 0.0 like their Debug output)
+        match (self, other) {
+            (EngineCheckMatesIn(self_moves), EngineCheckMatesIn(other_moves)) => self_moves == other_moves,
+            (Numeric(self_value), Numeric(other_value)) => self_value.to_bits() == other_value.to_bits(),
+            (Draw(self_reason), Draw(other_reason)) => self_reason == other_reason,
+            (EngineGetsCheckMatedIn(self_moves, self_value), EngineGetsCheckMatedIn(other_moves, other_value)) => {
+                self_moves == other_moves && self_value.to_bits() == other_value.to_bits()
+            }
+            _ => false,
+        }
     }
 }
 
@@ -85,6 +93,12 @@ impl MoveEvaluation {
     }
 }
 
+impl MoveEvaluation {
+    pub(crate) fn from_score(score: Score) -> MoveEvaluation {
+        MoveEvaluation::from(&score.to_evaluation())
+    }
+}
+
 impl Ord for MoveEvaluation {
     fn cmp(&self, other: &Self) -> Ordering {
         self.partial_cmp(other).unwrap()
@@ -3,6 +3,7 @@ use crate::base::Move;
 use crate::engine::evaluations::frontend::MoveEvaluation;
 
 pub mod frontend;
+pub mod score;
 pub(crate) mod testing;
 
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -19,6 +20,8 @@ pub enum DrawReason {
     InsufficientMaterial,
     ThreeTimesRepetition,
     NoChangeIn50Moves,
+    // the position is a theoretical draw according to the endgame tablebase
+    EndgameTablebase,
 }
 
 pub const MIN_EVALUATION: Evaluation = Evaluation::LoseIn(0, f32::MIN);
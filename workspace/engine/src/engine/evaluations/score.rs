@@ -0,0 +1,248 @@
+use std::fmt;
+use crate::engine::evaluations::{DrawReason, Evaluation};
+
+// numeric scores are stored times this factor, so that the draws fit between two centipawns
+const NUMERIC_FACTOR: i32 = 8;
+const MAX_CENTIPAWNS: i32 = 1_000_000;
+// the draws in the order of the Evaluation ordering, the best one first
+const DRAW_REASONS: [DrawReason; 5] = [
+    DrawReason::StaleMate,
+    DrawReason::InsufficientMaterial,
+    DrawReason::ThreeTimesRepetition,
+    DrawReason::NoChangeIn50Moves,
+    DrawReason::EndgameTablebase,
+];
+const WIN_BASE: i32 = 1 << 30;
+const LOSE_BASE: i32 = -(1 << 30);
+// every number of half moves of LoseIn gets a slot of this size for the numeric tie-break
+const LOSE_SLOT_SIZE: i32 = 2 * MAX_CENTIPAWNS + 1;
+const MAX_MATE_HALF_STEPS: i32 = u8::MAX as i32;
+
+/**
+ * An Evaluation packed into an i32, so that comparing two scores or testing them for equality
+ * is a single integer operation. The ordering is the one of Evaluation, from the lowest to the highest score:
+ * - LoseIn(n, eval): the fewer half moves the worse, the same number of half moves ordered by the numeric eval
+ * - negative numeric scores
+ * - Draw (a StaleMate being the best one), unless moved by a contempt
+ * - numeric scores >= 0
+ * - WinIn(n): the fewer half moves the better
+ *
+ * Numeric evaluations are stored in centipawns (and limited to +/- 10000 pawns).
+ */
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Score(i32);
+
+impl Score {
+    pub const MIN: Score = Score(LOSE_BASE);
+    pub const MAX: Score = Score(WIN_BASE);
+
+    pub fn win_in(half_steps: u8) -> Score {
+        Score(WIN_BASE - half_steps as i32)
+    }
+
+    /**
+     * the numeric evaluation (in pawns) orders losses in the same number of half steps
+     */
+    pub fn lose_in(half_steps: u8, pawns: f32) -> Score {
+        let tie_break = to_centipawns(pawns) + MAX_CENTIPAWNS;
+        Score(LOSE_BASE + half_steps as i32 * LOSE_SLOT_SIZE + tie_break)
+    }
+
+    pub fn draw(reason: DrawReason) -> Score {
+        Score::draw_with_contempt(reason, 0)
+    }
+
+    /**
+     * a draw ranked (just below) like a numeric score of -contempt_in_centipawns.
+     * With a positive contempt the engine avoids draws (e.g. against a weaker opponent),
+     * with a negative one it seeks them (e.g. against a stronger opponent).
+     */
+    pub fn draw_with_contempt(reason: DrawReason, contempt_in_centipawns: i32) -> Score {
+        let index = DRAW_REASONS.iter()
+            .position(|draw_reason| *draw_reason == reason)
+            .expect("every DrawReason is listed") as i32;
+        let contempt = contempt_in_centipawns.clamp(1 - MAX_CENTIPAWNS, MAX_CENTIPAWNS - 1);
+        Score(-contempt * NUMERIC_FACTOR - 1 - index)
+    }
+
+    pub fn from_centipawns(centipawns: i32) -> Score {
+        Score(centipawns.clamp(-MAX_CENTIPAWNS, MAX_CENTIPAWNS) * NUMERIC_FACTOR)
+    }
+
+    pub fn from_pawns(pawns: f32) -> Score {
+        Score::from_centipawns(to_centipawns(pawns))
+    }
+
+    /**
+     * the score that is the given number of centipawns lower. Draws are lowered like the numeric score they are ranked with,
+     * mate scores return None, since there are no scores "close" to them.
+     */
+    pub fn lowered_by(&self, centipawns: i32) -> Option<Score> {
+        let is_numeric_or_draw = self.0.abs() <= MAX_CENTIPAWNS * NUMERIC_FACTOR;
+        is_numeric_or_draw.then(|| Score((self.0 - centipawns * NUMERIC_FACTOR).max(-MAX_CENTIPAWNS * NUMERIC_FACTOR)))
+    }
+
+    pub fn is_numeric(&self) -> bool {
+        self.get_centipawns().is_some()
+    }
+
+    pub fn get_centipawns(&self) -> Option<i32> {
+        let is_in_numeric_range = self.0.abs() <= MAX_CENTIPAWNS * NUMERIC_FACTOR;
+        (is_in_numeric_range && self.0 % NUMERIC_FACTOR == 0).then_some(self.0 / NUMERIC_FACTOR)
+    }
+
+    pub fn to_evaluation(&self) -> Evaluation {
+        if self.0 > WIN_BASE - MAX_MATE_HALF_STEPS - 1 {
+            return Evaluation::WinIn((WIN_BASE - self.0) as u8);
+        }
+        if self.0 < LOSE_BASE + (MAX_MATE_HALF_STEPS + 1) * LOSE_SLOT_SIZE {
+            let offset = self.0 - LOSE_BASE;
+            let tie_break = (offset % LOSE_SLOT_SIZE) - MAX_CENTIPAWNS;
+            return Evaluation::LoseIn((offset / LOSE_SLOT_SIZE) as u8, to_pawns(tie_break));
+        }
+        match self.get_centipawns() {
+            Some(centipawns) => Evaluation::Numeric(to_pawns(centipawns)),
+            None => Evaluation::Draw(DRAW_REASONS[(NUMERIC_FACTOR - 1 - self.0.rem_euclid(NUMERIC_FACTOR)) as usize]),
+        }
+    }
+}
+
+impl From<Evaluation> for Score {
+    fn from(evaluation: Evaluation) -> Self {
+        match evaluation {
+            Evaluation::WinIn(half_steps) => Score::win_in(half_steps),
+            Evaluation::Numeric(pawns) => Score::from_pawns(pawns),
+            Evaluation::Draw(reason) => Score::draw(reason),
+            Evaluation::LoseIn(half_steps, pawns) => Score::lose_in(half_steps, pawns),
+        }
+    }
+}
+
+impl fmt::Debug for Score {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Score({:?})", self.to_evaluation())
+    }
+}
+
+fn to_centipawns(pawns: f32) -> i32 {
+    // the cast saturates (and maps NaN to 0)
+    ((pawns * 100.0).round() as i32).clamp(-MAX_CENTIPAWNS, MAX_CENTIPAWNS)
+}
+
+fn to_pawns(centipawns: i32) -> f32 {
+    centipawns as f32 / 100.0
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+    use crate::engine::evaluations::{MAX_EVALUATION, MIN_EVALUATION};
+
+    #[rstest(
+    evaluation,
+    case(Evaluation::WinIn(0)),
+    case(Evaluation::WinIn(1)),
+    case(Evaluation::WinIn(255)),
+    case(Evaluation::Numeric(3.4)),
+    case(Evaluation::Numeric(-0.25)),
+    case(Evaluation::Numeric(0.0)),
+    case(Evaluation::Numeric(10000.0)),
+    case(Evaluation::Numeric(-10000.0)),
+    case(Evaluation::Draw(DrawReason::StaleMate)),
+    case(Evaluation::Draw(DrawReason::EndgameTablebase)),
+    case(Evaluation::LoseIn(0, 0.0)),
+    case(Evaluation::LoseIn(3, -7.5)),
+    case(Evaluation::LoseIn(255, 10000.0)),
+    case(Evaluation::LoseIn(255, -10000.0)),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_round_trip(evaluation: Evaluation) {
+        assert_eq!(Score::from(evaluation).to_evaluation(), evaluation);
+    }
+
+    #[test]
+    fn test_min_and_max() {
+        assert_eq!(Score::from(MIN_EVALUATION), Score::MIN);
+        assert_eq!(Score::from(MAX_EVALUATION), Score::MAX);
+    }
+
+    #[test]
+    fn test_ordering_matches_evaluation() {
+        let evaluations = [
+            Evaluation::LoseIn(1, 1.0),
+            Evaluation::LoseIn(3, -7.0),
+            Evaluation::LoseIn(3, 3.0),
+            Evaluation::LoseIn(3, 5.0),
+            Evaluation::LoseIn(4, -5.0),
+            Evaluation::Numeric(-10000.0),
+            Evaluation::Numeric(-3.0),
+            Evaluation::Numeric(-0.01),
+            Evaluation::Draw(DrawReason::EndgameTablebase),
+            Evaluation::Draw(DrawReason::NoChangeIn50Moves),
+            Evaluation::Draw(DrawReason::ThreeTimesRepetition),
+            Evaluation::Draw(DrawReason::InsufficientMaterial),
+            Evaluation::Draw(DrawReason::StaleMate),
+            Evaluation::Numeric(0.0),
+            Evaluation::Numeric(0.4),
+            Evaluation::Numeric(10000.0),
+            Evaluation::WinIn(255),
+            Evaluation::WinIn(3),
+            Evaluation::WinIn(1),
+        ];
+        for window in evaluations.windows(2) {
+            assert!(window[0] < window[1], "{:?} < {:?}", window[0], window[1]);
+            assert!(Score::from(window[0]) < Score::from(window[1]), "{:?} < {:?}", window[0], window[1]);
+        }
+    }
+
+    #[rstest(
+    pawns, expected_centipawns,
+    case(0.004, 0),
+    case(0.255, 26),
+    case(-1.5, -150),
+    case(f32::MAX, MAX_CENTIPAWNS),
+    case(f32::MIN, -MAX_CENTIPAWNS),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_from_pawns(pawns: f32, expected_centipawns: i32) {
+        assert_eq!(Score::from_pawns(pawns).get_centipawns(), Some(expected_centipawns));
+    }
+
+    #[rstest(
+    contempt, expected_lower_centipawns,
+    case(0, -1),
+    case(25, -26),
+    case(-25, 24),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_draw_with_contempt(contempt: i32, expected_lower_centipawns: i32) {
+        for reason in DRAW_REASONS {
+            let draw = Score::draw_with_contempt(reason, contempt);
+            assert_eq!(draw.to_evaluation(), Evaluation::Draw(reason));
+            assert!(draw < Score::from_centipawns(expected_lower_centipawns + 1), "{:?}", reason);
+            assert!(draw > Score::from_centipawns(expected_lower_centipawns), "{:?}", reason);
+        }
+        assert!(Score::draw_with_contempt(DrawReason::StaleMate, contempt) > Score::draw_with_contempt(DrawReason::InsufficientMaterial, contempt));
+    }
+
+    #[test]
+    fn test_lowered_by() {
+        assert_eq!(Score::from_centipawns(30).lowered_by(20), Some(Score::from_centipawns(10)));
+        assert_eq!(Score::from_centipawns(-MAX_CENTIPAWNS).lowered_by(20), Some(Score::from_centipawns(-MAX_CENTIPAWNS)));
+        let lowered_draw = Score::draw_with_contempt(DrawReason::ThreeTimesRepetition, 10).lowered_by(20).unwrap();
+        assert!(lowered_draw < Score::from_centipawns(-30) && lowered_draw > Score::from_centipawns(-31), "{:?}", lowered_draw);
+        assert_eq!(Score::win_in(3).lowered_by(20), None);
+        assert_eq!(Score::lose_in(3, 0.0).lowered_by(20), None);
+    }
+
+    #[test]
+    fn test_only_numeric_scores_have_centipawns() {
+        assert!(!Score::draw(DrawReason::StaleMate).is_numeric());
+        assert!(!Score::win_in(3).is_numeric());
+        assert!(!Score::lose_in(3, 0.0).is_numeric());
+        assert!(Score::from_pawns(-2.0).is_numeric());
+    }
+}
@@ -0,0 +1,117 @@
+use web_time::{Duration, Instant};
+use crate::engine::{choose_next_move_with, deepen_root_moves, get_eval_type_for, init_game, to_evaluated_moves, to_game_evaluation, OngoingGameOrEvaluation};
+use crate::engine::evaluations::frontend::GameEvaluation;
+use crate::engine::min_max::get_nodes_searched;
+use crate::engine::search_config::SearchConfig;
+use crate::game::Game;
+
+/**
+ * how much the search may use for one move. The limits are only checked between the steps of the
+ * iterative deepening (see Pruner::get_deepening_steps), so a step that was started is finished.
+ * Without limits the search is the same as the one of engine::evaluate_with_config.
+ */
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub struct SearchLimits {
+    pub opt_time_limit: Option<Duration>,
+    pub opt_node_limit: Option<u64>,
+}
+
+impl SearchLimits {
+    pub fn with_time_limit(self, time_limit: Duration) -> SearchLimits {
+        SearchLimits {
+            opt_time_limit: Some(time_limit),
+            ..self
+        }
+    }
+
+    pub fn with_node_limit(self, node_limit: u64) -> SearchLimits {
+        SearchLimits {
+            opt_node_limit: Some(node_limit),
+            ..self
+        }
+    }
+}
+
+/**
+ * like engine::evaluate_with_config, but the search gets deeper step by step (up to the pruner of the config)
+ * as long as the limits allow
+ */
+pub fn evaluate_with_limits(game_config: &str, config: &SearchConfig, limits: SearchLimits) -> GameEvaluation {
+    let game = match init_game(game_config) {
+        OngoingGameOrEvaluation::Ongoing(game) => {game}
+        OngoingGameOrEvaluation::Ended(final_eval) => {return final_eval;}
+    };
+    evaluate_game_with_limits(&game, config, limits)
+}
+
+/**
+ * like evaluate_with_limits, but for an already parsed game
+ */
+pub fn evaluate_game_with_limits(game: &Game, config: &SearchConfig, limits: SearchLimits) -> GameEvaluation {
+    let eval_type = get_eval_type_for(game, config.opt_eval_params);
+
+    #[cfg(feature = "syzygy")]
+    if let Some((tablebase_move, evaluation)) = crate::engine::tablebase::get_tablebase_move(game, eval_type) {
+        use crate::engine::evaluations::EvaluatedMove;
+        use crate::engine::evaluations::frontend::MoveEvaluation;
+        return to_game_evaluation(game, EvaluatedMove { a_move: tablebase_move, evaluation: MoveEvaluation::from(&evaluation) });
+    }
+
+    let start = Instant::now();
+    let nodes_before = get_nodes_searched();
+    let evaluations = deepen_root_moves(game, config.pruner.get_deepening_steps(), eval_type, config.contempt_in_centipawns, config.move_selection, || {
+        let is_out_of_time = limits.opt_time_limit.is_some_and(|time_limit| start.elapsed() >= time_limit);
+        let is_out_of_nodes = limits.opt_node_limit.is_some_and(|node_limit| get_nodes_searched() - nodes_before >= node_limit);
+        is_out_of_time || is_out_of_nodes
+    });
+    let evaluated_moves = to_evaluated_moves(evaluations);
+    to_game_evaluation(game, choose_next_move_with(evaluated_moves, config.move_selection, config.contempt_in_centipawns))
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+    use crate::engine::evaluate_with_config;
+    use crate::engine::evaluations::DrawReason;
+    use crate::engine::evaluations::frontend::GameEndResult;
+    use crate::engine::min_max::pruner::*;
+    use crate::engine::search_config::MoveSelection;
+
+    //♔♕♗♘♖♙♚♛♝♞♜♟
+
+    #[rstest(
+    game_config, pruner,
+    case("white ♔g3 ♖d2 ♚g1 ♙c2 ♙d3", PRUNER_L2),
+    case("e2-e4 d7-d5", PRUNER_L2),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_unreached_limits_search_as_deep_as_the_pruner(game_config: &str, pruner: Pruner) {
+        let config = SearchConfig::new(pruner).with_move_selection(MoveSelection::Deterministic);
+        let expected_evaluation = evaluate_with_config(game_config, &config);
+        let limits = SearchLimits::default().with_node_limit(u64::MAX).with_time_limit(Duration::from_secs(3600));
+        assert_eq!(evaluate_with_limits(game_config, &config, limits), expected_evaluation);
+        assert_eq!(evaluate_with_limits(game_config, &config, SearchLimits::default()), expected_evaluation);
+    }
+
+    #[test]
+    fn test_node_limit_stops_the_deepening() {
+        let game = "e2-e4 e7-e5 g1-f3 b8-c6".parse::<Game>().unwrap();
+        let config = SearchConfig::new(PRUNER_L2).with_move_selection(MoveSelection::Deterministic);
+        let count_nodes = |limits: SearchLimits| {
+            let nodes_before = get_nodes_searched();
+            assert!(matches!(evaluate_game_with_limits(&game, &config, limits), GameEvaluation::MoveToPlay(_, _)));
+            get_nodes_searched() - nodes_before
+        };
+        let nodes_with_limit = count_nodes(SearchLimits::default().with_node_limit(1));
+        assert!(nodes_with_limit < count_nodes(SearchLimits::default()), "{}", nodes_with_limit);
+    }
+
+    #[test]
+    fn test_ended_game() {
+        let evaluation = evaluate_with_limits("black ♔b6 ♙a7 ♚a8", &SearchConfig::new(PRUNER_L2), SearchLimits::default().with_node_limit(10));
+        assert_eq!(evaluation, GameEvaluation::GameEnded(GameEndResult::Draw(DrawReason::StaleMate)));
+    }
+}
@@ -0,0 +1,183 @@
+use crate::base::Move;
+use crate::game::{Game, MoveResult, StoppedReason};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum MateSearchMode {
+    AllMoves,
+    /**
+     * the attacker only considers checks. This is a lot faster, but can miss mates that start with a quiet move.
+     */
+    OnlyChecks,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum MateSearchResult {
+    /**
+     * the active color mates in the given number of moves. The line contains the moves of both colors,
+     * the defender always picking the reply that delays the mate the longest.
+     */
+    MateIn(usize, Vec<Move>),
+    /**
+     * there is no forced mate within the given number of moves (considering only checks in OnlyChecks mode)
+     */
+    NoMateWithin(usize),
+}
+
+/**
+ * a proof search for the shortest forced mate of the active color within max_moves of its moves.
+ * Unlike the min-max search there is no static evaluation and no pruning of the defender's replies,
+ * so a found mate is guaranteed.
+ */
+pub fn find_mate(game: &Game, max_moves: usize, mode: MateSearchMode) -> MateSearchResult {
+    match find_shortest_mate(game, max_moves, mode) {
+        None => MateSearchResult::NoMateWithin(max_moves),
+        Some((mate_in, _)) => MateSearchResult::MateIn(mate_in, get_mate_line(game, mate_in, mode)),
+    }
+}
+
+/**
+ * returns the number of moves to mate and the first move of the attacker
+ */
+fn find_shortest_mate(game: &Game, max_moves: usize, mode: MateSearchMode) -> Option<(usize, Move)> {
+    (1..=max_moves).find_map(|mate_in| find_mating_move(game, mate_in, mode).map(|a_move| (mate_in, a_move)))
+}
+
+/**
+ * the line of the shortest mate: the attacker plays the fastest mating move, the defender the reply that takes longest to mate
+ */
+fn get_mate_line(game: &Game, mate_in: usize, mode: MateSearchMode) -> Vec<Move> {
+    let mut line: Vec<Move> = Vec::with_capacity(mate_in * 2 - 1);
+    let mut current_game = game.clone();
+    let mut current_mate_in = mate_in;
+    loop {
+        let (_, attacker_move) = find_shortest_mate(&current_game, current_mate_in, mode)
+            .expect("the mate was found before, so it's still there");
+        line.push(attacker_move);
+        let defender_game = match current_game.play(attacker_move) {
+            MoveResult::Ongoing(game, _) => game,
+            MoveResult::Stopped(_, _) => unreachable!("a mating move doesn't stop the game"),
+        };
+        let mut longest_resistance: Option<(usize, Move, Box<Game>)> = None;
+        for (reply, game_after_reply) in get_legal_moves(&defender_game) {
+            let (reply_mate_in, _) = find_shortest_mate(&game_after_reply, current_mate_in - 1, mode)
+                .expect("every reply of the defender leads to a mate");
+            if longest_resistance.as_ref().is_none_or(|(longest_mate_in, _, _)| reply_mate_in > *longest_mate_in) {
+                longest_resistance = Some((reply_mate_in, reply, game_after_reply));
+            }
+        }
+        match longest_resistance {
+            // checkmate
+            None => { return line; }
+            Some((reply_mate_in, reply, game_after_reply)) => {
+                line.push(reply);
+                current_game = *game_after_reply;
+                current_mate_in = reply_mate_in;
+            }
+        }
+    }
+}
+
+/**
+ * returns a move of the active color that forces a mate within mate_in moves
+ */
+fn find_mating_move(game: &Game, mate_in: usize, mode: MateSearchMode) -> Option<Move> {
+    for a_move in game.get_reachable_moves().iter() {
+        let defender_game = match game.play(*a_move) {
+            MoveResult::Ongoing(defender_game, _) => defender_game,
+            // either the move was illegal or the game ended in a draw
+            MoveResult::Stopped(_, _) => { continue; }
+        };
+        // the mating move itself is always a check
+        let is_check_needed = mate_in == 1 || mode == MateSearchMode::OnlyChecks;
+        if is_check_needed && !defender_game.is_active_king_in_check() {
+            continue;
+        }
+        if is_mate_forced(&defender_game, mate_in, mode) {
+            return Some(*a_move);
+        }
+    }
+    None
+}
+
+/**
+ * the defender (the active color) gets mated within mate_in moves of the attacker (the one just played included)
+ */
+fn is_mate_forced(defender_game: &Game, mate_in: usize, mode: MateSearchMode) -> bool {
+    let mut has_legal_reply = false;
+    for a_move in defender_game.get_reachable_moves().iter() {
+        match defender_game.play(*a_move) {
+            MoveResult::Stopped(StoppedReason::KingInCheckAfterMove, _) => {}
+            // the defender escapes into a draw
+            MoveResult::Stopped(_, _) => { return false; }
+            MoveResult::Ongoing(attacker_game, _) => {
+                has_legal_reply = true;
+                if mate_in == 1 || find_mating_move(&attacker_game, mate_in - 1, mode).is_none() {
+                    return false;
+                }
+            }
+        }
+    }
+    // without a legal reply it's either checkmate or stalemate
+    has_legal_reply || defender_game.is_active_king_in_check()
+}
+
+fn get_legal_moves(game: &Game) -> Vec<(Move, Box<Game>)> {
+    game.get_reachable_moves().iter().filter_map(|a_move| match game.play(*a_move) {
+        MoveResult::Ongoing(new_game, _) => Some((*a_move, new_game)),
+        MoveResult::Stopped(_, _) => None,
+    }).collect()
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    //♔♕♗♘♖♙♚♛♝♞♜♟
+
+    #[rstest(
+    game_config, max_moves, mode, expected_line,
+    case("white ♔g3 ♖d2 ♚g1 ♙c2 ♙d3", 3, MateSearchMode::AllMoves, "d2-d1"),
+    case("white ♔g3 ♖d2 ♚g1 ♙c2 ♙d3", 1, MateSearchMode::OnlyChecks, "d2-d1"),
+    case("black ♚g6 ♜d7 ♔g8 ♟c7 ♟d6", 2, MateSearchMode::AllMoves, "d7-d8"),
+    case("white ♔f3 ♖d2 ♚h1 ♙c2 ♙d3", 2, MateSearchMode::AllMoves, "f3-g3 h1-g1 d2-d1"),
+    case("white ♔b6 ♖h2 ♚b8", 2, MateSearchMode::AllMoves, "h2-h8"),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_find_mate(game_config: &str, max_moves: usize, mode: MateSearchMode, expected_line: &str) {
+        let game = game_config.parse::<Game>().unwrap();
+        let expected_moves: Vec<Move> = expected_line.split(' ').map(Move::from_code).collect();
+        let expected_mate_in = expected_moves.len().div_ceil(2);
+        assert_eq!(find_mate(&game, max_moves, mode), MateSearchResult::MateIn(expected_mate_in, expected_moves));
+    }
+
+    #[rstest(
+    game_config, max_moves, mode,
+    case("white ♔f3 ♖d2 ♚h1 ♙c2 ♙d3", 1, MateSearchMode::AllMoves),
+    case("white ♔f3 ♖d2 ♚h1 ♙c2 ♙d3", 2, MateSearchMode::OnlyChecks), // the mate starts with a quiet king move
+    case("white ♔b6 ♙a7 ♚a8", 2, MateSearchMode::AllMoves), // black is stalemate
+    case("white ♔c6 ♖h2 ♚a8", 1, MateSearchMode::AllMoves),
+    case("white ♔e1 ♕d1 ♚e8 ♜a8 ♝c8", 2, MateSearchMode::OnlyChecks),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_find_no_mate(game_config: &str, max_moves: usize, mode: MateSearchMode) {
+        let game = game_config.parse::<Game>().unwrap();
+        assert_eq!(find_mate(&game, max_moves, mode), MateSearchResult::NoMateWithin(max_moves));
+    }
+
+    #[test]
+    fn test_mate_line_delays_the_mate() {
+        // white mates in 3, the line has to contain 5 half moves no matter which reply the defender picks
+        let game = "white ♔e3 ♖d2 ♚g1 ♙c2 ♙d3".parse::<Game>().unwrap();
+        match find_mate(&game, 3, MateSearchMode::AllMoves) {
+            MateSearchResult::MateIn(mate_in, line) => {
+                assert_eq!(mate_in, 3);
+                assert_eq!(line.len(), 5, "line: {:?}", line);
+                assert_eq!(line[0], Move::from_code("e3-f3"));
+            }
+            result => panic!("unexpected result: {:?}", result),
+        }
+    }
+}
@@ -1,39 +1,142 @@
+use std::cell::Cell;
 use crate::game::{*};
-use crate::engine::evaluations::{Evaluation, DrawReason, MIN_EVALUATION, MAX_EVALUATION};
-use crate::base::{Color, Move, Moves};
+use crate::engine::evaluations::{Evaluation, DrawReason};
+use crate::engine::evaluations::score::Score;
+use crate::base::{Color, Move, MoveType, Moves};
 use crate::engine::static_eval::{static_eval, StaticEvalType};
 use crate::engine::min_max::pruner::Pruner;
 
 pub mod pruner;
 
+thread_local! {
+    static NODES_SEARCHED: Cell<u64> = const { Cell::new(0) };
+}
+
+/**
+ * the number of moves the search has played on the current thread so far.
+ * The difference before and after a search is the number of nodes it searched.
+ */
+pub fn get_nodes_searched() -> u64 {
+    NODES_SEARCHED.with(|nodes_searched| nodes_searched.get())
+}
+
+fn count_node() {
+    NODES_SEARCHED.with(|nodes_searched| nodes_searched.set(nodes_searched.get() + 1));
+}
+
 pub fn evaluate_move(
     old_game: &Game,
     a_move: Move,
     pruner: Pruner,
     evaluate_for: Color,
-    current_max_one_level_up: Evaluation,
+    current_max_one_level_up: Score,
     eval_type: StaticEvalType,
-) -> Evaluation {
+) -> Score {
+    evaluate_move_in_window(
+        old_game,
+        a_move,
+        pruner,
+        evaluate_for,
+        Window { alpha: current_max_one_level_up, beta: Score::MAX },
+        eval_type,
+        0,
+    )
+}
+
+/**
+ * like evaluate_move, but an evaluation outside of the window is only a bound:
+ * one below (or equal to) window.alpha is at least as good as the actual one, one above window.beta at most as good.
+ * Draws are ranked like a numeric score of -contempt centipawns (see Score::draw_with_contempt).
+ */
+pub fn evaluate_move_in_window(
+    old_game: &Game,
+    a_move: Move,
+    pruner: Pruner,
+    evaluate_for: Color,
+    window: Window,
+    eval_type: StaticEvalType,
+    contempt: i32,
+) -> Score {
     get_min_after(
         OldGameData {
             old_game,
             old_half_step: 0,
             was_check: false,
             old_move_stats: MoveStats::default(),
+            old_reduction: 0,
+            late_move_reduction: 0,
         },
         a_move,
         pruner,
         evaluate_for,
-        current_max_one_level_up,
+        window,
         eval_type,
+        contempt,
     )
 }
 
+/**
+ * The range of evaluations the search is interested in: alpha is what the maximizing side is already guaranteed,
+ * beta what the minimizing side is already guaranteed.
+ * Since the next larger i32 of a packed Score isn't necessarily a valid Score,
+ * a null window is represented by alpha == beta. It only answers whether the actual score is larger than alpha.
+ */
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Window {
+    pub alpha: Score,
+    pub beta: Score,
+}
+
+impl Window {
+    pub const FULL: Window = Window { alpha: Score::MIN, beta: Score::MAX };
+
+    pub fn null_window(bound: Score) -> Window {
+        Window { alpha: bound, beta: bound }
+    }
+
+    pub fn is_null_window(&self) -> bool {
+        self.alpha == self.beta
+    }
+
+    /**
+     * the maximizing side found an evaluation the minimizing side won't allow
+     */
+    pub fn fails_high(&self, eval: Score) -> bool {
+        if self.is_null_window() {
+            eval > self.beta
+        } else {
+            eval >= self.beta
+        }
+    }
+
+    /**
+     * the minimizing side found an evaluation the maximizing side won't allow
+     */
+    pub fn fails_low(&self, eval: Score) -> bool {
+        eval <= self.alpha
+    }
+}
+
 struct OldGameData<'a> {
     old_game: &'a Game,
     old_half_step: usize,
     was_check: bool,
     old_move_stats: MoveStats,
+    // the half steps the search depth is already reduced by (through null moves and late move reductions)
+    old_reduction: usize,
+    // the additional reduction if the move turns out to be quiet
+    late_move_reduction: usize,
+}
+
+/**
+ * the data of a position that is searched further
+ */
+struct NodeData {
+    half_step: usize,
+    is_check: bool,
+    move_stats: MoveStats,
+    reduction: usize,
+    is_after_null_move: bool,
 }
 
 fn get_max_after(
@@ -41,63 +144,125 @@ fn get_max_after(
     a_move: Move,
     pruner: Pruner,
     evaluate_for: Color,
-    current_min_one_level_up: Evaluation,
-    eval_type: StaticEvalType
-) -> Evaluation {
+    window: Window,
+    eval_type: StaticEvalType,
+    contempt: i32
+) -> Score {
     let move_result = old_game_data.old_game.play(a_move);
     let new_half_step = old_game_data.old_half_step + 1;
+    count_node();
 
     debug_assert!(new_half_step%2==0, "get_min's new_half_step is supposed to be even, but was {}", new_half_step);
 
     return match move_result {
         MoveResult::Stopped(reason, _) => {
             match reason {
-                StoppedReason::KingInCheckAfterMove => Evaluation::WinIn(new_half_step as u8),
-                StoppedReason::InsufficientMaterial => Evaluation::Draw(DrawReason::InsufficientMaterial),
-                StoppedReason::ThreeTimesRepetition => Evaluation::Draw(DrawReason::ThreeTimesRepetition),
-                StoppedReason::NoChangeIn50Moves => Evaluation::Draw(DrawReason::ThreeTimesRepetition),
+                StoppedReason::KingInCheckAfterMove => Score::win_in(new_half_step as u8),
+                StoppedReason::InsufficientMaterial => Score::draw_with_contempt(DrawReason::InsufficientMaterial, contempt),
+                StoppedReason::ThreeTimesRepetition => Score::draw_with_contempt(DrawReason::ThreeTimesRepetition, contempt),
+                StoppedReason::NoChangeIn50Moves => Score::draw_with_contempt(DrawReason::ThreeTimesRepetition, contempt),
             }
         }
         MoveResult::Ongoing(game, move_stats) => {
+            if move_stats.did_catch_figure {
+                if let Some(tablebase_eval) = probe_tablebase(&game, new_half_step, evaluate_for, eval_type, contempt) {
+                    return tablebase_eval;
+                }
+            }
             let is_check = game.is_active_king_in_check();
-            if pruner.should_stop_min_max_ing(new_half_step, move_stats, old_game_data.old_move_stats, is_check, old_game_data.was_check) {
-                return if game.is_active_king_checkmate() {
+            let reduction = get_reduction(&old_game_data, move_stats, is_check);
+            let extension_stats = without_losing_capture(old_game_data.old_game, a_move, move_stats);
+            if pruner.should_stop_min_max_ing(new_half_step + reduction, extension_stats, old_game_data.old_move_stats, is_check, old_game_data.was_check) {
+                return if is_check && game.is_active_king_checkmate() {
                     get_lose_eval(game.get_game_state(), new_half_step + 1, evaluate_for, eval_type)
                 } else {
-                    Evaluation::Numeric(static_eval(game.get_game_state(), eval_type, evaluate_for))
-                }
-            }
-            let moves = game.get_reachable_moves();
-            let mut current_max = MIN_EVALUATION;
-            for next_move in scramble(moves).iter() {
-                let eval = get_min_after(
-                    OldGameData {
-                        old_game: &game,
-                        old_half_step: new_half_step,
-                        was_check: is_check,
-                        old_move_stats: move_stats,
-                    },
-                    *next_move,
-                    pruner,
-                    evaluate_for,
-                    current_max,
-                    eval_type
-                );
-                if eval>current_max {
-                    current_max = eval;
-                    if eval >= current_min_one_level_up {
-                        return eval;
-                    }
+                    Score::from_pawns(static_eval(game.get_game_state(), eval_type, evaluate_for))
                 }
             }
+            get_max_of(
+                &game,
+                NodeData { half_step: new_half_step, is_check, move_stats, reduction, is_after_null_move: false },
+                pruner,
+                evaluate_for,
+                window,
+                eval_type,
+                contempt,
+            )
+        }
+    };
+}
+
+/**
+ * Principal variation search: only the first move is searched with the whole window,
+ * the following ones with a null window to prove that they aren't better.
+ * Only if that fails they are searched again with the whole window.
+ */
+fn get_max_of(
+    game: &Game,
+    node: NodeData,
+    pruner: Pruner,
+    evaluate_for: Color,
+    window: Window,
+    eval_type: StaticEvalType,
+    contempt: i32
+) -> Score {
+    if let Some(null_move_eval) = get_null_move_eval(game, &node, pruner, evaluate_for, Window::null_window(window.beta), eval_type, contempt) {
+        // even passing is too good for the minimizing side to allow
+        if null_move_eval.is_numeric() && null_move_eval > window.beta {
+            return null_move_eval;
+        }
+    }
 
-            if is_max_eval_actually_stalemate(current_max, new_half_step, &game) {
-                Evaluation::Draw(DrawReason::StaleMate)
-            } else {
-                current_max
+    let moves = game.get_reachable_moves();
+    let mut current_max = Score::MIN;
+    let mut alpha = window.alpha;
+    for (move_index, next_move) in order_moves(game, moves).iter().enumerate() {
+        let get_eval = |late_move_reduction: usize, child_window: Window| get_min_after(
+            OldGameData {
+                old_game: game,
+                old_half_step: node.half_step,
+                was_check: node.is_check,
+                old_move_stats: node.move_stats,
+                old_reduction: node.reduction,
+                late_move_reduction,
+            },
+            *next_move,
+            pruner,
+            evaluate_for,
+            child_window,
+            eval_type,
+            contempt
+        );
+        let full_window = Window { alpha, beta: window.beta };
+        let eval = if move_index == 0 || full_window.is_null_window() {
+            get_eval(0, full_window)
+        } else {
+            let late_move_reduction = pruner.get_late_move_reduction(move_index);
+            let mut eval = get_eval(late_move_reduction, Window::null_window(alpha));
+            if late_move_reduction > 0 && eval > alpha {
+                eval = get_eval(0, Window::null_window(alpha));
+            }
+            if eval > alpha && !full_window.fails_high(eval) {
+                eval = get_eval(0, full_window);
+            }
+            eval
+        };
+        if eval>current_max {
+            current_max = eval;
+            if window.fails_high(eval) {
+                return eval;
+            }
+            if eval > alpha {
+                alpha = eval;
             }
         }
-    };
+    }
+
+    if is_max_eval_actually_stalemate(current_max, node.half_step, game) {
+        Score::draw_with_contempt(DrawReason::StaleMate, contempt)
+    } else {
+        current_max
+    }
 }
 
 fn get_min_after(
@@ -105,58 +270,169 @@ fn get_min_after(
     a_move: Move,
     pruner: Pruner,
     evaluate_for: Color,
-    current_max_one_level_up: Evaluation,
-    eval_type: StaticEvalType
-) -> Evaluation {
+    window: Window,
+    eval_type: StaticEvalType,
+    contempt: i32
+) -> Score {
     let move_result = old_game_data.old_game.play(a_move);
     let new_half_step = old_game_data.old_half_step + 1;
+    count_node();
 
     debug_assert!(new_half_step%2==1, "get_max's new_half_step is supposed to be odd, but was {}", new_half_step);
 
     return match move_result {
         MoveResult::Stopped(reason, final_game_state) => {
-            get_min_after_stopped_eval(reason, *final_game_state, new_half_step, evaluate_for, eval_type)
+            get_min_after_stopped_eval(reason, *final_game_state, new_half_step, evaluate_for, eval_type, contempt)
         }
         MoveResult::Ongoing(game, move_stats) => {
-            let is_check = game.is_active_king_in_check();
-            if pruner.should_stop_min_max_ing(new_half_step, move_stats, old_game_data.old_move_stats, is_check, old_game_data.was_check) {
-                return if game.is_active_king_checkmate() {
-                    Evaluation::WinIn((new_half_step + 1) as u8)
-                } else {
-                    Evaluation::Numeric(static_eval(game.get_game_state(), eval_type, evaluate_for))
+            if move_stats.did_catch_figure {
+                if let Some(tablebase_eval) = probe_tablebase(&game, new_half_step, evaluate_for, eval_type, contempt) {
+                    return tablebase_eval;
                 }
             }
-            let moves = game.get_reachable_moves();
-            let mut current_min = MAX_EVALUATION;
-            for next_move in scramble(moves).iter() {
-                let eval = get_max_after(
-                    OldGameData {
-                        old_game: &game,
-                        old_half_step: new_half_step,
-                        was_check: is_check,
-                        old_move_stats: move_stats,
-                    },
-                    *next_move,
-                    pruner,
-                    evaluate_for,
-                    current_min,
-                    eval_type
-                );
-                if eval<current_min {
-                    current_min = eval;
-                    if eval <= current_max_one_level_up {
-                        return eval;
-                    }
+            let is_check = game.is_active_king_in_check();
+            let reduction = get_reduction(&old_game_data, move_stats, is_check);
+            let extension_stats = without_losing_capture(old_game_data.old_game, a_move, move_stats);
+            if pruner.should_stop_min_max_ing(new_half_step + reduction, extension_stats, old_game_data.old_move_stats, is_check, old_game_data.was_check) {
+                return if is_check && game.is_active_king_checkmate() {
+                    Score::win_in((new_half_step + 1) as u8)
+                } else {
+                    Score::from_pawns(static_eval(game.get_game_state(), eval_type, evaluate_for))
                 }
             }
+            get_min_of(
+                &game,
+                NodeData { half_step: new_half_step, is_check, move_stats, reduction, is_after_null_move: false },
+                pruner,
+                evaluate_for,
+                window,
+                eval_type,
+                contempt,
+            )
+        }
+    };
+}
+
+/**
+ * the counterpart of get_max_of. The null window can only prove that a move is larger than a bound,
+ * so it's placed at beta and a move that doesn't fail high might be better and is searched again.
+ */
+fn get_min_of(
+    game: &Game,
+    node: NodeData,
+    pruner: Pruner,
+    evaluate_for: Color,
+    window: Window,
+    eval_type: StaticEvalType,
+    contempt: i32
+) -> Score {
+    if let Some(null_move_eval) = get_null_move_eval(game, &node, pruner, evaluate_for, Window::null_window(window.alpha), eval_type, contempt) {
+        // even passing is too good for the maximizing side to allow
+        if null_move_eval.is_numeric() && window.fails_low(null_move_eval) {
+            return null_move_eval;
+        }
+    }
 
-            if is_min_eval_actually_stalemate(current_min, new_half_step, &game) {
-                Evaluation::Draw(DrawReason::StaleMate)
-            } else {
-                current_min
+    let moves = game.get_reachable_moves();
+    let mut current_min = Score::MAX;
+    let mut beta = window.beta;
+    for (move_index, next_move) in order_moves(game, moves).iter().enumerate() {
+        let get_eval = |late_move_reduction: usize, child_window: Window| get_max_after(
+            OldGameData {
+                old_game: game,
+                old_half_step: node.half_step,
+                was_check: node.is_check,
+                old_move_stats: node.move_stats,
+                old_reduction: node.reduction,
+                late_move_reduction,
+            },
+            *next_move,
+            pruner,
+            evaluate_for,
+            child_window,
+            eval_type,
+            contempt
+        );
+        let full_window = Window { alpha: window.alpha, beta };
+        let eval = if move_index == 0 || full_window.is_null_window() {
+            get_eval(0, full_window)
+        } else {
+            let late_move_reduction = pruner.get_late_move_reduction(move_index);
+            let mut eval = get_eval(late_move_reduction, Window::null_window(beta));
+            if late_move_reduction > 0 && eval <= beta {
+                eval = get_eval(0, Window::null_window(beta));
+            }
+            if eval <= beta && !full_window.fails_low(eval) {
+                eval = get_eval(0, full_window);
+            }
+            eval
+        };
+        if eval<current_min {
+            current_min = eval;
+            if window.fails_low(eval) {
+                return eval;
+            }
+            if eval < beta {
+                beta = eval;
             }
         }
+    }
+
+    if is_min_eval_actually_stalemate(current_min, node.half_step, game) {
+        Score::draw_with_contempt(DrawReason::StaleMate, contempt)
+    } else {
+        current_min
+    }
+}
+
+/**
+ * the reduction of the search depth after a move. The late move reduction only applies to quiet moves.
+ */
+fn get_reduction(old_game_data: &OldGameData, move_stats: MoveStats, is_check: bool) -> usize {
+    let is_quiet = !(move_stats.did_catch_figure || move_stats.did_move_pawn || is_check || old_game_data.was_check);
+    if is_quiet {
+        old_game_data.old_reduction + old_game_data.late_move_reduction
+    } else {
+        old_game_data.old_reduction
+    }
+}
+
+/**
+ * Null-move pruning: the evaluation after the active color passes, searched with a reduced depth.
+ * The evaluation is from the view of the original search, the null window is placed at the bound the caller compares with.
+ * Returns None if the null move isn't tried.
+ */
+fn get_null_move_eval(
+    game: &Game,
+    node: &NodeData,
+    pruner: Pruner,
+    evaluate_for: Color,
+    null_window: Window,
+    eval_type: StaticEvalType,
+    contempt: i32,
+) -> Option<Score> {
+    let null_move_reduction = pruner.get_null_move_reduction(node.half_step + node.reduction)?;
+    if node.is_check || node.is_after_null_move || !game.get_game_state().has_active_color_non_pawn_figures() {
+        return None;
+    }
+    let null_move_game = game.play_null_move()?;
+    let null_move_node = NodeData {
+        half_step: node.half_step + 1,
+        is_check: false,
+        move_stats: MoveStats::default(),
+        reduction: node.reduction + null_move_reduction,
+        is_after_null_move: true,
     };
+    let null_move_half_step = null_move_node.half_step + null_move_node.reduction;
+    if pruner.should_stop_min_max_ing(null_move_half_step, MoveStats::default(), node.move_stats, false, false) {
+        return Some(Score::from_pawns(static_eval(null_move_game.get_game_state(), eval_type, evaluate_for)));
+    }
+    let null_move_eval = if node.half_step % 2 == 0 {
+        get_min_of(&null_move_game, null_move_node, pruner, evaluate_for, null_window, eval_type, contempt)
+    } else {
+        get_max_of(&null_move_game, null_move_node, pruner, evaluate_for, null_window, eval_type, contempt)
+    };
+    Some(null_move_eval)
 }
 
 fn get_min_after_stopped_eval(
@@ -165,21 +441,21 @@ fn get_min_after_stopped_eval(
     new_half_step: usize,
     evaluate_for: Color,
     eval_type: StaticEvalType,
-) -> Evaluation {
-    println!("get_min_after_stopped_eval reached with new_half_step {}", new_half_step);
+    contempt: i32,
+) -> Score {
     match reason {
         StoppedReason::KingInCheckAfterMove => {
             get_lose_eval(&final_game_state, new_half_step, evaluate_for, eval_type)
         }
-        StoppedReason::InsufficientMaterial => Evaluation::Draw(DrawReason::InsufficientMaterial),
-        StoppedReason::ThreeTimesRepetition => Evaluation::Draw(DrawReason::ThreeTimesRepetition),
-        StoppedReason::NoChangeIn50Moves => Evaluation::Draw(DrawReason::ThreeTimesRepetition),
+        StoppedReason::InsufficientMaterial => Score::draw_with_contempt(DrawReason::InsufficientMaterial, contempt),
+        StoppedReason::ThreeTimesRepetition => Score::draw_with_contempt(DrawReason::ThreeTimesRepetition, contempt),
+        StoppedReason::NoChangeIn50Moves => Score::draw_with_contempt(DrawReason::ThreeTimesRepetition, contempt),
     }
 }
 
-fn is_min_eval_actually_stalemate(max_eval: Evaluation, half_step: usize, game: &Game) -> bool {
+fn is_min_eval_actually_stalemate(max_eval: Score, half_step: usize, game: &Game) -> bool {
     debug_assert!(half_step%2==1, "is_max_eval_actually_stalemate's half_step is supposed to be odd, but was {}", half_step);
-    if let Evaluation::WinIn(win_in_half_step) = max_eval {
+    if let Evaluation::WinIn(win_in_half_step) = max_eval.to_evaluation() {
         debug_assert!(win_in_half_step%2==0, "is_max_eval_actually_stalemate's win_in_half_step is supposed to be even, but was {}", win_in_half_step);
         if win_in_half_step as usize == (half_step + 1) && !game.is_active_king_in_check() {
             return true;
@@ -188,9 +464,9 @@ fn is_min_eval_actually_stalemate(max_eval: Evaluation, half_step: usize, game:
     false
 }
 
-fn is_max_eval_actually_stalemate(min_eval: Evaluation, half_step: usize, game: &Game) -> bool {
+fn is_max_eval_actually_stalemate(min_eval: Score, half_step: usize, game: &Game) -> bool {
     debug_assert!(half_step%2==0, "is_min_eval_actually_stalemate's half_step is supposed to be even, but was {}", half_step);
-    if let Evaluation::LoseIn(lose_in_half_step, _) = min_eval {
+    if let Evaluation::LoseIn(lose_in_half_step, _) = min_eval.to_evaluation() {
         debug_assert!(lose_in_half_step%2==1, "is_min_eval_actually_stalemate's half_step is supposed to be odd, but was {}", lose_in_half_step);
         if lose_in_half_step as usize == (half_step + 1) && !game.is_active_king_in_check() {
             return true;
@@ -199,9 +475,56 @@ fn is_max_eval_actually_stalemate(min_eval: Evaluation, half_step: usize, game:
     false
 }
 
-fn get_lose_eval(game_state: &GameState, lost_after_nr_of_half_steps: usize, evaluate_for: Color, eval_type: StaticEvalType) -> Evaluation {
+fn get_lose_eval(game_state: &GameState, lost_after_nr_of_half_steps: usize, evaluate_for: Color, eval_type: StaticEvalType) -> Score {
     debug_assert!(lost_after_nr_of_half_steps%2==1, "get_lose_eval's half_step is supposed to be odd, but was {}", lost_after_nr_of_half_steps);
-    Evaluation::LoseIn(lost_after_nr_of_half_steps as u8, static_eval(game_state, eval_type, evaluate_for))
+    Score::lose_in(lost_after_nr_of_half_steps as u8, static_eval(game_state, eval_type, evaluate_for))
+}
+
+/**
+ * since a position can only enter the tablebase by a capture, it's only probed after captures
+ * (the root position is probed before the search starts)
+ */
+#[cfg(feature = "syzygy")]
+fn probe_tablebase(game: &Game, half_step: usize, evaluate_for: Color, eval_type: StaticEvalType, contempt: i32) -> Option<Score> {
+    crate::engine::tablebase::probe_position(game, half_step, evaluate_for, eval_type).map(|evaluation| match evaluation {
+        Evaluation::Draw(reason) => Score::draw_with_contempt(reason, contempt),
+        _ => Score::from(evaluation),
+    })
+}
+
+#[cfg(not(feature = "syzygy"))]
+fn probe_tablebase(_game: &Game, _half_step: usize, _evaluate_for: Color, _eval_type: StaticEvalType, _contempt: i32) -> Option<Score> {
+    None
+}
+
+/**
+ * Quiescence pruning: a capture that loses material (according to the static exchange evaluation)
+ * isn't searched deeper than a quiet move, since the capture extension rarely pays off for it.
+ */
+fn without_losing_capture(old_game: &Game, a_move: Move, move_stats: MoveStats) -> MoveStats {
+    if move_stats.did_catch_figure && old_game.get_game_state().static_exchange_eval_of(a_move) < 0 {
+        MoveStats { did_catch_figure: false, ..move_stats }
+    } else {
+        move_stats
+    }
+}
+
+/**
+ * improves the performance of alpha-beta pruning: captures that win material (according to the static exchange evaluation)
+ * are tried first and captures that lose material last. The other moves keep their scrambled order.
+ */
+fn order_moves(game: &Game, moves: &Moves) -> Moves {
+    let game_state = game.get_game_state();
+    let mut ordered_moves = scramble(moves);
+    ordered_moves.sort_by_cached_key(|a_move| {
+        let is_capture = a_move.move_type() == MoveType::EnPassant || game_state.board.get_figure(a_move.to()).is_some();
+        if !is_capture {
+            return (1, 0);
+        }
+        let balance = game_state.static_exchange_eval_of(*a_move);
+        if balance >= 0 { (0, -balance) } else { (2, -balance) }
+    });
+    ordered_moves
 }
 
 // improves the performance of alpha-beta pruning
@@ -224,7 +547,7 @@ mod tests {
     use rstest::*;
     use crate::engine::evaluations::*;
     use crate::engine::evaluations::testing::{EvaluationMatcher};
-    use crate::engine::min_max::pruner::PRUNER_L2;
+    use crate::engine::min_max::pruner::{PRUNER_L2, PRUNER_L3};
 
     //♔♕♗♘♖♙♚♛♝♞♜♟
 
@@ -248,13 +571,132 @@ mod tests {
             next_move,
             PRUNER_L2,
             Color::White,
-            MIN_EVALUATION,
+            Score::MIN,
+            StaticEvalType::Default,
+        );
+        assert!(
+            expected_matcher.matches(&actual_evaluation.to_evaluation()),
+            "actual_eval: {:?}",
+            actual_evaluation,
+        );
+    }
+
+    #[rstest(
+    game_config_testing_white, next_move_str, expected_matcher,
+    case("white ♔b6 ♙a7 ♚a8", "b6-a6", EvaluationMatcher::Draw(DrawReason::StaleMate)),
+    case("white ♔d6 ♖a7 ♚d8", "a7-a8", EvaluationMatcher::WinIn),
+    case("white ♔f8 ♜a7 ♚e6", "f8-e8", EvaluationMatcher::LoseIn),
+    case("white ♔g3 ♖d2 ♚g1 ♙c2 ♙d3", "d2-d1", EvaluationMatcher::WinIn),
+    case("white ♔e1 ♕d1 ♖h1 ♚e8 ♜a8 ♞b8", "d1-d7", EvaluationMatcher::NegativeNumeric), // the queen is lost
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_evaluate_move_with_reductions(
+        game_config_testing_white: &str,
+        next_move_str: &str,
+        expected_matcher: EvaluationMatcher,
+    ) {
+        let game = game_config_testing_white.parse::<Game>().unwrap();
+        let next_move = next_move_str.parse::<Move>().unwrap();
+        let pruner = PRUNER_L3.with_null_move_pruning(2).with_late_move_reductions(3, 1);
+        let actual_evaluation = evaluate_move(
+            &game,
+            next_move,
+            pruner,
+            Color::White,
+            Score::MIN,
             StaticEvalType::Default,
         );
         assert!(
-            expected_matcher.matches(&actual_evaluation),
+            expected_matcher.matches(&actual_evaluation.to_evaluation()),
             "actual_eval: {:?}",
             actual_evaluation,
         );
     }
-}
\ No newline at end of file
+
+    #[rstest(
+    window, eval, expected_fails_high, expected_fails_low,
+    case(Window::FULL, Score::from_pawns(0.5), false, false),
+    case(Window::FULL, Score::MAX, true, false),
+    case(Window { alpha: Score::from_pawns(-0.5), beta: Score::from_pawns(0.5) }, Score::from_pawns(0.5), true, false),
+    case(Window { alpha: Score::from_pawns(-0.5), beta: Score::from_pawns(0.5) }, Score::draw(DrawReason::StaleMate), false, false),
+    case(Window { alpha: Score::from_pawns(-0.5), beta: Score::from_pawns(0.5) }, Score::lose_in(3, 2.0), false, true),
+    case(Window::null_window(Score::from_pawns(0.5)), Score::from_pawns(0.5), false, true),
+    case(Window::null_window(Score::from_pawns(0.5)), Score::from_pawns(0.6), true, false),
+    case(Window::null_window(Score::from_pawns(0.5)), Score::win_in(6), true, false),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_window(window: Window, eval: Score, expected_fails_high: bool, expected_fails_low: bool) {
+        assert_eq!(window.fails_high(eval), expected_fails_high);
+        assert_eq!(window.fails_low(eval), expected_fails_low);
+    }
+
+    #[rstest(
+    game_config,
+    case("white ♔e1 ♕d1 ♙a2 ♚e8 ♜a8 ♝c8"),
+    case("white ♔f3 ♖d2 ♚h1 ♙c2 ♙d3"),
+    case("e2-e4 d7-d5"),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_null_window_tells_if_a_move_is_better(game_config: &str) {
+        let game = game_config.parse::<Game>().unwrap();
+        let turn_by = game.get_game_state().turn_by;
+        for a_move in game.get_reachable_moves().iter() {
+            let exact_eval = evaluate_move_in_window(&game, *a_move, PRUNER_L2, turn_by, Window::FULL, StaticEvalType::Default, 0);
+            for bound in [exact_eval, Score::from_pawns(-1.0), Score::from_pawns(0.0), Score::from_pawns(1.0), Score::win_in(4)] {
+                let null_window_eval = evaluate_move_in_window(&game, *a_move, PRUNER_L2, turn_by, Window::null_window(bound), StaticEvalType::Default, 0);
+                assert_eq!(null_window_eval > bound, exact_eval > bound, "move: {}, bound: {:?}, exact: {:?}, null window: {:?}", a_move, bound, exact_eval, null_window_eval);
+            }
+        }
+    }
+
+    #[rstest(
+    contempt, expected_lower_centipawns,
+    case(0, -1),
+    case(50, -51),
+    case(-50, 49),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_contempt_moves_draws(contempt: i32, expected_lower_centipawns: i32) {
+        let game = "white ♔b6 ♙a7 ♚a8".parse::<Game>().unwrap();
+        let stalemate_score = evaluate_move_in_window(&game, Move::from_code("b6-a6"), PRUNER_L2, Color::White, Window::FULL, StaticEvalType::Default, contempt);
+        assert_eq!(stalemate_score.to_evaluation(), Evaluation::Draw(DrawReason::StaleMate));
+        assert!(stalemate_score > Score::from_centipawns(expected_lower_centipawns));
+        assert!(stalemate_score < Score::from_centipawns(expected_lower_centipawns + 1));
+    }
+
+    #[test]
+    fn test_order_moves_by_static_exchange_eval() {
+        let game = "white ♔a1 ♖e1 ♕d1 ♟e5 ♟d6 ♞d5 ♚h8".parse::<Game>().unwrap();
+        let ordered_moves = order_moves(&game, game.get_reachable_moves());
+        assert_eq!(ordered_moves.len(), game.get_reachable_moves().len());
+        assert_eq!(ordered_moves.first(), Some(&Move::from_code("d1-d5")), "the queen wins the knight");
+        assert_eq!(ordered_moves.last(), Some(&Move::from_code("e1-e5")), "the rook loses itself for a pawn");
+    }
+
+    #[rstest(
+    capture_str, expected_did_catch_figure,
+    case("d1-d5", true),
+    case("e1-e5", false),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_losing_captures_arent_extended(capture_str: &str, expected_did_catch_figure: bool) {
+        let game = "white ♔a1 ♖e1 ♕d1 ♟e5 ♟d6 ♞d5 ♚h8".parse::<Game>().unwrap();
+        let move_stats = MoveStats { did_catch_figure: true, did_move_pawn: false };
+        let extension_stats = without_losing_capture(&game, Move::from_code(capture_str), move_stats);
+        assert_eq!(extension_stats.did_catch_figure, expected_did_catch_figure);
+    }
+
+    #[test]
+    fn test_nodes_searched_increase_with_the_depth() {
+        let game = "e2-e4 e7-e5".parse::<Game>().unwrap();
+        let a_move = Move::from_code("g1-f3");
+        let count_nodes = |pruner: Pruner| {
+            let nodes_before = get_nodes_searched();
+            evaluate_move_in_window(&game, a_move, pruner, Color::White, Window::FULL, StaticEvalType::Default, 0);
+            get_nodes_searched() - nodes_before
+        };
+        let shallow_nodes = count_nodes(PRUNER_L2);
+        assert!(shallow_nodes > 1, "{}", shallow_nodes);
+        assert!(count_nodes(PRUNER_L3) > shallow_nodes);
+    }
+}
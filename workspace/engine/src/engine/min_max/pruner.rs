@@ -1,20 +1,33 @@
 use crate::game::MoveStats;
 
-pub static PRUNER_L0: Pruner = Pruner { half_step_depth: 0, half_step_depth_after_pawn_moved: 0, half_step_depth_after_check: 0, half_step_depth_after_figure_caught: 0 };
-pub static PRUNER_L1: Pruner = Pruner { half_step_depth: 2, half_step_depth_after_pawn_moved: 2, half_step_depth_after_check: 2, half_step_depth_after_figure_caught: 2 };
-pub static PRUNER_L2: Pruner = Pruner { half_step_depth: 4, half_step_depth_after_pawn_moved: 4, half_step_depth_after_check: 4, half_step_depth_after_figure_caught: 4 };
-pub static PRUNER_L3: Pruner = Pruner { half_step_depth: 6, half_step_depth_after_pawn_moved: 6, half_step_depth_after_check: 6, half_step_depth_after_figure_caught: 6 };
-pub static PRUNER_1_1_3_3: Pruner = Pruner { half_step_depth: 2, half_step_depth_after_pawn_moved: 3, half_step_depth_after_check: 6, half_step_depth_after_figure_caught: 6 };
-pub static PRUNER_1_2_3_4: Pruner = Pruner { half_step_depth: 2, half_step_depth_after_pawn_moved: 4, half_step_depth_after_check: 6, half_step_depth_after_figure_caught: 8 };
-pub static PRUNER_2_4_5_8: Pruner = Pruner { half_step_depth: 4, half_step_depth_after_pawn_moved: 8, half_step_depth_after_check: 10, half_step_depth_after_figure_caught: 16 };
-pub static PRUNER_3_4_4_6: Pruner = Pruner { half_step_depth: 6, half_step_depth_after_pawn_moved: 8, half_step_depth_after_check: 8, half_step_depth_after_figure_caught: 12 };
-
-#[derive(Debug, Copy, Clone)]
+pub static PRUNER_L0: Pruner = Pruner { half_step_depth: 0, half_step_depth_after_pawn_moved: 0, half_step_depth_after_check: 0, half_step_depth_after_figure_caught: 0, opt_null_move_reduction: None, opt_late_move_reduction: None };
+pub static PRUNER_L1: Pruner = Pruner { half_step_depth: 2, half_step_depth_after_pawn_moved: 2, half_step_depth_after_check: 2, half_step_depth_after_figure_caught: 2, opt_null_move_reduction: None, opt_late_move_reduction: None };
+pub static PRUNER_L2: Pruner = Pruner { half_step_depth: 4, half_step_depth_after_pawn_moved: 4, half_step_depth_after_check: 4, half_step_depth_after_figure_caught: 4, opt_null_move_reduction: None, opt_late_move_reduction: None };
+pub static PRUNER_L3: Pruner = Pruner { half_step_depth: 6, half_step_depth_after_pawn_moved: 6, half_step_depth_after_check: 6, half_step_depth_after_figure_caught: 6, opt_null_move_reduction: None, opt_late_move_reduction: None };
+pub static PRUNER_1_1_3_3: Pruner = Pruner { half_step_depth: 2, half_step_depth_after_pawn_moved: 3, half_step_depth_after_check: 6, half_step_depth_after_figure_caught: 6, opt_null_move_reduction: None, opt_late_move_reduction: None };
+pub static PRUNER_1_2_3_4: Pruner = Pruner { half_step_depth: 2, half_step_depth_after_pawn_moved: 4, half_step_depth_after_check: 6, half_step_depth_after_figure_caught: 8, opt_null_move_reduction: None, opt_late_move_reduction: None };
+pub static PRUNER_2_2_3_4: Pruner = Pruner { half_step_depth: 4, half_step_depth_after_pawn_moved: 4, half_step_depth_after_check: 6, half_step_depth_after_figure_caught: 8, opt_null_move_reduction: None, opt_late_move_reduction: None };
+pub static PRUNER_2_4_5_8: Pruner = Pruner { half_step_depth: 4, half_step_depth_after_pawn_moved: 8, half_step_depth_after_check: 10, half_step_depth_after_figure_caught: 16, opt_null_move_reduction: None, opt_late_move_reduction: None };
+pub static PRUNER_3_4_4_6: Pruner = Pruner { half_step_depth: 6, half_step_depth_after_pawn_moved: 8, half_step_depth_after_check: 8, half_step_depth_after_figure_caught: 12, opt_null_move_reduction: None, opt_late_move_reduction: None };
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub struct Pruner {
     half_step_depth: usize,
     half_step_depth_after_pawn_moved: usize,
     half_step_depth_after_check: usize,
     half_step_depth_after_figure_caught: usize,
+    opt_null_move_reduction: Option<usize>,
+    opt_late_move_reduction: Option<LateMoveReduction>,
+}
+
+/**
+ * quiet moves that are tried after the first after_nr_of_moves moves of a position
+ * are searched half_step_reduction half steps less deep (and again with full depth, if they turn out to be better).
+ */
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct LateMoveReduction {
+    pub after_nr_of_moves: usize,
+    pub half_step_reduction: usize,
 }
 
 impl Pruner {
@@ -41,8 +54,81 @@ impl Pruner {
             half_step_depth_after_pawn_moved: step_depth_after_pawn_moved * 2,
             half_step_depth_after_check: step_depth_after_chess_moved * 2,
             half_step_depth_after_figure_caught: step_depth_after_figure_caught * 2,
+            opt_null_move_reduction: None,
+            opt_late_move_reduction: None,
+        }
+    }
+
+    /**
+     * lets the active color pass before searching its moves. If the position is still too good for the opponent
+     * to allow (searched half_step_reduction half steps less deep), the other moves don't need to be searched.
+     * It's never tried while in check or if the active color only has pawns left (because of zugzwang).
+     */
+    pub const fn with_null_move_pruning(self, half_step_reduction: usize) -> Pruner {
+        Pruner {
+            opt_null_move_reduction: Some(half_step_reduction),
+            ..self
+        }
+    }
+
+    pub const fn with_late_move_reductions(self, after_nr_of_moves: usize, half_step_reduction: usize) -> Pruner {
+        Pruner {
+            opt_late_move_reduction: Some(LateMoveReduction { after_nr_of_moves, half_step_reduction }),
+            ..self
+        }
+    }
+
+    pub const fn without_null_move_pruning(self) -> Pruner {
+        Pruner {
+            opt_null_move_reduction: None,
+            ..self
+        }
+    }
+
+    pub const fn without_late_move_reductions(self) -> Pruner {
+        Pruner {
+            opt_late_move_reduction: None,
+            ..self
+        }
+    }
+
+    /**
+     * the reduction of the null move search, if null move pruning is activated and the regular depth isn't reached yet
+     */
+    pub fn get_null_move_reduction(&self, current_half_step: usize) -> Option<usize> {
+        let reduction = self.opt_null_move_reduction?;
+        (current_half_step + reduction < self.half_step_depth).then_some(reduction)
+    }
+
+    /**
+     * the reduction for the move with the given index in the list of moves of a position (0 if none)
+     */
+    pub fn get_late_move_reduction(&self, move_index: usize) -> usize {
+        match self.opt_late_move_reduction {
+            Some(late_move_reduction) if move_index >= late_move_reduction.after_nr_of_moves => late_move_reduction.half_step_reduction,
+            _ => 0,
         }
     }
+
+    /**
+     * the shallower pruners an iterative deepening search goes through before reaching this one (which is the last step).
+     * Every step searches 2 half steps deeper than the one before, the extensions after pawn moves, checks
+     * and captures stay the same (relative to the regular depth). Since the depth after a check may be smaller
+     * than the regular depth, it's reduced to 0 at the least.
+     */
+    pub fn get_deepening_steps(&self) -> Vec<Pruner> {
+        (0..=self.half_step_depth / 2).map(|step| {
+            let reduction = self.half_step_depth - step * 2;
+            Pruner {
+                half_step_depth: self.half_step_depth - reduction,
+                half_step_depth_after_pawn_moved: self.half_step_depth_after_pawn_moved - reduction,
+                half_step_depth_after_check: self.half_step_depth_after_check.saturating_sub(reduction),
+                half_step_depth_after_figure_caught: self.half_step_depth_after_figure_caught - reduction,
+                ..*self
+            }
+        }).collect()
+    }
+
     pub fn should_stop_min_max_ing(
         &self,
         current_half_step: usize,
@@ -58,4 +144,65 @@ impl Pruner {
             (new_stats.did_catch_figure && current_half_step < self.half_step_depth_after_figure_caught);
         !should_continue
     }
-}
\ No newline at end of file
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[test]
+    fn test_reductions_are_off_by_default() {
+        for pruner in [PRUNER_L0, PRUNER_L3, PRUNER_2_4_5_8, Pruner::new(2, 3, 4, 5)] {
+            assert_eq!(pruner.get_null_move_reduction(0), None);
+            assert_eq!(pruner.get_late_move_reduction(100), 0);
+        }
+    }
+
+    #[rstest(
+    current_half_step, expected_reduction,
+    case(0, Some(2)),
+    case(1, Some(2)),
+    case(2, None),
+    case(5, None),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_get_null_move_reduction(current_half_step: usize, expected_reduction: Option<usize>) {
+        let pruner = PRUNER_L2.with_null_move_pruning(2);
+        assert_eq!(pruner.get_null_move_reduction(current_half_step), expected_reduction);
+        assert_eq!(pruner.without_null_move_pruning().get_null_move_reduction(current_half_step), None);
+    }
+
+    #[rstest(
+    move_index, expected_reduction,
+    case(0, 0),
+    case(3, 0),
+    case(4, 1),
+    case(30, 1),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_get_late_move_reduction(move_index: usize, expected_reduction: usize) {
+        let pruner = PRUNER_L3.with_late_move_reductions(4, 1);
+        assert_eq!(pruner.get_late_move_reduction(move_index), expected_reduction);
+        assert_eq!(pruner.without_late_move_reductions().get_late_move_reduction(move_index), 0);
+    }
+
+    #[test]
+    fn test_get_deepening_steps() {
+        assert_eq!(PRUNER_L0.get_deepening_steps(), vec![PRUNER_L0]);
+        assert_eq!(PRUNER_L3.get_deepening_steps(), vec![PRUNER_L0, PRUNER_L1, PRUNER_L2, PRUNER_L3]);
+        let pruner = PRUNER_2_2_3_4.with_null_move_pruning(2);
+        assert_eq!(pruner.get_deepening_steps(), vec![
+            Pruner::new(0, 0, 1, 2).with_null_move_pruning(2),
+            Pruner::new(1, 1, 2, 3).with_null_move_pruning(2),
+            pruner,
+        ]);
+        assert_eq!(Pruner::new(2, 2, 0, 2).get_deepening_steps(), vec![
+            Pruner::new(0, 0, 0, 0),
+            Pruner::new(1, 1, 0, 1),
+            Pruner::new(2, 2, 0, 2),
+        ]);
+    }
+}
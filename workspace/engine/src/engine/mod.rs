@@ -1,47 +1,113 @@
 use rand::prelude::*;
+use rand::rngs::StdRng;
 use rand::thread_rng;
 use crate::game::*;
 use crate::engine::evaluations::*;
-use crate::engine::min_max::{evaluate_move};
-use crate::engine::static_eval::StaticEvalType;
-use crate::base::{ErrorKind, Move};
+use crate::engine::evaluations::score::Score;
+use crate::engine::min_max::{evaluate_move, evaluate_move_in_window, Window};
+use crate::engine::static_eval::{EvalParams, StaticEvalType};
+use crate::base::{ChessError, ErrorKind, Move};
 use crate::engine::evaluations::frontend::*;
 use crate::engine::min_max::pruner::*;
+use crate::engine::search_config::{MoveSelection, SearchConfig};
+
+const ASPIRATION_WINDOW_HALF_WIDTH_IN_CENTIPAWNS: i32 = 50;
+// a random move selection picks among the moves that are less than this much worse than the best one
+const ALMOST_AS_GOOD_IN_CENTIPAWNS: i32 = 20;
 
 pub mod evaluations;
 pub mod min_max;
-mod static_eval;
+pub mod static_eval;
+pub mod opening_book;
+pub mod endgame;
+pub mod mate_search;
+pub mod search_config;
+pub mod skill;
+pub mod root_split;
+pub mod limits;
+pub mod epd_suite;
+#[cfg(feature = "syzygy")]
+pub mod tablebase;
+#[cfg(feature = "parallel")]
+pub mod parallel;
 
 pub fn evaluate_single_move(game_config: &str, next_move: Move, pruner: Pruner) -> GameEvaluation {
+    evaluate_single_move_with_params(game_config, next_move, pruner, None)
+}
+
+/**
+ * like evaluate_single_move, but with the possibility to replace the default evaluation weights
+ */
+pub fn evaluate_single_move_with_params(game_config: &str, next_move: Move, pruner: Pruner, opt_eval_params: Option<EvalParams>) -> GameEvaluation {
     let game_or_final_eval = init_game(game_config);
     let game = match game_or_final_eval {
         OngoingGameOrEvaluation::Ongoing(game) => {game}
         OngoingGameOrEvaluation::Ended(final_eval) => {return final_eval;}
     };
+    if !game.get_reachable_moves().contains(&next_move) {
+        return GameEvaluation::Err(ChessError {
+            msg: format!("move {} isn't possible in {}", next_move, game.get_fen()),
+            kind: ErrorKind::IllegalMove,
+        });
+    }
 
-    let eval_type = get_eval_type_for(&game);
+    let eval_type = get_eval_type_for(&game, opt_eval_params);
+
+    #[cfg(feature = "syzygy")]
+    if let Some(evaluation) = tablebase::probe_move(&game, next_move, eval_type) {
+        return GameEvaluation::MoveToPlay(next_move, MoveEvaluation::from(&evaluation));
+    }
 
     let evaluation = evaluate_move(
         &game,
         next_move,
         pruner,
         game.get_game_state().turn_by,
-        MIN_EVALUATION,
+        Score::MIN,
         eval_type,
     );
 
-    GameEvaluation::MoveToPlay(next_move, MoveEvaluation::from(&evaluation))
+    GameEvaluation::MoveToPlay(next_move, MoveEvaluation::from_score(evaluation))
 }
 
 pub fn evaluate(game_config: &str, pruner: Pruner) -> GameEvaluation {
+    evaluate_with_params(game_config, pruner, None)
+}
+
+/**
+ * like evaluate, but with the possibility to replace the default evaluation weights
+ */
+pub fn evaluate_with_params(game_config: &str, pruner: Pruner, opt_eval_params: Option<EvalParams>) -> GameEvaluation {
+    evaluate_with_config(game_config, &SearchConfig {
+        pruner,
+        opt_eval_params,
+        move_selection: MoveSelection::Random,
+        contempt_in_centipawns: 0,
+    })
+}
+
+/**
+ * like evaluate, but the pruner, the evaluation weights and how the move is picked are given by the config
+ */
+pub fn evaluate_with_config(game_config: &str, config: &SearchConfig) -> GameEvaluation {
     let game_or_final_eval = init_game(game_config);
     let game = match game_or_final_eval {
         OngoingGameOrEvaluation::Ongoing(game) => {game}
         OngoingGameOrEvaluation::Ended(final_eval) => {return final_eval;}
     };
 
-    let best_move: EvaluatedMove = evaluate_game(&game, pruner);
+    evaluate_game_with_config(&game, config)
+}
+
+/**
+ * like evaluate_with_config, but for an already parsed game (e.g. one kept alive between the moves)
+ */
+pub fn evaluate_game_with_config(game: &Game, config: &SearchConfig) -> GameEvaluation {
+    let best_move: EvaluatedMove = evaluate_game(game, config);
+    to_game_evaluation(game, best_move)
+}
 
+fn to_game_evaluation(game: &Game, best_move: EvaluatedMove) -> GameEvaluation {
     if let MoveEvaluation::EngineGetsCheckMatedIn(0, _) = best_move.evaluation {
         return if game.is_active_king_in_check() {
             GameEvaluation::GameEnded(GameEndResult::EngineLost)
@@ -53,41 +119,215 @@ pub fn evaluate(game_config: &str, pruner: Pruner) -> GameEvaluation {
     GameEvaluation::MoveToPlay(best_move.a_move, best_move.evaluation)
 }
 
-fn evaluate_game(game: &Game, pruner: Pruner) -> EvaluatedMove {
+fn evaluate_game(game: &Game, config: &SearchConfig) -> EvaluatedMove {
 
-    let eval_type = get_eval_type_for(game);
-    let mut evaluated_moves: Vec<EvaluatedMove> = vec![];
-    let mut current_max_eval: Evaluation = MIN_EVALUATION;
-    for next_move in game.get_reachable_moves().iter() {
-        let evaluation = evaluate_move(
-            game,
-            *next_move,
-            pruner,
-            game.get_game_state().turn_by,
-            current_max_eval,
-            eval_type,
-        );
+    let eval_type = get_eval_type_for(game, config.opt_eval_params);
+
+    #[cfg(feature = "syzygy")]
+    if let Some((tablebase_move, evaluation)) = tablebase::get_tablebase_move(game, eval_type) {
+        return EvaluatedMove { a_move: tablebase_move, evaluation: MoveEvaluation::from(&evaluation) };
+    }
+    let evaluated_moves = evaluate_root_moves(game, config.pruner, eval_type, config.contempt_in_centipawns, config.move_selection);
+    choose_next_move_with(evaluated_moves, config.move_selection, config.contempt_in_centipawns)
+}
+
+fn evaluate_root_moves(game: &Game, pruner: Pruner, eval_type: StaticEvalType, contempt: i32, move_selection: MoveSelection) -> Vec<EvaluatedMove> {
+    to_evaluated_moves(deepen_root_moves(game, pruner.get_deepening_steps(), eval_type, contempt, move_selection, || false))
+}
+
+/**
+ * Iterative deepening: searches the root moves with every pruner of deepening_steps (e.g. pruner.get_deepening_steps()),
+ * each step within an aspiration window around the best score of the step before (see search_root_moves_around)
+ * and with the moves ordered by the evaluations of the step before.
+ * should_stop is asked after every step and ends the deepening early, so the evaluations of the last finished step are returned.
+ * Unless the move_selection is Deterministic, the moves that might be almost as good as the best one get exact scores
+ * (see with_exact_scores_of_almost_as_good_moves).
+ */
+fn deepen_root_moves(
+    game: &Game,
+    deepening_steps: Vec<Pruner>,
+    eval_type: StaticEvalType,
+    contempt: i32,
+    move_selection: MoveSelection,
+    should_stop: impl FnMut() -> bool,
+) -> Vec<(Move, Score)> {
+    deepen_root_moves_with(game, deepening_steps, move_selection, should_stop, |root_moves, pruner, window| {
+        search_root_moves(game, root_moves, pruner, eval_type, contempt, window)
+    })
+}
+
+/**
+ * like deepen_root_moves, but every step is searched by search_root_moves_in (e.g. on several threads)
+ * which gets the root moves best first, the pruner of the step and the window like search_root_moves does
+ */
+fn deepen_root_moves_with(
+    game: &Game,
+    deepening_steps: Vec<Pruner>,
+    move_selection: MoveSelection,
+    mut should_stop: impl FnMut() -> bool,
+    mut search_root_moves_in: impl FnMut(&[Move], Pruner, Window) -> Vec<(Move, Score)>,
+) -> Vec<(Move, Score)> {
+    let mut evaluations: Vec<(Move, Score)> = vec![];
+    let mut opt_last_pruner: Option<Pruner> = None;
+    for step_pruner in deepening_steps {
+        let opt_previous_score = (!evaluations.is_empty()).then(|| get_best_score(&evaluations));
+        let root_moves = get_root_moves_best_first(game, &evaluations);
+        evaluations = search_root_moves_around(opt_previous_score, |window| search_root_moves_in(&root_moves, step_pruner, window));
+        opt_last_pruner = Some(step_pruner);
+        if should_stop() {
+            break;
+        }
+    }
+    match (move_selection, opt_last_pruner) {
+        (MoveSelection::Deterministic, _) | (_, None) => evaluations,
+        (_, Some(last_pruner)) => with_exact_scores_of_almost_as_good_moves(evaluations, |a_move, window| {
+            get_best_score(&search_root_moves_in(&[a_move], last_pruner, window))
+        }),
+    }
+}
 
-        if evaluation > current_max_eval {
-            current_max_eval = evaluation;
+/**
+ * Principal variation search only gives the best move an exact score, the other moves get upper bounds
+ * which can be (much) higher than their real score. Since a random move selection picks among the moves
+ * that are almost as good as the best one (see choose_next_move_with_rng), every move whose bound is within
+ * ALMOST_AS_GOOD_IN_CENTIPAWNS of the best score is searched again, with a window starting there.
+ * A move that stays below gets an upper bound again, but one that keeps it out of the choice.
+ * If several moves share the best score, it isn't known which of them is exact, so all of them are searched again.
+ */
+fn with_exact_scores_of_almost_as_good_moves(
+    mut evaluations: Vec<(Move, Score)>,
+    mut search_move_in: impl FnMut(Move, Window) -> Score,
+) -> Vec<(Move, Score)> {
+    let best_score = get_best_score(&evaluations);
+    let Some(almost_as_good_score) = best_score.lowered_by(ALMOST_AS_GOOD_IN_CENTIPAWNS) else {
+        return evaluations;
+    };
+    let is_best_score_unique = evaluations.iter().filter(|(_, score)| *score == best_score).count() == 1;
+    for (a_move, score) in evaluations.iter_mut() {
+        let is_exact = is_best_score_unique && *score == best_score;
+        if !is_exact && *score > almost_as_good_score {
+            *score = search_move_in(*a_move, Window { alpha: almost_as_good_score, beta: Score::MAX });
+        }
+    }
+    evaluations
+}
+
+/**
+ * Evaluates every move of root_moves (the reachable moves of the current position in the order they should be searched) with principal variation search (see min_max::get_max_of):
+ * a move after the first one that isn't better than the best so far only gets an upper bound as evaluation
+ * (see with_exact_scores_of_almost_as_good_moves for when that isn't good enough).
+ * If a move fails high (only possible if window.beta isn't Score::MAX) the remaining moves aren't evaluated.
+ */
+fn search_root_moves(game: &Game, root_moves: &[Move], pruner: Pruner, eval_type: StaticEvalType, contempt: i32, window: Window) -> Vec<(Move, Score)> {
+    let evaluate_for = game.get_game_state().turn_by;
+    let mut evaluations: Vec<(Move, Score)> = vec![];
+    let mut alpha = window.alpha;
+    for (move_index, next_move) in root_moves.iter().enumerate() {
+        let full_window = Window { alpha, beta: window.beta };
+        let evaluate_in = |window: Window| evaluate_move_in_window(game, *next_move, pruner, evaluate_for, window, eval_type, contempt);
+        let mut evaluation = if move_index == 0 {
+            evaluate_in(full_window)
+        } else {
+            evaluate_in(Window::null_window(alpha))
+        };
+        if move_index > 0 && evaluation > alpha && !full_window.fails_high(evaluation) {
+            evaluation = evaluate_in(full_window);
+        }
+
+        evaluations.push((*next_move, evaluation));
+        if window.fails_high(evaluation) {
+            break;
+        }
+        if evaluation > alpha {
+            alpha = evaluation;
+        }
+    }
+    evaluations
+}
+
+/**
+ * Aspiration windows: searches the root moves with search_root_moves_in (e.g. search_root_moves),
+ * but if there is the numeric score of a previous (shallower) search, the window is narrowed around it. If the best evaluation falls outside, the search is repeated
+ * with the window opened completely on that side. Mate and draw scores don't get a narrowed window,
+ * since there are no scores "close" to them.
+ */
+fn search_root_moves_around(
+    opt_previous_score: Option<Score>,
+    mut search_root_moves_in: impl FnMut(Window) -> Vec<(Move, Score)>,
+) -> Vec<(Move, Score)> {
+    let mut window = match opt_previous_score.and_then(|previous_score| previous_score.get_centipawns()) {
+        Some(previous_centipawns) => Window {
+            alpha: Score::from_centipawns(previous_centipawns - ASPIRATION_WINDOW_HALF_WIDTH_IN_CENTIPAWNS),
+            beta: Score::from_centipawns(previous_centipawns + ASPIRATION_WINDOW_HALF_WIDTH_IN_CENTIPAWNS),
+        },
+        None => Window::FULL,
+    };
+    loop {
+        let evaluations = search_root_moves_in(window);
+        let best_score = get_best_score(&evaluations);
+        if window.fails_high(best_score) && window.beta != Score::MAX {
+            window.beta = Score::MAX;
+        } else if window.fails_low(best_score) && window.alpha != Score::MIN {
+            window.alpha = Score::MIN;
+        } else {
+            return evaluations;
         }
+    }
+}
 
-        evaluated_moves.push(EvaluatedMove { a_move: *next_move, evaluation: MoveEvaluation::from(&evaluation) });
+/**
+ * the reachable moves ordered by the evaluations of a previous (shallower) search, best first,
+ * so that principal variation search at the root starts with the most promising move.
+ * Without previous evaluations the moves keep the order of game.get_reachable_moves().
+ */
+fn get_root_moves_best_first(game: &Game, previous_evaluations: &[(Move, Score)]) -> Vec<Move> {
+    if previous_evaluations.is_empty() {
+        return game.get_reachable_moves().to_vec();
     }
+    let mut evaluations = previous_evaluations.to_vec();
+    evaluations.sort_by(|(_, score1), (_, score2)| score2.cmp(score1));
+    evaluations.into_iter().map(|(a_move, _)| a_move).collect()
+}
 
-    choose_next_move(evaluated_moves)
+fn get_best_score(evaluations: &[(Move, Score)]) -> Score {
+    evaluations.iter().map(|(_, score)| *score).max().unwrap_or(Score::MIN)
 }
 
-pub fn choose_next_move(mut evaluated_moves: Vec<EvaluatedMove>) -> EvaluatedMove {
-    fn get_numeric_eval(evaluation: &MoveEvaluation) -> Option<f32> {
-        match evaluation {
-            MoveEvaluation::Numeric(numeric_eval) => Some(*numeric_eval),
-            MoveEvaluation::Draw(_) => Some(0.0),
-            _ => None,
+fn to_evaluated_moves(evaluations: Vec<(Move, Score)>) -> Vec<EvaluatedMove> {
+    evaluations.into_iter()
+        .map(|(a_move, score)| EvaluatedMove { a_move, evaluation: MoveEvaluation::from_score(score) })
+        .collect()
+}
+
+pub fn choose_next_move(evaluated_moves: Vec<EvaluatedMove>) -> EvaluatedMove {
+    choose_next_move_with_rng(evaluated_moves, 0, &mut thread_rng())
+}
+
+/**
+ * a draw is ranked like a numeric evaluation of -contempt centipawns (see SearchConfig::with_contempt)
+ */
+pub fn choose_next_move_with(evaluated_moves: Vec<EvaluatedMove>, move_selection: MoveSelection, contempt: i32) -> EvaluatedMove {
+    match move_selection {
+        MoveSelection::Random => choose_next_move_with_rng(evaluated_moves, contempt, &mut thread_rng()),
+        MoveSelection::Seeded(seed) => choose_next_move_with_rng(evaluated_moves, contempt, &mut StdRng::seed_from_u64(seed)),
+        MoveSelection::Deterministic => {
+            // the first of the equally good moves is picked, because the sorting is stable
+            *sort_best_first(evaluated_moves, contempt).first().expect("there should be at least one move")
         }
     }
+}
+
+/**
+ * picks randomly among the moves that are (almost) as good as the best one
+ */
+pub fn choose_next_move_with_rng<R: Rng>(evaluated_moves: Vec<EvaluatedMove>, contempt: i32, random: &mut R) -> EvaluatedMove {
+    let get_numeric_eval = |evaluation: &MoveEvaluation| match with_contempt(*evaluation, contempt) {
+        MoveEvaluation::Numeric(numeric_eval) => Some(numeric_eval),
+        MoveEvaluation::Draw(_) => Some(0.0),
+        _ => None,
+    };
 
-    evaluated_moves.sort_unstable_by(|e_m1, e_m2| e_m2.evaluation.cmp(&e_m1.evaluation));
+    let evaluated_moves = sort_best_first(evaluated_moves, contempt);
 
     let mut best_first_iter = evaluated_moves.iter();
     let mut chosen_move = *best_first_iter.next().expect("iterator should contain at least one move");
@@ -95,7 +335,6 @@ pub fn choose_next_move(mut evaluated_moves: Vec<EvaluatedMove>) -> EvaluatedMov
         None => { return chosen_move; }
         Some(num_eval) => { num_eval }
     };
-    let mut random = thread_rng();
     loop {
         if random.r#gen::<f32>() < 0.7 {
             break;
@@ -108,7 +347,7 @@ pub fn choose_next_move(mut evaluated_moves: Vec<EvaluatedMove>) -> EvaluatedMov
             None => { break; }
             Some(num_eval) => { num_eval }
         };
-        if best_eval - next_eval < 0.2 {
+        if best_eval - next_eval < ALMOST_AS_GOOD_IN_CENTIPAWNS as f32 / 100.0 {
             chosen_move = next_move;
         } else {
             break;
@@ -117,8 +356,26 @@ pub fn choose_next_move(mut evaluated_moves: Vec<EvaluatedMove>) -> EvaluatedMov
     chosen_move
 }
 
-fn get_eval_type_for(_game: &Game) -> StaticEvalType {
-    StaticEvalType::Default
+fn sort_best_first(mut evaluated_moves: Vec<EvaluatedMove>, contempt: i32) -> Vec<EvaluatedMove> {
+    evaluated_moves.sort_by(|e_m1, e_m2| with_contempt(e_m2.evaluation, contempt).cmp(&with_contempt(e_m1.evaluation, contempt)));
+    evaluated_moves
+}
+
+/**
+ * with a contempt a draw is treated like the corresponding numeric evaluation
+ */
+fn with_contempt(evaluation: MoveEvaluation, contempt: i32) -> MoveEvaluation {
+    match evaluation {
+        MoveEvaluation::Draw(_) if contempt != 0 => MoveEvaluation::Numeric(-contempt as f32 / 100.0),
+        _ => evaluation,
+    }
+}
+
+fn get_eval_type_for(_game: &Game, opt_eval_params: Option<EvalParams>) -> StaticEvalType {
+    match opt_eval_params {
+        None => StaticEvalType::Default,
+        Some(eval_params) => StaticEvalType::Parameterised(eval_params),
+    }
 }
 
 fn init_game(game_config: &str) -> OngoingGameOrEvaluation {
@@ -132,7 +389,10 @@ fn init_game(game_config: &str) -> OngoingGameOrEvaluation {
                     StoppedReason::NoChangeIn50Moves => {GameEvaluation::GameEnded(GameEndResult::Draw(DrawReason::NoChangeIn50Moves))}
                 }
             } else {
-                GameEvaluation::Err(format!("unexpected error {}, reason: {}", game_config, err))
+                GameEvaluation::Err(ChessError {
+                    msg: format!("unexpected error {}, reason: {}", game_config, err.msg),
+                    kind: err.kind,
+                })
             };
             return OngoingGameOrEvaluation::Ended(game_eval);
         }
@@ -172,6 +432,8 @@ mod tests {
     case("black ♔h6 ♙g7 ♚h8 ♗f5 ♙e4", PRUNER_L1, GameEvaluationMatcher::MoveToPlay(Move::from_code("h8-g8"), MoveEvaluationMatcher::EngineGetsCheckMatedIn(1))),
     case("black ♔g3 ♖d2 ♚h1 ♙c2 ♙d3", PRUNER_L1, GameEvaluationMatcher::MoveToPlay(Move::from_code("h1-g1"), MoveEvaluationMatcher::EngineGetsCheckMatedIn(1))),
     case("black ♔f3 ♖d2 ♚g1 ♙c2 ♙d3", PRUNER_L2, GameEvaluationMatcher::MoveToPlay(Move::from_code("g1-h1"), MoveEvaluationMatcher::EngineGetsCheckMatedIn(2))),
+    case("e2-e4 e2-e4", PRUNER_L0, GameEvaluationMatcher::Err),
+    case("white ♔e1 ♚e8 ♙", PRUNER_L0, GameEvaluationMatcher::Err),
     ::trace //This leads to the arguments being printed in front of the test result.
     )]
     fn test_evaluate(
@@ -186,4 +448,194 @@ mod tests {
             actual_evaluation,
         );
     }
-}
\ No newline at end of file
+
+    #[rstest(
+    game_config, move_str, expected_error_kind,
+    case("", "e2-e5", ErrorKind::IllegalMove),
+    case("", "e4-e5", ErrorKind::IllegalMove),
+    case("e2-e5", "e7-e5", ErrorKind::IllegalMove),
+    case("white ♔e1 ♚e8 ♙a", "e1-e2", ErrorKind::IllegalFormat),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_evaluate_single_move_returns_error(game_config: &str, move_str: &str, expected_error_kind: ErrorKind) {
+        match evaluate_single_move(game_config, Move::from_code(move_str), PRUNER_L0) {
+            GameEvaluation::Err(err) => assert_eq!(err.kind, expected_error_kind, "{}", err),
+            game_evaluation => panic!("unexpected evaluation: {:?}", game_evaluation),
+        }
+    }
+
+    fn get_evaluated_moves() -> Vec<EvaluatedMove> {
+        vec![
+            EvaluatedMove { a_move: Move::from_code("a2-a3"), evaluation: MoveEvaluation::Numeric(0.1) },
+            EvaluatedMove { a_move: Move::from_code("b2-b3"), evaluation: MoveEvaluation::Numeric(0.3) },
+            EvaluatedMove { a_move: Move::from_code("c2-c3"), evaluation: MoveEvaluation::Numeric(0.3) },
+            EvaluatedMove { a_move: Move::from_code("d2-d3"), evaluation: MoveEvaluation::Numeric(0.25) },
+            EvaluatedMove { a_move: Move::from_code("e2-e3"), evaluation: MoveEvaluation::Numeric(-1.0) },
+        ]
+    }
+
+    #[test]
+    fn test_deterministic_move_selection() {
+        for _ in 0..10 {
+            let chosen_move = choose_next_move_with(get_evaluated_moves(), MoveSelection::Deterministic, 0);
+            assert_eq!(chosen_move.a_move, Move::from_code("b2-b3"));
+        }
+    }
+
+    #[rstest(
+    seed,
+    case(0),
+    case(7),
+    case(1234567),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_seeded_move_selection(seed: u64) {
+        let chosen_move = choose_next_move_with(get_evaluated_moves(), MoveSelection::Seeded(seed), 0);
+        assert!(["b2-b3", "c2-c3", "d2-d3"].contains(&chosen_move.a_move.to_string().as_str()), "chosen move: {}", chosen_move.a_move);
+        for _ in 0..10 {
+            assert_eq!(choose_next_move_with(get_evaluated_moves(), MoveSelection::Seeded(seed), 0), chosen_move);
+        }
+    }
+
+    #[rstest(
+    game_config,
+    case(""),
+    case("e2-e4 d7-d5"),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_evaluate_with_config_is_reproducible(game_config: &str) {
+        for move_selection in [MoveSelection::Seeded(42), MoveSelection::Deterministic] {
+            let config = SearchConfig::new(PRUNER_L1).with_move_selection(move_selection);
+            let first_evaluation = evaluate_with_config(game_config, &config);
+            for _ in 0..3 {
+                assert_eq!(evaluate_with_config(game_config, &config), first_evaluation, "move selection: {:?}", move_selection);
+            }
+        }
+    }
+
+    #[rstest(
+    game_config, pruner,
+    case("", PRUNER_L1),
+    case("e2-e4 d7-d5", PRUNER_L2),
+    case("white ♔e1 ♕d1 ♙a2 ♚e8 ♜a8 ♝c8", PRUNER_L2),
+    case("white ♔e3 ♖d2 ♚g1 ♙c2 ♙d3", PRUNER_L3),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_search_root_moves_finds_the_best_evaluation(game_config: &str, pruner: Pruner) {
+        let game = game_config.parse::<Game>().unwrap();
+        let turn_by = game.get_game_state().turn_by;
+        // every move searched on its own with the whole window
+        let expected_best_score = game.get_reachable_moves().iter()
+            .map(|a_move| evaluate_move(&game, *a_move, pruner, turn_by, Score::MIN, StaticEvalType::Default))
+            .max()
+            .unwrap();
+        let root_moves = game.get_reachable_moves().to_vec();
+        assert_eq!(get_best_score(&search_root_moves(&game, &root_moves, pruner, StaticEvalType::Default, 0, Window::FULL)), expected_best_score);
+        for previous_score in [Score::from_pawns(0.0), Score::from_pawns(5.0), Score::from_pawns(-5.0), Score::win_in(4)] {
+            let evaluations = search_root_moves_around(Some(previous_score), |window| {
+                search_root_moves(&game, &root_moves, pruner, StaticEvalType::Default, 0, window)
+            });
+            assert_eq!(evaluations.len(), game.get_reachable_moves().len(), "previous_score: {:?}", previous_score);
+            assert_eq!(get_best_score(&evaluations), expected_best_score, "previous_score: {:?}", previous_score);
+        }
+        let mut nr_of_steps = 0;
+        let evaluations = deepen_root_moves(&game, pruner.get_deepening_steps(), StaticEvalType::Default, 0, MoveSelection::Deterministic, || { nr_of_steps += 1; false });
+        assert_eq!(nr_of_steps, pruner.get_deepening_steps().len());
+        assert_eq!(get_best_score(&evaluations), expected_best_score);
+    }
+
+    #[test]
+    fn test_with_exact_scores_of_almost_as_good_moves() {
+        let a_move = |move_str: &str| move_str.parse::<Move>().unwrap();
+        let exact_score_of = |searched_move: Move| match searched_move.to_string().as_str() {
+            "e2-e4" => Score::from_centipawns(50),
+            "d2-d4" => Score::from_centipawns(40),
+            "c2-c4" => Score::from_centipawns(-100),
+            _ => Score::from_centipawns(-300),
+        };
+        let evaluations = vec![
+            (a_move("e2-e4"), Score::from_centipawns(50)),
+            (a_move("d2-d4"), Score::from_centipawns(50)), // a bound equal to the best score
+            (a_move("c2-c4"), Score::from_centipawns(45)), // an inflated bound
+            (a_move("a2-a3"), Score::from_centipawns(30)), // a bound too low to be almost as good
+        ];
+        let mut searched_moves: Vec<Move> = vec![];
+        let rescored_evaluations = with_exact_scores_of_almost_as_good_moves(evaluations, |searched_move, window| {
+            assert_eq!(window, Window { alpha: Score::from_centipawns(30), beta: Score::MAX });
+            searched_moves.push(searched_move);
+            exact_score_of(searched_move).max(window.alpha)
+        });
+        // the best score isn't unique, so e2-e4 is searched again, too
+        assert_eq!(searched_moves, vec![a_move("e2-e4"), a_move("d2-d4"), a_move("c2-c4")]);
+        assert_eq!(rescored_evaluations, vec![
+            (a_move("e2-e4"), Score::from_centipawns(50)),
+            (a_move("d2-d4"), Score::from_centipawns(40)),
+            (a_move("c2-c4"), Score::from_centipawns(30)),
+            (a_move("a2-a3"), Score::from_centipawns(30)),
+        ]);
+
+        let mate_evaluations = vec![(a_move("e2-e4"), Score::win_in(3)), (a_move("d2-d4"), Score::win_in(5))];
+        assert_eq!(with_exact_scores_of_almost_as_good_moves(mate_evaluations.clone(), |_, _| panic!("no search expected")), mate_evaluations);
+    }
+
+    #[rstest(
+    game_config, pruner,
+    case("", PRUNER_L1),
+    case("e2-e4 d7-d5", PRUNER_L2),
+    case("white ♔e1 ♕d1 ♙a2 ♚e8 ♜a8 ♝c8", PRUNER_L2),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_random_selection_gets_exact_scores_of_almost_as_good_moves(game_config: &str, pruner: Pruner) {
+        let game = game_config.parse::<Game>().unwrap();
+        let turn_by = game.get_game_state().turn_by;
+        let evaluations = deepen_root_moves(&game, pruner.get_deepening_steps(), StaticEvalType::Default, 0, MoveSelection::Seeded(1), || false);
+        let almost_as_good_score = get_best_score(&evaluations).lowered_by(ALMOST_AS_GOOD_IN_CENTIPAWNS).unwrap();
+        for (a_move, score) in evaluations {
+            let exact_score = evaluate_move(&game, a_move, pruner, turn_by, Score::MIN, StaticEvalType::Default);
+            if exact_score > almost_as_good_score {
+                assert_eq!(score, exact_score, "move: {}", a_move);
+            } else {
+                assert!(score <= almost_as_good_score, "move: {}, score: {:?}", a_move, score);
+            }
+        }
+    }
+
+    #[test]
+    fn test_get_root_moves_best_first() {
+        let game = "".parse::<Game>().unwrap();
+        assert_eq!(get_root_moves_best_first(&game, &[]), game.get_reachable_moves().to_vec());
+
+        let a_move = |move_str: &str| move_str.parse::<Move>().unwrap();
+        let previous_evaluations = vec![
+            (a_move("a2-a3"), Score::from_pawns(-0.5)),
+            (a_move("e2-e4"), Score::from_pawns(0.3)),
+            (a_move("b1-c3"), Score::from_pawns(0.1)),
+            (a_move("g1-f3"), Score::from_pawns(0.1)),
+        ];
+        assert_eq!(get_root_moves_best_first(&game, &previous_evaluations), vec![
+            a_move("e2-e4"), a_move("b1-c3"), a_move("g1-f3"), a_move("a2-a3"),
+        ]);
+    }
+
+    #[rstest(
+    contempt, other_evaluation, expected_to_choose_draw,
+    case(0, MoveEvaluation::Numeric(-0.1), true),
+    case(0, MoveEvaluation::Numeric(0.1), false),
+    case(50, MoveEvaluation::Numeric(-0.1), false),
+    case(50, MoveEvaluation::Numeric(-0.6), true),
+    case(-50, MoveEvaluation::Numeric(0.3), true),
+    case(-50, MoveEvaluation::Numeric(0.6), false),
+    case(-50, MoveEvaluation::EngineCheckMatesIn(3), false),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_choose_next_move_with_contempt(contempt: i32, other_evaluation: MoveEvaluation, expected_to_choose_draw: bool) {
+        let evaluated_moves = vec![
+            EvaluatedMove { a_move: Move::from_code("a2-a3"), evaluation: other_evaluation },
+            EvaluatedMove { a_move: Move::from_code("b2-b3"), evaluation: MoveEvaluation::Draw(DrawReason::ThreeTimesRepetition) },
+        ];
+        for move_selection in [MoveSelection::Deterministic, MoveSelection::Seeded(3)] {
+            let chosen_move = choose_next_move_with(evaluated_moves.clone(), move_selection, contempt);
+            assert_eq!(chosen_move.evaluation == MoveEvaluation::Draw(DrawReason::ThreeTimesRepetition), expected_to_choose_draw, "move selection: {:?}", move_selection);
+        }
+    }
+}
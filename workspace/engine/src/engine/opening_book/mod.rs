@@ -0,0 +1,407 @@
+use std::fs;
+use std::path::Path;
+use rand::prelude::*;
+use rand::thread_rng;
+use crate::base::{find_reachable_move, CastlingType, ChessError, Color, ErrorKind, Move, MoveType, Position, PromotionType};
+use crate::figure::FigureType;
+use crate::game::{Game, GameState, USIZE_RANGE_063};
+use crate::engine::opening_book::polyglot_keys::POLYGLOT_RANDOM_64;
+
+mod polyglot_keys;
+
+const ENTRY_SIZE: usize = 16;
+const CASTLING_KEY_OFFSET: usize = 768;
+const EN_PASSANT_KEY_OFFSET: usize = 772;
+const TURN_KEY_OFFSET: usize = 780;
+
+/**
+ * an entry of a Polyglot book: 16 bytes in big-endian order.
+ * The move is encoded as: to column (bits 0-2), to row (3-5), from column (6-8), from row (9-11)
+ * and promotion (12-14: none, knight, bishop, rook, queen). Castling is encoded as king takes own rook.
+ */
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct BookEntry {
+    pub key: u64,
+    pub raw_move: u16,
+    pub weight: u16,
+    pub learn: u32,
+}
+
+impl BookEntry {
+    fn from_bytes(bytes: &[u8]) -> BookEntry {
+        BookEntry {
+            key: u64::from_be_bytes(bytes[0..8].try_into().unwrap()),
+            raw_move: u16::from_be_bytes(bytes[8..10].try_into().unwrap()),
+            weight: u16::from_be_bytes(bytes[10..12].try_into().unwrap()),
+            learn: u32::from_be_bytes(bytes[12..16].try_into().unwrap()),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct BookMove {
+    pub a_move: Move,
+    pub weight: u16,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum BookMoveSelection {
+    // a move is chosen with a probability proportional to its weight
+    WeightedRandom,
+    // always the move with the highest weight
+    Best,
+}
+
+pub struct OpeningBook {
+    // sorted by key so that the entries of a position can be found by binary search
+    entries: Vec<BookEntry>,
+    opt_max_depth: Option<usize>,
+}
+
+impl OpeningBook {
+    pub fn from_bytes(bytes: &[u8]) -> Result<OpeningBook, ChessError> {
+        if bytes.len() % ENTRY_SIZE != 0 {
+            return Err(ChessError {
+                msg: format!("the size of a polyglot book has to be a multiple of {} bytes but was {}", ENTRY_SIZE, bytes.len()),
+                kind: ErrorKind::IllegalFormat,
+            });
+        }
+        let mut entries: Vec<BookEntry> = bytes.chunks_exact(ENTRY_SIZE).map(BookEntry::from_bytes).collect();
+        // polyglot books are already sorted, but a stable sort keeps the order of the moves of a position
+        entries.sort_by_key(|entry| entry.key);
+        Ok(OpeningBook {
+            entries,
+            opt_max_depth: None,
+        })
+    }
+
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<OpeningBook, ChessError> {
+        let path = path.as_ref();
+        let bytes = fs::read(path).map_err(|err| ChessError {
+            msg: format!("couldn't read opening book {}: {}", path.display(), err),
+            kind: ErrorKind::IllegalConfig,
+        })?;
+        OpeningBook::from_bytes(bytes.as_slice())
+    }
+
+    /**
+     * the book is only consulted during the first max_depth half moves of a game
+     */
+    pub fn with_max_depth(self, max_depth: usize) -> OpeningBook {
+        OpeningBook {
+            opt_max_depth: Some(max_depth),
+            ..self
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /**
+     * all book moves for the given position in the order of the book (usually highest weight first).
+     * Entries whose move isn't reachable in the position (e.g. because of a key collision) are skipped.
+     * The max depth isn't taken into account here.
+     */
+    pub fn get_book_moves(&self, game_state: &GameState) -> Vec<BookMove> {
+        let key = polyglot_key(game_state);
+        let first_index = self.entries.partition_point(|entry| entry.key < key);
+        let reachable_moves = game_state.get_reachable_moves();
+
+        self.entries[first_index..].iter()
+            .take_while(|entry| entry.key == key)
+            .filter_map(|entry| {
+                decode_move(entry.raw_move, &reachable_moves).map(|a_move| BookMove {
+                    a_move,
+                    weight: entry.weight,
+                })
+            })
+            .collect()
+    }
+
+    /**
+     * returns None if the game is beyond the max depth of the book or the position isn't in the book.
+     * Moves with a weight of 0 are never chosen.
+     */
+    pub fn choose_move(&self, game: &Game, selection: BookMoveSelection) -> Option<Move> {
+        self.choose_move_with_rng(game, selection, &mut thread_rng())
+    }
+
+    /**
+     * like choose_move, but the weighted random choice uses the given random number generator (e.g. a seeded one)
+     */
+    pub fn choose_move_with_rng<R: Rng>(&self, game: &Game, selection: BookMoveSelection, random: &mut R) -> Option<Move> {
+        if let Some(max_depth) = self.opt_max_depth {
+            if game.get_half_moves_played() >= max_depth {
+                return None;
+            }
+        }
+        let book_moves: Vec<BookMove> = self.get_book_moves(game.get_game_state()).into_iter()
+            .filter(|book_move| book_move.weight > 0)
+            .collect();
+        if book_moves.is_empty() {
+            return None;
+        }
+
+        match selection {
+            BookMoveSelection::Best => {
+                // max_by_key returns the last of equally weighted moves, so the order is reversed first
+                book_moves.iter().rev().max_by_key(|book_move| book_move.weight).map(|book_move| book_move.a_move)
+            }
+            BookMoveSelection::WeightedRandom => {
+                let total_weight: u32 = book_moves.iter().map(|book_move| book_move.weight as u32).sum();
+                let mut random_weight = random.gen_range(0..total_weight);
+                for book_move in book_moves.iter() {
+                    let weight = book_move.weight as u32;
+                    if random_weight < weight {
+                        return Some(book_move.a_move);
+                    }
+                    random_weight -= weight;
+                }
+                unreachable!("the random weight is smaller than the total weight")
+            }
+        }
+    }
+}
+
+/**
+ * the Zobrist key of the position as defined by the Polyglot book format
+ */
+pub fn polyglot_key(game_state: &GameState) -> u64 {
+    let mut key: u64 = 0;
+    for state_index in USIZE_RANGE_063 {
+        let pos = Position::from_index_unchecked(state_index);
+        if let Some(figure) = game_state.board.get_figure(pos) {
+            let type_index = match figure.fig_type {
+                FigureType::Pawn => 0,
+                FigureType::Knight => 1,
+                FigureType::Bishop => 2,
+                FigureType::Rook => 3,
+                FigureType::Queen => 4,
+                FigureType::King => 5,
+            };
+            let kind = 2 * type_index + if figure.color == Color::White { 1 } else { 0 };
+            // the index of a position is 8 * row + column, just like the square index of polyglot
+            key ^= POLYGLOT_RANDOM_64[64 * kind + state_index];
+        }
+    }
+
+    let castling_rights = [
+        game_state.is_white_king_side_castling_still_possible,
+        game_state.is_white_queen_side_castling_still_possible,
+        game_state.is_black_king_side_castling_still_possible,
+        game_state.is_black_queen_side_castling_still_possible,
+    ];
+    for (offset, castling_right) in castling_rights.iter().enumerate() {
+        if castling_right.get_value() {
+            key ^= POLYGLOT_RANDOM_64[CASTLING_KEY_OFFSET + offset];
+        }
+    }
+
+    if let Some(en_passant_pos) = game_state.en_passant_intercept_pos {
+        if can_capture_en_passant(game_state, en_passant_pos) {
+            key ^= POLYGLOT_RANDOM_64[EN_PASSANT_KEY_OFFSET + en_passant_pos.column() as usize];
+        }
+    }
+
+    if game_state.turn_by == Color::White {
+        key ^= POLYGLOT_RANDOM_64[TURN_KEY_OFFSET];
+    }
+    key
+}
+
+/**
+ * polyglot only hashes the en-passant column if a pawn of the active color stands next to the pawn
+ * that just moved two steps (whether the capture would be legal doesn't matter).
+ */
+fn can_capture_en_passant(game_state: &GameState, en_passant_pos: Position) -> bool {
+    let capturing_pawn_row = if game_state.turn_by == Color::White {
+        en_passant_pos.row() - 1
+    } else {
+        en_passant_pos.row() + 1
+    };
+    [en_passant_pos.column() - 1, en_passant_pos.column() + 1].iter().any(|column| {
+        match Position::new_checked(*column, capturing_pawn_row) {
+            None => false,
+            Some(pos) => game_state.board.contains_figure(pos, FigureType::Pawn, game_state.turn_by),
+        }
+    })
+}
+
+/**
+ * finds the reachable move matching the polyglot move. Polyglot encodes castling as the king
+ * taking its own rook (e.g. e1h1), while the castling moves of this engine go to the king's destination (e1cg1).
+ */
+fn decode_move(raw_move: u16, reachable_moves: &[Move]) -> Option<Move> {
+    let raw_move = raw_move as usize;
+    let to = Position::from_index_unchecked(raw_move & 63);
+    let from = Position::from_index_unchecked((raw_move >> 6) & 63);
+    let opt_promotion_type = match (raw_move >> 12) & 7 {
+        0 => None,
+        1 => Some(PromotionType::Knight),
+        2 => Some(PromotionType::Bishop),
+        3 => Some(PromotionType::Rook),
+        4 => Some(PromotionType::Queen),
+        _ => { return None; }
+    };
+    find_reachable_move(reachable_moves, from, to, opt_promotion_type)
+        .or_else(|| find_castling_move(reachable_moves, from, to))
+}
+
+fn find_castling_move(reachable_moves: &[Move], king_pos: Position, rook_pos: Position) -> Option<Move> {
+    if rook_pos.row() != king_pos.row() {
+        return None;
+    }
+    let castling_type = match rook_pos.column() {
+        7 => CastlingType::KingSide,
+        0 => CastlingType::QueenSide,
+        _ => { return None; }
+    };
+    reachable_moves.iter().find(|a_move| {
+        a_move.from() == king_pos && a_move.move_type() == MoveType::Castling(castling_type)
+    }).copied()
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+    use rand::rngs::StdRng;
+
+    #[rstest(
+    game_config, expected_key,
+    case("", 0x463b96181691fc9c),
+    case("e2-e4", 0x823c9b50fd114196),
+    case("e2-e4 d7-d5", 0x0756b94461c50fb0),
+    case("e2-e4 d7-d5 e4-e5", 0x662fafb965db29d4),
+    case("e2-e4 d7-d5 e4-e5 f7-f5", 0x22a48b5a8e47ff78), // the en-passant column is hashed
+    case("e2-e4 d7-d5 e4-e5 f7-f5 e1-e2", 0x652a607ca3f242c1),
+    case("e2-e4 d7-d5 e4-e5 f7-f5 e1-e2 e8-f7", 0x00fdd303c946bdd9),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_polyglot_key(game_config: &str, expected_key: u64) {
+        let game = game_config.parse::<Game>().unwrap();
+        assert_eq!(polyglot_key(game.get_game_state()), expected_key, "key: {:#x}", polyglot_key(game.get_game_state()));
+    }
+
+    fn encode_move(a_move: &str) -> u16 {
+        let a_move = a_move.parse::<Move>().unwrap();
+        ((a_move.from().index << 6) | a_move.to().index) as u16
+    }
+
+    fn book_bytes(entries: &[(u64, &str, u16)]) -> Vec<u8> {
+        let mut bytes: Vec<u8> = vec![];
+        for (key, a_move, weight) in entries {
+            bytes.extend_from_slice(&key.to_be_bytes());
+            bytes.extend_from_slice(&encode_move(a_move).to_be_bytes());
+            bytes.extend_from_slice(&weight.to_be_bytes());
+            bytes.extend_from_slice(&0_u32.to_be_bytes());
+        }
+        bytes
+    }
+
+    fn test_book() -> OpeningBook {
+        OpeningBook::from_bytes(book_bytes(&[
+            (0x823c9b50fd114196, "d7-d5", 3),
+            (0x823c9b50fd114196, "e7-e5", 10),
+            (0x463b96181691fc9c, "e2-e4", 20),
+            (0x463b96181691fc9c, "d2-d4", 20),
+            (0x463b96181691fc9c, "b1-c3", 0),
+            (0x463b96181691fc9c, "e2-e5", 100), // not a reachable move
+        ]).as_slice()).unwrap()
+    }
+
+    #[test]
+    fn test_get_book_moves() {
+        let book = test_book();
+        assert_eq!(book.len(), 6);
+
+        let book_moves = book.get_book_moves(&GameState::classic());
+        let book_move_strs: Vec<String> = book_moves.iter().map(|book_move| format!("{}:{}", book_move.a_move, book_move.weight)).collect();
+        assert_eq!(book_move_strs, vec!["e2-e4:20", "d2-d4:20", "b1-c3:0"]);
+
+        let game = "d2-d4".parse::<Game>().unwrap();
+        assert!(book.get_book_moves(game.get_game_state()).is_empty());
+    }
+
+    #[rstest(
+    game_config, expected_move,
+    case("", Some("e2-e4")), // the first of equally weighted moves
+    case("e2-e4", Some("e7-e5")),
+    case("d2-d4", None),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_choose_best_move(game_config: &str, expected_move: Option<&str>) {
+        let game = game_config.parse::<Game>().unwrap();
+        let expected_move = expected_move.map(|a_move| a_move.parse::<Move>().unwrap());
+        assert_eq!(test_book().choose_move(&game, BookMoveSelection::Best), expected_move);
+    }
+
+    #[test]
+    fn test_choose_weighted_random_move() {
+        let book = test_book();
+        let game = Game::classic();
+        for _ in 0..20 {
+            let chosen_move = book.choose_move(&game, BookMoveSelection::WeightedRandom).unwrap();
+            // b1-c3 has a weight of 0
+            assert!(chosen_move == Move::from_code("e2-e4") || chosen_move == Move::from_code("d2-d4"), "chosen move: {}", chosen_move);
+        }
+    }
+
+    #[test]
+    fn test_choose_seeded_random_move() {
+        let book = test_book();
+        let game = Game::classic();
+        let chosen_move = book.choose_move_with_rng(&game, BookMoveSelection::WeightedRandom, &mut StdRng::seed_from_u64(5));
+        for _ in 0..10 {
+            assert_eq!(book.choose_move_with_rng(&game, BookMoveSelection::WeightedRandom, &mut StdRng::seed_from_u64(5)), chosen_move);
+        }
+    }
+
+    #[rstest(
+    game_config, polyglot_move, expected_move,
+    case("e2-e4 e7-e5 g1-f3 g8-f6 f1-c4 f8-c5", "e1-h1", "e1cg1"),
+    case("e2-e4 e7-e5 g1-f3 g8-f6 f1-c4 f8-c5 e1cg1", "e8-h8", "e8cg8"),
+    case("d2-d4 d7-d5 b1-c3 b8-c6 c1-f4 c8-f5 d1-d2 d8-d7", "e1-a1", "e1Cc1"),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_castling_book_move(game_config: &str, polyglot_move: &str, expected_move: &str) {
+        let game = game_config.parse::<Game>().unwrap();
+        let book = OpeningBook::from_bytes(book_bytes(&[
+            (polyglot_key(game.get_game_state()), polyglot_move, 1),
+        ]).as_slice()).unwrap();
+        assert_eq!(book.choose_move(&game, BookMoveSelection::Best), Some(Move::from_code(expected_move)));
+    }
+
+    #[test]
+    fn test_max_depth() {
+        let book = test_book().with_max_depth(1);
+        assert!(book.choose_move(&Game::classic(), BookMoveSelection::Best).is_some());
+        let game = "e2-e4".parse::<Game>().unwrap();
+        assert!(book.choose_move(&game, BookMoveSelection::Best).is_none());
+    }
+
+    #[rstest(
+    nr_of_bytes,
+    case(1),
+    case(15),
+    case(17),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_from_bytes_fails_on_illegal_size(nr_of_bytes: usize) {
+        let err = OpeningBook::from_bytes(vec![0_u8; nr_of_bytes].as_slice()).err().unwrap();
+        assert!(matches!(err.kind, ErrorKind::IllegalFormat), "error kind: {:?}", err.kind);
+    }
+
+    #[test]
+    fn test_from_file_fails_on_missing_file() {
+        let err = OpeningBook::from_file("this/file/does/not/exist.bin").err().unwrap();
+        assert!(matches!(err.kind, ErrorKind::IllegalConfig), "error kind: {:?}", err.kind);
+    }
+}
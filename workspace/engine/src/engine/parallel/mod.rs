@@ -0,0 +1,180 @@
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use crate::base::Move;
+use crate::engine::{choose_next_move_with, deepen_root_moves_with, evaluate_root_moves, get_eval_type_for, init_game, to_evaluated_moves, to_game_evaluation, OngoingGameOrEvaluation};
+use crate::engine::evaluations::EvaluatedMove;
+use crate::engine::evaluations::score::Score;
+use crate::engine::evaluations::frontend::GameEvaluation;
+use crate::engine::min_max::{evaluate_move_in_window, Window};
+use crate::engine::min_max::pruner::Pruner;
+use crate::engine::search_config::{MoveSelection, SearchConfig};
+use crate::engine::static_eval::{EvalParams, StaticEvalType};
+use crate::game::Game;
+
+/**
+ * the number of threads the platform recommends (at least 1)
+ */
+pub fn get_default_nr_of_threads() -> usize {
+    thread::available_parallelism().map(|nr_of_threads| nr_of_threads.get()).unwrap_or(1)
+}
+
+/**
+ * like engine::evaluate, but the moves of the current position are distributed over nr_of_threads threads
+ */
+pub fn evaluate_parallel(game_config: &str, pruner: Pruner, nr_of_threads: usize) -> GameEvaluation {
+    evaluate_parallel_with_params(game_config, pruner, None, nr_of_threads)
+}
+
+/**
+ * like engine::evaluate_with_params, but the moves of the current position are distributed over nr_of_threads threads
+ */
+pub fn evaluate_parallel_with_params(game_config: &str, pruner: Pruner, opt_eval_params: Option<EvalParams>, nr_of_threads: usize) -> GameEvaluation {
+    evaluate_parallel_with_config(game_config, &SearchConfig {
+        pruner,
+        opt_eval_params,
+        move_selection: MoveSelection::Random,
+        contempt_in_centipawns: 0,
+    }, nr_of_threads)
+}
+
+/**
+ * like engine::evaluate_with_config, but the moves of the current position are distributed over nr_of_threads threads
+ */
+pub fn evaluate_parallel_with_config(game_config: &str, config: &SearchConfig, nr_of_threads: usize) -> GameEvaluation {
+    let game = match init_game(game_config) {
+        OngoingGameOrEvaluation::Ongoing(game) => {game}
+        OngoingGameOrEvaluation::Ended(final_eval) => {return final_eval;}
+    };
+    let eval_type = get_eval_type_for(&game, config.opt_eval_params);
+
+    #[cfg(feature = "syzygy")]
+    if let Some((tablebase_move, evaluation)) = crate::engine::tablebase::get_tablebase_move(&game, eval_type) {
+        use crate::engine::evaluations::frontend::MoveEvaluation;
+        return to_game_evaluation(&game, EvaluatedMove { a_move: tablebase_move, evaluation: MoveEvaluation::from(&evaluation) });
+    }
+
+    let evaluated_moves = if nr_of_threads <= 1 {
+        evaluate_root_moves(&game, config.pruner, eval_type, config.contempt_in_centipawns, config.move_selection)
+    } else {
+        evaluate_root_moves_parallel(game_config, &game, config.pruner, eval_type, config.contempt_in_centipawns, config.move_selection, nr_of_threads)
+    };
+    to_game_evaluation(&game, choose_next_move_with(evaluated_moves, config.move_selection, config.contempt_in_centipawns))
+}
+
+/**
+ * Root splitting with iterative deepening: like engine::evaluate_root_moves every step of pruner.get_deepening_steps()
+ * searches the moves best first within an aspiration window (see engine::deepen_root_moves),
+ * but the moves of a step are distributed over the threads (see search_root_moves_parallel).
+ */
+fn evaluate_root_moves_parallel(
+    game_config: &str,
+    game: &Game,
+    pruner: Pruner,
+    eval_type: StaticEvalType,
+    contempt: i32,
+    move_selection: MoveSelection,
+    nr_of_threads: usize,
+) -> Vec<EvaluatedMove> {
+    let evaluations = deepen_root_moves_with(game, pruner.get_deepening_steps(), move_selection, || false, |root_moves, step_pruner, window| {
+        search_root_moves_parallel(game_config, root_moves, step_pruner, eval_type, contempt, window, nr_of_threads)
+    });
+    to_evaluated_moves(evaluations)
+}
+
+/**
+ * Root splitting: the threads take the next unevaluated move until all are done.
+ * The best evaluation found so far is shared, so that the threads can prune like the single-threaded search does.
+ * Once a move fails high no further moves are taken (like in engine::search_root_moves).
+ * A Game can't be sent to another thread, so every thread parses its own copy of the game_config.
+ */
+fn search_root_moves_parallel(
+    game_config: &str,
+    root_moves: &[Move],
+    pruner: Pruner,
+    eval_type: StaticEvalType,
+    contempt: i32,
+    window: Window,
+    nr_of_threads: usize,
+) -> Vec<(Move, Score)> {
+    let next_move_index = AtomicUsize::new(0);
+    let current_max_score: Mutex<Score> = Mutex::new(window.alpha);
+
+    let mut indexed_moves: Vec<(usize, (Move, Score))> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..nr_of_threads.min(root_moves.len())).map(|_| {
+            scope.spawn(|| {
+                let game = game_config.parse::<Game>().expect("the game_config was already parsed successfully");
+                let turn_by = game.get_game_state().turn_by;
+                let mut evaluations: Vec<(usize, (Move, Score))> = Vec::new();
+                loop {
+                    let max_score = *current_max_score.lock().unwrap();
+                    if window.fails_high(max_score) {
+                        return evaluations;
+                    }
+                    let move_index = next_move_index.fetch_add(1, Ordering::Relaxed);
+                    let Some(next_move) = root_moves.get(move_index) else {
+                        return evaluations;
+                    };
+                    let score = evaluate_move_in_window(&game, *next_move, pruner, turn_by, Window { alpha: max_score, beta: window.beta }, eval_type, contempt);
+                    {
+                        let mut current_max_score = current_max_score.lock().unwrap();
+                        if score > *current_max_score {
+                            *current_max_score = score;
+                        }
+                    }
+                    evaluations.push((move_index, (*next_move, score)));
+                }
+            })
+        }).collect();
+        handles.into_iter().flat_map(|handle| handle.join().expect("search thread panicked")).collect()
+    });
+
+    // same order as the single-threaded search
+    indexed_moves.sort_unstable_by_key(|(move_index, _)| *move_index);
+    indexed_moves.into_iter().map(|(_, evaluation)| evaluation).collect()
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+    use crate::engine::evaluate;
+    use crate::engine::min_max::pruner::*;
+
+    //♔♕♗♘♖♙♚♛♝♞♜♟
+
+    #[rstest(
+    game_config, pruner,
+    case("black ♔b6 ♙a7 ♚a8", PRUNER_L0),
+    case("white ♔g3 ♖d1 ♚g1 ♙c2 ♙d3", PRUNER_L0),
+    case("white ♔f3 ♖d2 ♚h1 ♙c2 ♙d3", PRUNER_L2),
+    case("white ♔e3 ♖d2 ♚g1 ♙c2 ♙d3", PRUNER_L3),
+    case("black ♔f3 ♖d2 ♚g1 ♙c2 ♙d3", PRUNER_L2),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_parallel_matches_single_threaded(game_config: &str, pruner: Pruner) {
+        assert_eq!(evaluate_parallel(game_config, pruner, 4), evaluate(game_config, pruner));
+    }
+
+    #[rstest(
+    game_config, pruner,
+    case("", PRUNER_L1),
+    case("e2-e4 d7-d5", PRUNER_L2),
+    case("white ♔e1 ♕d1 ♙a2 ♚e8 ♜a8 ♝c8", PRUNER_L2),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_parallel_finds_the_same_best_evaluation(game_config: &str, pruner: Pruner) {
+        let game = game_config.parse::<Game>().unwrap();
+        let get_best_evaluation = |evaluated_moves: Vec<EvaluatedMove>| evaluated_moves.into_iter()
+            .map(|evaluated_move| evaluated_move.evaluation)
+            .max()
+            .unwrap();
+        let single_threaded = get_best_evaluation(evaluate_root_moves(&game, pruner, StaticEvalType::Default, 0, MoveSelection::Deterministic));
+        for nr_of_threads in [2, 3, 8] {
+            let parallel = get_best_evaluation(evaluate_root_moves_parallel(game_config, &game, pruner, StaticEvalType::Default, 0, MoveSelection::Deterministic, nr_of_threads));
+            assert_eq!(parallel, single_threaded, "nr_of_threads: {}", nr_of_threads);
+        }
+    }
+}
@@ -0,0 +1,228 @@
+use crate::base::{ChessError, ErrorKind, Move};
+use crate::engine::{choose_next_move_with, get_eval_type_for, init_game, OngoingGameOrEvaluation};
+use crate::engine::evaluations::{DrawReason, EvaluatedMove};
+use crate::engine::evaluations::frontend::{GameEndResult, GameEvaluation, MoveEvaluation};
+use crate::engine::min_max::{evaluate_move_in_window, Window};
+use crate::engine::search_config::SearchConfig;
+use crate::figure::functions::allowed::get_legal_moves_of;
+use crate::game::Game;
+
+/**
+ * one legal move of the position to evaluate. It contains everything needed to evaluate it on its own,
+ * so it can be handed to another thread, a web worker or a job queue.
+ */
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct WorkItem {
+    pub game_config: String,
+    pub a_move: Move,
+}
+
+#[derive(Debug, Clone)]
+pub enum RootSplit {
+    /**
+     * there is nothing to evaluate: the game has ended (checkmate, stalemate, a draw) or the game_config is invalid
+     */
+    Ended(GameEvaluation),
+    WorkItems(Vec<WorkItem>),
+}
+
+/**
+ * Splitting the search at the root: the legal moves of the position become work items
+ * that can be evaluated independently (see evaluate_work_item). The evaluated moves are merged
+ * into the move to play with merge_evaluated_moves.
+ * Unlike engine::parallel the evaluations can't prune each other, so every move gets its exact evaluation.
+ */
+pub fn split_root_moves(game_config: &str) -> RootSplit {
+    let game = match init_game(game_config) {
+        OngoingGameOrEvaluation::Ongoing(game) => {game}
+        OngoingGameOrEvaluation::Ended(final_eval) => {return RootSplit::Ended(final_eval);}
+    };
+    let legal_moves = get_legal_moves_of(&game);
+    if legal_moves.is_empty() {
+        let game_end_result = if game.is_active_king_in_check() {
+            GameEndResult::EngineLost
+        } else {
+            GameEndResult::Draw(DrawReason::StaleMate)
+        };
+        return RootSplit::Ended(GameEvaluation::GameEnded(game_end_result));
+    }
+    RootSplit::WorkItems(legal_moves.into_iter().map(|a_move| WorkItem {
+        game_config: game_config.to_string(),
+        a_move,
+    }).collect())
+}
+
+/**
+ * evaluates the move of the work item with the pruner, evaluation weights and contempt of the config
+ */
+pub fn evaluate_work_item(work_item: &WorkItem, config: &SearchConfig) -> Result<EvaluatedMove, ChessError> {
+    let game = work_item.game_config.parse::<Game>()?;
+    if !get_legal_moves_of(&game).contains(&work_item.a_move) {
+        return Err(ChessError {
+            msg: format!("move {} isn't possible in {}", work_item.a_move, game.get_fen()),
+            kind: ErrorKind::IllegalMove,
+        });
+    }
+    let eval_type = get_eval_type_for(&game, config.opt_eval_params);
+
+    #[cfg(feature = "syzygy")]
+    if let Some(evaluation) = crate::engine::tablebase::probe_move(&game, work_item.a_move, eval_type) {
+        return Ok(EvaluatedMove { a_move: work_item.a_move, evaluation: MoveEvaluation::from(&evaluation) });
+    }
+
+    let score = evaluate_move_in_window(
+        &game,
+        work_item.a_move,
+        config.pruner,
+        game.get_game_state().turn_by,
+        Window::FULL,
+        eval_type,
+        config.contempt_in_centipawns,
+    );
+    Ok(EvaluatedMove { a_move: work_item.a_move, evaluation: MoveEvaluation::from_score(score) })
+}
+
+/**
+ * picks the move to play (according to the move selection and contempt of the config) from the evaluated work items
+ * of split_root_moves(game_config). Every work item has to be evaluated exactly once, but the order doesn't matter.
+ */
+pub fn merge_evaluated_moves(game_config: &str, evaluated_moves: Vec<EvaluatedMove>, config: &SearchConfig) -> GameEvaluation {
+    let work_items = match split_root_moves(game_config) {
+        RootSplit::Ended(final_eval) => {return final_eval;}
+        RootSplit::WorkItems(work_items) => {work_items}
+    };
+    if let Some(unexpected_move) = evaluated_moves.iter().find(|evaluated_move| {
+        !work_items.iter().any(|work_item| work_item.a_move == evaluated_move.a_move)
+    }) {
+        return GameEvaluation::Err(ChessError {
+            msg: format!("{} isn't one of the moves to evaluate", unexpected_move.a_move),
+            kind: ErrorKind::IllegalConfig,
+        });
+    }
+    // ordered like the work items, so that the move selection doesn't depend on the order the evaluations arrived in
+    let mut ordered_moves: Vec<EvaluatedMove> = Vec::with_capacity(work_items.len());
+    for work_item in work_items.iter() {
+        let mut evaluations_of_move = evaluated_moves.iter().filter(|evaluated_move| evaluated_move.a_move == work_item.a_move);
+        match (evaluations_of_move.next(), evaluations_of_move.next()) {
+            (Some(evaluated_move), None) => ordered_moves.push(*evaluated_move),
+            (opt_first, _) => {
+                let problem = if opt_first.is_none() { "is missing" } else { "was given more than once" };
+                return GameEvaluation::Err(ChessError {
+                    msg: format!("the evaluation of move {} {}", work_item.a_move, problem),
+                    kind: ErrorKind::IllegalConfig,
+                });
+            }
+        }
+    }
+    let best_move = choose_next_move_with(ordered_moves, config.move_selection, config.contempt_in_centipawns);
+    GameEvaluation::MoveToPlay(best_move.a_move, best_move.evaluation)
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+    use std::thread;
+    use crate::engine::evaluate_root_moves;
+    use crate::engine::min_max::pruner::*;
+    use crate::engine::search_config::MoveSelection;
+    use crate::engine::static_eval::StaticEvalType;
+    use crate::engine::evaluations::testing::GameEvaluationMatcher;
+
+    //♔♕♗♘♖♙♚♛♝♞♜♟
+
+    #[rstest(
+    game_config, expected_matcher,
+    case("black ♔b6 ♙a7 ♚a8", GameEvaluationMatcher::GameEnded(GameEndResult::Draw(DrawReason::StaleMate))),
+    case("black ♔g3 ♖d1 ♚g1 ♙c2 ♙d3", GameEvaluationMatcher::GameEnded(GameEndResult::EngineLost)),
+    case("white ♔h8 ♚f8 ♞a7", GameEvaluationMatcher::GameEnded(GameEndResult::Draw(DrawReason::InsufficientMaterial))),
+    case("e2-e4 e2-e4", GameEvaluationMatcher::Err),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_split_ended_game(game_config: &str, expected_matcher: GameEvaluationMatcher) {
+        match split_root_moves(game_config) {
+            RootSplit::Ended(game_evaluation) => assert!(expected_matcher.matches(&game_evaluation), "{:?}", game_evaluation),
+            RootSplit::WorkItems(work_items) => panic!("unexpected work items: {:?}", work_items),
+        }
+    }
+
+    #[rstest(
+    game_config, expected_nr_of_work_items,
+    case("", 20),
+    // the white king is in check by the bishop
+    case("white ♔e1 ♖h1 ♚g8 ♝c3", 4),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_split_into_legal_moves(game_config: &str, expected_nr_of_work_items: usize) {
+        match split_root_moves(game_config) {
+            RootSplit::Ended(game_evaluation) => panic!("unexpected end: {:?}", game_evaluation),
+            RootSplit::WorkItems(work_items) => assert_eq!(work_items.len(), expected_nr_of_work_items, "{:?}", work_items),
+        }
+    }
+
+    #[rstest(
+    game_config, pruner,
+    case("white ♔g3 ♖d2 ♚g1 ♙c2 ♙d3", PRUNER_L0),
+    case("white ♔f3 ♖d2 ♚h1 ♙c2 ♙d3", PRUNER_L2),
+    case("e2-e4 d7-d5", PRUNER_L1),
+    case("white ♔e1 ♕d1 ♙a2 ♚e8 ♜a8 ♝c8", PRUNER_L2),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_split_evaluate_merge_on_threads(game_config: &str, pruner: Pruner) {
+        let config = SearchConfig::new(pruner).with_move_selection(MoveSelection::Deterministic);
+        let RootSplit::WorkItems(work_items) = split_root_moves(game_config) else {
+            panic!("the game shouldn't have ended");
+        };
+        // the work items are evaluated in reverse order on 2 threads
+        let (first_half, second_half) = work_items.split_at(work_items.len() / 2);
+        let evaluated_moves: Vec<EvaluatedMove> = thread::scope(|scope| {
+            let handles: Vec<_> = [second_half, first_half].into_iter().map(|work_items| {
+                scope.spawn(move || work_items.iter().rev()
+                    .map(|work_item| evaluate_work_item(work_item, &config).unwrap())
+                    .collect::<Vec<EvaluatedMove>>())
+            }).collect();
+            handles.into_iter().flat_map(|handle| handle.join().unwrap()).collect()
+        });
+
+        let game = game_config.parse::<Game>().unwrap();
+        let single_threaded_best = evaluate_root_moves(&game, pruner, StaticEvalType::Default, 0, MoveSelection::Deterministic).into_iter()
+            .map(|evaluated_move| evaluated_move.evaluation)
+            .max()
+            .unwrap();
+        match merge_evaluated_moves(game_config, evaluated_moves, &config) {
+            GameEvaluation::MoveToPlay(_, evaluation) => assert_eq!(evaluation, single_threaded_best),
+            game_evaluation => panic!("unexpected evaluation: {:?}", game_evaluation),
+        }
+    }
+
+    #[test]
+    fn test_merge_needs_every_move_once() {
+        let game_config = "white ♔e1 ♖h1 ♚g8 ♝c3";
+        let config = SearchConfig::new(PRUNER_L0);
+        let RootSplit::WorkItems(work_items) = split_root_moves(game_config) else {
+            panic!("the game shouldn't have ended");
+        };
+        let evaluated_moves: Vec<EvaluatedMove> = work_items.iter()
+            .map(|work_item| evaluate_work_item(work_item, &config).unwrap())
+            .collect();
+        assert!(matches!(merge_evaluated_moves(game_config, evaluated_moves.clone(), &config), GameEvaluation::MoveToPlay(_, _)));
+
+        let missing_one = evaluated_moves[1..].to_vec();
+        assert!(matches!(merge_evaluated_moves(game_config, missing_one, &config), GameEvaluation::Err(_)));
+        let mut duplicated_one = evaluated_moves.clone();
+        duplicated_one.push(evaluated_moves[0]);
+        assert!(matches!(merge_evaluated_moves(game_config, duplicated_one, &config), GameEvaluation::Err(_)));
+        let mut unexpected_one = evaluated_moves.clone();
+        unexpected_one.push(EvaluatedMove { a_move: Move::from_code("e1-d2"), evaluation: MoveEvaluation::Numeric(0.0) });
+        assert!(matches!(merge_evaluated_moves(game_config, unexpected_one, &config), GameEvaluation::Err(_)));
+    }
+
+    #[test]
+    fn test_evaluate_illegal_work_item() {
+        let work_item = WorkItem { game_config: "white ♔e1 ♖h1 ♚g8 ♝c3".to_string(), a_move: Move::from_code("e1-d2") };
+        let err = evaluate_work_item(&work_item, &SearchConfig::new(PRUNER_L0)).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::IllegalMove);
+    }
+}
@@ -0,0 +1,68 @@
+use crate::engine::min_max::pruner::Pruner;
+use crate::engine::static_eval::EvalParams;
+
+/**
+ * how the move to play is picked from the evaluated moves
+ */
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum MoveSelection {
+    /**
+     * picks randomly among the moves that are (almost) as good as the best one.
+     * The random number generator is seeded by the OS, so the choice can't be reproduced.
+     */
+    Random,
+    /**
+     * like Random, but the random number generator is seeded with the given value
+     * so the same position and seed always lead to the same move
+     */
+    Seeded(u64),
+    /**
+     * always the best move, of several equally good ones the first one generated
+     */
+    Deterministic,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct SearchConfig {
+    pub pruner: Pruner,
+    pub opt_eval_params: Option<EvalParams>,
+    pub move_selection: MoveSelection,
+    /**
+     * how much worse than an even position a draw is for the engine (in centipawns).
+     * Positive against weaker opponents (the engine avoids repetitions, stalemates and trading into insufficient material),
+     * negative against stronger ones (the engine seeks them).
+     */
+    pub contempt_in_centipawns: i32,
+}
+
+impl SearchConfig {
+    pub fn new(pruner: Pruner) -> SearchConfig {
+        SearchConfig {
+            pruner,
+            opt_eval_params: None,
+            move_selection: MoveSelection::Random,
+            contempt_in_centipawns: 0,
+        }
+    }
+
+    pub fn with_eval_params(self, eval_params: EvalParams) -> SearchConfig {
+        SearchConfig {
+            opt_eval_params: Some(eval_params),
+            ..self
+        }
+    }
+
+    pub fn with_move_selection(self, move_selection: MoveSelection) -> SearchConfig {
+        SearchConfig {
+            move_selection,
+            ..self
+        }
+    }
+
+    pub fn with_contempt(self, contempt_in_centipawns: i32) -> SearchConfig {
+        SearchConfig {
+            contempt_in_centipawns,
+            ..self
+        }
+    }
+}
@@ -0,0 +1,381 @@
+use std::{fmt, str};
+use rand::prelude::*;
+use rand::rngs::StdRng;
+use web_time::{Duration, Instant};
+use crate::base::{ChessError, ErrorKind};
+use crate::engine::{choose_next_move_with_rng, deepen_root_moves, get_eval_type_for, init_game, sort_best_first, to_evaluated_moves, to_game_evaluation, OngoingGameOrEvaluation};
+use crate::engine::evaluations::EvaluatedMove;
+use crate::engine::evaluations::frontend::{GameEvaluation, MoveEvaluation};
+use crate::game::Game;
+use crate::engine::min_max::pruner::*;
+use crate::engine::search_config::MoveSelection;
+
+pub const MIN_SKILL_LEVEL: u8 = 1;
+pub const MAX_SKILL_LEVEL: u8 = 20;
+
+// a rough guess, the levels haven't been rated against other engines
+const ELO_BELOW_MIN_SKILL_LEVEL: u32 = 740;
+const ELO_PER_SKILL_LEVEL: u32 = 60;
+
+// a higher skill level can use a pruner further back
+static PRUNERS_BY_STRENGTH: [&Pruner; 5] = [&PRUNER_L0, &PRUNER_L1, &PRUNER_1_1_3_3, &PRUNER_1_2_3_4, &PRUNER_2_2_3_4];
+
+/**
+ * the playing strength from 1 (weakest) to 20 (strongest). A level combines the search depth,
+ * noise on the evaluations and the probability to play a random move on purpose.
+ * A time limit can be added to keep the engine responsive (see with_time_limit).
+ */
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub struct SkillLevel {
+    level: u8,
+    opt_time_limit: Option<Duration>,
+    contempt_in_centipawns: i32,
+}
+
+impl SkillLevel {
+    pub fn new(level: u8) -> Result<SkillLevel, ChessError> {
+        if !(MIN_SKILL_LEVEL..=MAX_SKILL_LEVEL).contains(&level) {
+            return Err(ChessError {
+                msg: format!("the skill level has to be between {} and {} but was {}", MIN_SKILL_LEVEL, MAX_SKILL_LEVEL, level),
+                kind: ErrorKind::IllegalConfig,
+            });
+        }
+        Ok(SkillLevel { level, opt_time_limit: None, contempt_in_centipawns: 0 })
+    }
+
+    pub fn strongest() -> SkillLevel {
+        SkillLevel { level: MAX_SKILL_LEVEL, opt_time_limit: None, contempt_in_centipawns: 0 }
+    }
+
+    /**
+     * the skill level closest to the given Elo rating
+     */
+    pub fn from_elo(elo: u32) -> SkillLevel {
+        let level = (elo.saturating_sub(ELO_BELOW_MIN_SKILL_LEVEL) + ELO_PER_SKILL_LEVEL / 2) / ELO_PER_SKILL_LEVEL;
+        SkillLevel { level: level.clamp(MIN_SKILL_LEVEL as u32, MAX_SKILL_LEVEL as u32) as u8, opt_time_limit: None, contempt_in_centipawns: 0 }
+    }
+
+    pub fn get_level(&self) -> u8 {
+        self.level
+    }
+
+    /**
+     * the approximate Elo rating of this level
+     */
+    pub fn get_elo(&self) -> u32 {
+        ELO_BELOW_MIN_SKILL_LEVEL + ELO_PER_SKILL_LEVEL * self.level as u32
+    }
+
+    pub fn get_pruner(&self) -> Pruner {
+        *PRUNERS_BY_STRENGTH[self.get_pruner_index()]
+    }
+
+    /**
+     * the numeric evaluation of every move is changed by a random value of up to this many pawns
+     */
+    pub fn get_eval_noise(&self) -> f32 {
+        (MAX_SKILL_LEVEL - self.level) as f32 * 0.1
+    }
+
+    /**
+     * the probability to play a random move (that doesn't lead into a checkmate) instead of the best one
+     */
+    pub fn get_blunder_probability(&self) -> f32 {
+        0.25 * (MAX_SKILL_LEVEL - self.level) as f32 / (MAX_SKILL_LEVEL - MIN_SKILL_LEVEL) as f32
+    }
+
+    /**
+     * no deeper search is started after the time is used up (the search that is running isn't interrupted,
+     * so finding a move can take longer). Since the depth then depends on the speed of the machine,
+     * MoveSelection::Seeded and Deterministic are only reproducible without a time limit.
+     */
+    pub fn with_time_limit(self, time_limit: Duration) -> SkillLevel {
+        SkillLevel {
+            opt_time_limit: Some(time_limit),
+            ..self
+        }
+    }
+
+    pub fn get_time_limit(&self) -> Option<Duration> {
+        self.opt_time_limit
+    }
+
+    /**
+     * like SearchConfig::with_contempt: how much (in centipawns) the engine avoids a draw
+     */
+    pub fn with_contempt(self, contempt_in_centipawns: i32) -> SkillLevel {
+        SkillLevel {
+            contempt_in_centipawns,
+            ..self
+        }
+    }
+
+    pub fn get_contempt(&self) -> i32 {
+        self.contempt_in_centipawns
+    }
+
+    /**
+     * a time limit that keeps the level responsive in an interactive game
+     */
+    pub fn get_suggested_time_limit(&self) -> Duration {
+        Duration::from_millis(500 + 250 * self.level as u64)
+    }
+
+    /**
+     * the steps of the iterative deepening: the pruners of all weaker levels up to the one of this level
+     */
+    fn get_deepening_steps(&self) -> Vec<Pruner> {
+        PRUNERS_BY_STRENGTH[..=self.get_pruner_index()].iter().map(|pruner| **pruner).collect()
+    }
+
+    fn get_pruner_index(&self) -> usize {
+        match self.level {
+            0..=3 => 0,
+            4..=6 => 1,
+            7..=10 => 2,
+            11..=15 => 3,
+            _ => 4,
+        }
+    }
+}
+
+impl str::FromStr for SkillLevel {
+    type Err = ChessError;
+
+    fn from_str(desc: &str) -> Result<Self, Self::Err> {
+        let level = desc.trim().parse::<u8>().map_err(|_| ChessError {
+            msg: format!("the skill level has to be a number between {} and {} but was {}", MIN_SKILL_LEVEL, MAX_SKILL_LEVEL, desc),
+            kind: ErrorKind::IllegalFormat,
+        })?;
+        SkillLevel::new(level)
+    }
+}
+
+impl fmt::Display for SkillLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.level)
+    }
+}
+
+/**
+ * like engine::evaluate, but the engine plays like a player of the given skill level:
+ * the search gets deeper step by step up to the pruner of the level (or until the optional time limit is used up),
+ * the evaluations get blurred by noise and sometimes a random move is played on purpose.
+ * Without a time limit the result only depends on the move selection, so Seeded and Deterministic are reproducible.
+ */
+pub fn evaluate_with_skill(game_config: &str, skill_level: SkillLevel, move_selection: MoveSelection) -> GameEvaluation {
+    let game = match init_game(game_config) {
+        OngoingGameOrEvaluation::Ongoing(game) => {game}
+        OngoingGameOrEvaluation::Ended(final_eval) => {return final_eval;}
+    };
+    evaluate_game_with_skill(&game, skill_level, move_selection)
+}
+
+/**
+ * like evaluate_with_skill, but for an already parsed game
+ */
+pub fn evaluate_game_with_skill(game: &Game, skill_level: SkillLevel, move_selection: MoveSelection) -> GameEvaluation {
+    let eval_type = get_eval_type_for(game, None);
+
+    let start = Instant::now();
+    let evaluations = deepen_root_moves(game, skill_level.get_deepening_steps(), eval_type, skill_level.get_contempt(), move_selection, || {
+        skill_level.get_time_limit().is_some_and(|time_limit| start.elapsed() >= time_limit)
+    });
+    let evaluated_moves = to_evaluated_moves(evaluations);
+
+    let mut random = match move_selection {
+        MoveSelection::Random => StdRng::from_entropy(),
+        MoveSelection::Seeded(seed) => StdRng::seed_from_u64(seed),
+        MoveSelection::Deterministic => StdRng::seed_from_u64(0),
+    };
+    let chosen_move = choose_move_for_skill(evaluated_moves, skill_level, move_selection, &mut random);
+    to_game_evaluation(game, chosen_move)
+}
+
+fn choose_move_for_skill<R: Rng>(
+    evaluated_moves: Vec<EvaluatedMove>,
+    skill_level: SkillLevel,
+    move_selection: MoveSelection,
+    random: &mut R,
+) -> EvaluatedMove {
+    if random.r#gen::<f32>() < skill_level.get_blunder_probability() {
+        let not_losing_moves: Vec<&EvaluatedMove> = evaluated_moves.iter()
+            .filter(|evaluated_move| !matches!(evaluated_move.evaluation, MoveEvaluation::EngineGetsCheckMatedIn(_, _)))
+            .collect();
+        if let Some(blunder) = not_losing_moves.choose(random) {
+            return **blunder;
+        }
+    }
+
+    let noise = skill_level.get_eval_noise();
+    let noisy_moves: Vec<EvaluatedMove> = evaluated_moves.iter().map(|evaluated_move| match evaluated_move.evaluation {
+        MoveEvaluation::Numeric(value) if noise > 0.0 => EvaluatedMove {
+            a_move: evaluated_move.a_move,
+            evaluation: MoveEvaluation::Numeric(value + random.gen_range(-noise..=noise)),
+        },
+        _ => *evaluated_move,
+    }).collect();
+    let chosen_move = match move_selection {
+        MoveSelection::Deterministic => *sort_best_first(noisy_moves, skill_level.get_contempt()).first().expect("there should be at least one move"),
+        _ => choose_next_move_with_rng(noisy_moves, skill_level.get_contempt(), random),
+    };
+    // the noise is only meant to influence the choice, not the reported evaluation
+    *evaluated_moves.iter()
+        .find(|evaluated_move| evaluated_move.a_move == chosen_move.a_move)
+        .expect("the chosen move is one of the evaluated moves")
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+    use crate::base::Move;
+    use crate::engine::evaluations::DrawReason;
+    use crate::engine::evaluations::frontend::GameEndResult;
+    use crate::engine::evaluations::testing::{GameEvaluationMatcher, MoveEvaluationMatcher};
+    use crate::engine::min_max::get_nodes_searched;
+
+    //♔♕♗♘♖♙♚♛♝♞♜♟
+
+    #[rstest(
+    level, is_valid,
+    case(0, false),
+    case(1, true),
+    case(20, true),
+    case(21, false),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_new(level: u8, is_valid: bool) {
+        assert_eq!(SkillLevel::new(level).is_ok(), is_valid);
+    }
+
+    #[rstest(
+    desc, expected_level,
+    case("1", Some(1)),
+    case(" 17 ", Some(17)),
+    case("21", None),
+    case("strong", None),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_from_str(desc: &str, expected_level: Option<u8>) {
+        let opt_level = desc.parse::<SkillLevel>().ok().map(|skill_level| skill_level.get_level());
+        assert_eq!(opt_level, expected_level);
+    }
+
+    #[rstest(
+    elo, expected_level,
+    case(0, 1),
+    case(800, 1),
+    case(1400, 11),
+    case(1425, 11),
+    case(1435, 12),
+    case(3000, 20),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_from_elo(elo: u32, expected_level: u8) {
+        assert_eq!(SkillLevel::from_elo(elo).get_level(), expected_level);
+    }
+
+    #[test]
+    fn test_levels_get_stronger() {
+        for level in MIN_SKILL_LEVEL..MAX_SKILL_LEVEL {
+            let skill_level = SkillLevel::new(level).unwrap();
+            let next_skill_level = SkillLevel::new(level + 1).unwrap();
+            assert_eq!(SkillLevel::from_elo(skill_level.get_elo()), skill_level);
+            assert!(skill_level.get_elo() < next_skill_level.get_elo());
+            assert!(skill_level.get_eval_noise() > next_skill_level.get_eval_noise());
+            assert!(skill_level.get_blunder_probability() > next_skill_level.get_blunder_probability());
+            assert!(skill_level.get_pruner_index() <= next_skill_level.get_pruner_index());
+        }
+        assert_eq!(SkillLevel::strongest().get_eval_noise(), 0.0);
+        assert_eq!(SkillLevel::strongest().get_blunder_probability(), 0.0);
+    }
+
+    #[rstest(
+    game_config, expected_matcher,
+    case("white ♔g3 ♖d2 ♚g1 ♙c2 ♙d3", GameEvaluationMatcher::MoveToPlay(Move::from_code("d2-d1"), MoveEvaluationMatcher::EngineCheckMatesIn(0))),
+    case("black ♔b6 ♙a7 ♚a8", GameEvaluationMatcher::GameEnded(GameEndResult::Draw(DrawReason::StaleMate))),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_strongest_level(game_config: &str, expected_matcher: GameEvaluationMatcher) {
+        let evaluation = evaluate_with_skill(game_config, SkillLevel::strongest(), MoveSelection::Random);
+        assert!(expected_matcher.matches(&evaluation), "evaluation: {:?}", evaluation);
+    }
+
+    #[rstest(
+    level,
+    case(1),
+    case(5),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_seeded_skill_is_reproducible(level: u8) {
+        let skill_level = SkillLevel::new(level).unwrap();
+        for seed in 0..5 {
+            let evaluation = evaluate_with_skill("e2-e4 e7-e5", skill_level, MoveSelection::Seeded(seed));
+            assert_eq!(evaluate_with_skill("e2-e4 e7-e5", skill_level, MoveSelection::Seeded(seed)), evaluation, "seed: {}", seed);
+        }
+    }
+
+    #[rstest(
+    level,
+    case(5),
+    case(12),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_deterministic_skill_is_reproducible(level: u8) {
+        let skill_level = SkillLevel::new(level).unwrap();
+        assert_eq!(skill_level.get_time_limit(), None);
+        let count_nodes_and_evaluate = || {
+            let nodes_before = get_nodes_searched();
+            let evaluation = evaluate_with_skill("e2-e4 e7-e5 g1-f3", skill_level, MoveSelection::Deterministic);
+            (get_nodes_searched() - nodes_before, evaluation)
+        };
+        // the same depth is searched every time
+        assert_eq!(count_nodes_and_evaluate(), count_nodes_and_evaluate());
+    }
+
+    #[test]
+    fn test_time_limit_stops_deepening() {
+        let skill_level = SkillLevel::new(12).unwrap();
+        let count_nodes = |skill_level: SkillLevel| {
+            let nodes_before = get_nodes_searched();
+            evaluate_with_skill("e2-e4 e7-e5 g1-f3", skill_level, MoveSelection::Deterministic);
+            get_nodes_searched() - nodes_before
+        };
+        let time_limited_skill_level = skill_level.with_time_limit(Duration::ZERO);
+        assert_eq!(time_limited_skill_level.get_time_limit(), Some(Duration::ZERO));
+        assert!(count_nodes(time_limited_skill_level) < count_nodes(skill_level));
+    }
+
+    #[rstest(
+    contempt, expected_to_choose_draw,
+    case(0, true),
+    case(100, false),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_skill_level_with_contempt(contempt: i32, expected_to_choose_draw: bool) {
+        let skill_level = SkillLevel::strongest().with_contempt(contempt);
+        assert_eq!(skill_level.get_contempt(), contempt);
+        let evaluated_moves = vec![
+            EvaluatedMove { a_move: Move::from_code("a2-a3"), evaluation: MoveEvaluation::Numeric(-0.3) },
+            EvaluatedMove { a_move: Move::from_code("b2-b3"), evaluation: MoveEvaluation::Draw(DrawReason::ThreeTimesRepetition) },
+        ];
+        for move_selection in [MoveSelection::Deterministic, MoveSelection::Seeded(3)] {
+            let chosen_move = choose_move_for_skill(evaluated_moves.clone(), skill_level, move_selection, &mut StdRng::seed_from_u64(0));
+            assert_eq!(chosen_move.evaluation == MoveEvaluation::Draw(DrawReason::ThreeTimesRepetition), expected_to_choose_draw, "move selection: {:?}", move_selection);
+        }
+    }
+
+    #[test]
+    fn test_weakest_level_varies_its_moves() {
+        let weakest = SkillLevel::new(MIN_SKILL_LEVEL).unwrap();
+        let mut chosen_moves: Vec<Move> = (0..20).filter_map(|seed| match evaluate_with_skill("", weakest, MoveSelection::Seeded(seed)) {
+            GameEvaluation::MoveToPlay(a_move, _) => Some(a_move),
+            _ => None,
+        }).collect();
+        chosen_moves.dedup();
+        assert!(chosen_moves.len() > 1, "chosen moves: {:?}", chosen_moves);
+    }
+}
@@ -1,31 +1,34 @@
 use crate::base::{Color, Direction, Position};
 use crate::figure::FigureType;
 use crate::game::{FiguresWithPosArray, GameState};
+use crate::engine::static_eval::EvalParams;
+use crate::engine::endgame::kpk::{probe_kpk_figures, KpkResult};
 
-const VALUE_OF_AREA: f32 = 0.015;
-
-pub fn default_static_eval_for_white(game_state: &GameState) -> f32 {
+pub fn default_static_eval_for_white(game_state: &GameState, params: &EvalParams) -> f32 {
     let (white_figures, black_figures) = game_state.board.get_white_and_black_figures();
-    let white_value = get_value(game_state, white_figures, Color::White);
-    let black_value = get_value(game_state, black_figures, Color::Black);
+    if let Some(kpk_result) = probe_kpk_figures(&white_figures, &black_figures, game_state.turn_by) {
+        return get_kpk_value_for_white(kpk_result, &white_figures, &black_figures, params);
+    }
+    let white_value = get_value(game_state, white_figures, Color::White, params);
+    let black_value = get_value(game_state, black_figures, Color::Black, params);
 
     let rules_area_diff_for_white = game_state.count_reachable_moves_diff_for_white();
 
-    (white_value - black_value) + (rules_area_diff_for_white as f32 * VALUE_OF_AREA)
+    (white_value - black_value) + (rules_area_diff_for_white as f32 * params.value_of_area)
 }
 
-fn get_value(game_state: &GameState, figures: FiguresWithPosArray, color: Color) -> f32 {
+fn get_value(game_state: &GameState, figures: FiguresWithPosArray, color: Color, params: &EvalParams) -> f32 {
     let mut value = 0.0;
     let (backward_left, _, backward_right) = Direction::forward_directions(color.toggle());
     for opt_fig_data in figures.iter() {
         match opt_fig_data  {
             Some((fig_type, pos)) => {
                 let fig_value = match fig_type {
-                    FigureType::Pawn => get_pawn_value(game_state, *pos, color, backward_left, backward_right),
-                    FigureType::Rook => 5.0,
-                    FigureType::Knight => 3.0,
-                    FigureType::Bishop => 3.01,
-                    FigureType::Queen => 9.0,
+                    FigureType::Pawn => get_pawn_value(game_state, *pos, color, backward_left, backward_right, params),
+                    FigureType::Rook => params.rook_value,
+                    FigureType::Knight => params.knight_value,
+                    FigureType::Bishop => params.bishop_value,
+                    FigureType::Queen => params.queen_value,
                     FigureType::King => 0.0,
                 };
                 value += fig_value;
@@ -44,6 +47,7 @@ fn get_pawn_value(
     color: Color,
     backward_left: Direction,
     backward_right: Direction,
+    params: &EvalParams,
 ) -> f32 {
     fn is_protected(
         game_state: &GameState,
@@ -72,10 +76,36 @@ fn get_pawn_value(
         Color::Black => 6 - pawn_pos.row(),
     } as f32;
     let steps_value = if is_protected(game_state, pawn_pos, color, backward_left, backward_right) {
-        0.2
+        params.protected_pawn_step_value
     } else {
-        0.15
+        params.unprotected_pawn_step_value
     };
 
-    1.0 + (steps_value * steps_taken)
-}
\ No newline at end of file
+    params.pawn_base_value + (steps_value * steps_taken)
+}
+
+/**
+ * a won king and pawn versus king endgame is worth almost a queen (the closer the pawn is to its promotion the more),
+ * a drawn one nothing at all.
+ */
+fn get_kpk_value_for_white(
+    kpk_result: KpkResult,
+    white_figures: &FiguresWithPosArray,
+    black_figures: &FiguresWithPosArray,
+    params: &EvalParams,
+) -> f32 {
+    let winning_color = match kpk_result {
+        KpkResult::Draw => { return 0.0; }
+        KpkResult::Win(color) => color,
+    };
+    let winning_figures = if winning_color == Color::White { white_figures } else { black_figures };
+    let rows_to_promotion = winning_figures.iter().flatten()
+        .find(|(fig_type, _)| *fig_type == FigureType::Pawn)
+        .map(|(_, pawn_pos)| match winning_color {
+            Color::White => 7 - pawn_pos.row(),
+            Color::Black => pawn_pos.row(),
+        })
+        .unwrap_or(0) as f32;
+    let won_value = params.queen_value - (rows_to_promotion * params.unprotected_pawn_step_value);
+    if winning_color == Color::White { won_value } else { -won_value }
+}
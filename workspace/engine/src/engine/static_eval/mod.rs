@@ -3,15 +3,75 @@ use crate::base::Color;
 use crate::game::GameState;
 
 mod default;
+mod params;
+
+pub use params::*;
 
 #[derive(Debug, Copy, Clone)]
 pub enum StaticEvalType {
     Default,
+    Parameterised(EvalParams),
 }
 
 pub fn static_eval(game_state: &GameState, eval_type: StaticEvalType, for_color: Color) -> f32 {
     let eval_for_white = match eval_type {
-        StaticEvalType::Default => default_static_eval_for_white(game_state),
+        StaticEvalType::Default => default_static_eval_for_white(game_state, &DEFAULT_EVAL_PARAMS),
+        StaticEvalType::Parameterised(params) => default_static_eval_for_white(game_state, &params),
     };
     if for_color==Color::White {eval_for_white} else {-eval_for_white}
-}
\ No newline at end of file
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    //♔♕♗♘♖♙♚♛♝♞♜♟
+
+    #[rstest(
+    game_config,
+    case(""),
+    case("e2-e4 d7-d5 e4-d5"),
+    case("white ♔e1 ♕d1 ♙a2 ♚e8 ♜a8 ♝c8"),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_default_params_match_default_eval(game_config: &str) {
+        let game_state = game_config.parse::<GameState>().unwrap();
+        let default_eval = static_eval(&game_state, StaticEvalType::Default, Color::White);
+        let parameterised_eval = static_eval(&game_state, StaticEvalType::Parameterised(EvalParams::default()), Color::White);
+        assert_eq!(default_eval, parameterised_eval);
+    }
+
+    #[test]
+    fn test_params_change_eval() {
+        let game_state = "white ♔e1 ♕d1 ♚e8 ♜a8".parse::<GameState>().unwrap();
+        let queen_heavy_params = EvalParams {
+            queen_value: 12.0,
+            ..DEFAULT_EVAL_PARAMS
+        };
+        let default_eval = static_eval(&game_state, StaticEvalType::Default, Color::White);
+        let queen_heavy_eval = static_eval(&game_state, StaticEvalType::Parameterised(queen_heavy_params), Color::White);
+        assert_eq!(queen_heavy_eval - default_eval, 3.0);
+    }
+
+    #[rstest(
+    game_config, for_color, expected_min_eval, expected_max_eval,
+    case("white ♔a6 ♙a5 ♚a8", Color::White, 0.0, 0.0), // drawn king and pawn versus king
+    case("white ♔e6 ♙e5 ♚e8", Color::White, 8.0, 9.0),
+    case("white ♔e6 ♙e5 ♚e8", Color::Black, -9.0, -8.0),
+    case("black ♔e1 ♚e3 ♟e4", Color::Black, 8.0, 9.0),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_kpk_eval(
+        game_config: &str,
+        for_color: Color,
+        expected_min_eval: f32,
+        expected_max_eval: f32,
+    ) {
+        let game_state = game_config.parse::<GameState>().unwrap();
+        let eval = static_eval(&game_state, StaticEvalType::Default, for_color);
+        assert!(expected_min_eval <= eval && eval <= expected_max_eval, "eval: {}", eval);
+    }
+}
@@ -0,0 +1,178 @@
+use std::fs;
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+use crate::base::{ChessError, ErrorKind};
+
+pub const DEFAULT_EVAL_PARAMS: EvalParams = EvalParams {
+    value_of_area: 0.015,
+    pawn_base_value: 1.0,
+    protected_pawn_step_value: 0.2,
+    unprotected_pawn_step_value: 0.15,
+    rook_value: 5.0,
+    knight_value: 3.0,
+    bishop_value: 3.01,
+    queen_value: 9.0,
+};
+
+pub const NR_OF_EVAL_PARAMS: usize = 8;
+
+/**
+ * The weights used by the default static evaluation.
+ * Fields missing in a json/toml config fall back to the values of DEFAULT_EVAL_PARAMS,
+ * so a personality only has to list the weights it wants to change.
+ */
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EvalParams {
+    pub value_of_area: f32,
+    pub pawn_base_value: f32,
+    pub protected_pawn_step_value: f32,
+    pub unprotected_pawn_step_value: f32,
+    pub rook_value: f32,
+    pub knight_value: f32,
+    pub bishop_value: f32,
+    pub queen_value: f32,
+}
+
+impl Default for EvalParams {
+    fn default() -> Self {
+        DEFAULT_EVAL_PARAMS
+    }
+}
+
+impl EvalParams {
+    pub fn from_json(json: &str) -> Result<EvalParams, ChessError> {
+        serde_json::from_str::<EvalParams>(json).map_err(|err| ChessError {
+            msg: format!("couldn't parse eval params from json: {}", err),
+            kind: ErrorKind::IllegalFormat,
+        })
+    }
+
+    pub fn from_toml(toml: &str) -> Result<EvalParams, ChessError> {
+        toml::from_str::<EvalParams>(toml).map_err(|err| ChessError {
+            msg: format!("couldn't parse eval params from toml: {}", err),
+            kind: ErrorKind::IllegalFormat,
+        })
+    }
+
+    /**
+     * files ending in ".toml" are parsed as toml, everything else as json.
+     */
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<EvalParams, ChessError> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path).map_err(|err| ChessError {
+            msg: format!("couldn't read eval params file {}: {}", path.display(), err),
+            kind: ErrorKind::IllegalConfig,
+        })?;
+        let is_toml = path.extension().is_some_and(|extension| extension == "toml");
+        if is_toml {
+            EvalParams::from_toml(content.as_str())
+        } else {
+            EvalParams::from_json(content.as_str())
+        }
+    }
+
+    /**
+     * the weights in declaration order, e.g. for tuning them generically
+     */
+    pub fn to_array(&self) -> [f32; NR_OF_EVAL_PARAMS] {
+        [
+            self.value_of_area,
+            self.pawn_base_value,
+            self.protected_pawn_step_value,
+            self.unprotected_pawn_step_value,
+            self.rook_value,
+            self.knight_value,
+            self.bishop_value,
+            self.queen_value,
+        ]
+    }
+
+    pub fn from_array(values: [f32; NR_OF_EVAL_PARAMS]) -> EvalParams {
+        EvalParams {
+            value_of_area: values[0],
+            pawn_base_value: values[1],
+            protected_pawn_step_value: values[2],
+            unprotected_pawn_step_value: values[3],
+            rook_value: values[4],
+            knight_value: values[5],
+            bishop_value: values[6],
+            queen_value: values[7],
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("EvalParams only consists of floats")
+    }
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[test]
+    fn test_json_round_trip() {
+        let params = EvalParams {
+            queen_value: 9.5,
+            ..DEFAULT_EVAL_PARAMS
+        };
+        let parsed_params = EvalParams::from_json(params.to_json().as_str()).unwrap();
+        assert_eq!(parsed_params, params);
+    }
+
+    #[test]
+    fn test_array_round_trip() {
+        let params = EvalParams {
+            value_of_area: 0.5,
+            knight_value: 3.5,
+            ..DEFAULT_EVAL_PARAMS
+        };
+        assert_eq!(EvalParams::from_array(params.to_array()), params);
+    }
+
+    #[rstest(
+    config, expected_queen_value, expected_rook_value,
+    case("{}", 9.0, 5.0),
+    case("{\"queen_value\": 8.5}", 8.5, 5.0),
+    case("{\"queen_value\": 8.5, \"rook_value\": 4.75}", 8.5, 4.75),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_from_json_falls_back_to_defaults(
+        config: &str,
+        expected_queen_value: f32,
+        expected_rook_value: f32,
+    ) {
+        let params = EvalParams::from_json(config).unwrap();
+        assert_eq!(params.queen_value, expected_queen_value, "queen value");
+        assert_eq!(params.rook_value, expected_rook_value, "rook value");
+        assert_eq!(params.knight_value, DEFAULT_EVAL_PARAMS.knight_value, "knight value");
+    }
+
+    #[test]
+    fn test_from_toml() {
+        let params = EvalParams::from_toml("value_of_area = 0.02\nbishop_value = 3.25\n").unwrap();
+        assert_eq!(params.value_of_area, 0.02);
+        assert_eq!(params.bishop_value, 3.25);
+        assert_eq!(params.pawn_base_value, DEFAULT_EVAL_PARAMS.pawn_base_value);
+    }
+
+    #[rstest(
+    config,
+    case("{\"queen_value\": \"nine\"}"),
+    case("queen_value = 9.0"),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_from_json_fails_on_illegal_input(config: &str) {
+        let err = EvalParams::from_json(config).unwrap_err();
+        assert!(matches!(err.kind, ErrorKind::IllegalFormat), "error kind: {:?}", err.kind);
+    }
+
+    #[test]
+    fn test_from_file_fails_on_missing_file() {
+        let err = EvalParams::from_file("this/file/does/not/exist.json").unwrap_err();
+        assert!(matches!(err.kind, ErrorKind::IllegalConfig), "error kind: {:?}", err.kind);
+    }
+}
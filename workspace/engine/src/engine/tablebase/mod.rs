@@ -0,0 +1,265 @@
+use std::env;
+use std::sync::RwLock;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use shakmaty::{CastlingMode, Chess, PositionError, Role};
+use shakmaty::fen::Fen;
+use shakmaty_syzygy::{AmbiguousWdl, Tablebase};
+use crate::base::{find_reachable_move, ChessError, Color, ErrorKind, Move, Position, PromotionType};
+use crate::engine::evaluations::{DrawReason, Evaluation};
+use crate::engine::static_eval::{static_eval, StaticEvalType};
+use crate::game::{Game, MoveResult, USIZE_RANGE_063};
+
+static TABLEBASE: RwLock<Option<Tablebase<Chess>>> = RwLock::new(None);
+// 0 as long as no tablebase is configured, so positions can be skipped without taking the lock
+static MAX_PIECES: AtomicUsize = AtomicUsize::new(0);
+
+/**
+ * loads the Syzygy WDL/DTZ files of the given directory. Like the SyzygyPath option of UCI engines
+ * several directories can be given separated by the platform's path separator (':' or ';').
+ * Returns the number of tables found, an error if there wasn't a single one.
+ */
+pub fn set_tablebase_path(path: &str) -> Result<usize, ChessError> {
+    let mut tablebase: Tablebase<Chess> = Tablebase::new();
+    let mut nr_of_tables = 0;
+    for directory in env::split_paths(path) {
+        nr_of_tables += tablebase.add_directory(&directory).map_err(|err| ChessError {
+            msg: format!("couldn't read tablebase directory {}: {}", directory.display(), err),
+            kind: ErrorKind::IllegalConfig,
+        })?;
+    }
+    if nr_of_tables == 0 {
+        return Err(ChessError {
+            msg: format!("no syzygy tables found in {}", path),
+            kind: ErrorKind::IllegalConfig,
+        });
+    }
+
+    let max_pieces = tablebase.max_pieces();
+    *TABLEBASE.write().unwrap() = Some(tablebase);
+    MAX_PIECES.store(max_pieces, Ordering::Relaxed);
+    Ok(nr_of_tables)
+}
+
+pub fn clear_tablebase() {
+    MAX_PIECES.store(0, Ordering::Relaxed);
+    *TABLEBASE.write().unwrap() = None;
+}
+
+/**
+ * the maximal number of figures (kings included) of the configured tablebase, 0 if there is none
+ */
+pub fn get_tablebase_max_pieces() -> usize {
+    MAX_PIECES.load(Ordering::Relaxed)
+}
+
+/**
+ * the evaluation of the game's current position during the search, if it's in the tablebase.
+ * half_step is the number of half moves since the start of the search.
+ * Only the cheap WDL table is probed, so a win or loss is evaluated as the longest one possible.
+ */
+pub(crate) fn probe_position(game: &Game, half_step: usize, evaluate_for: Color, eval_type: StaticEvalType) -> Option<Evaluation> {
+    with_tablebase_position(game, |tablebase, position| {
+        let wdl = tablebase.probe_wdl(position).ok()?;
+        let is_evaluate_for_active = game.get_game_state().turn_by == evaluate_for;
+        Some(to_evaluation(wdl, None, half_step, is_evaluate_for_active, || static_eval(game.get_game_state(), eval_type, evaluate_for)))
+    })
+}
+
+/**
+ * the evaluation of the position after a root move (played by the active color), if it's in the tablebase.
+ * Contrary to the probes within the search the DTZ table is used to tell shorter wins from longer ones.
+ */
+pub(crate) fn probe_move(game: &Game, a_move: Move, eval_type: StaticEvalType) -> Option<Evaluation> {
+    if get_tablebase_max_pieces() == 0 {
+        return None;
+    }
+    let game_after_move = match game.play(a_move) {
+        MoveResult::Ongoing(game_after_move, _) => game_after_move,
+        MoveResult::Stopped(_, _) => { return None; }
+    };
+    with_tablebase_position(&game_after_move, |tablebase, position| {
+        let wdl = tablebase.probe_wdl(position).ok()?;
+        let dtz = tablebase.probe_dtz(position).ok()?.ignore_rounding().0;
+        let evaluate_for = game.get_game_state().turn_by;
+        Some(to_evaluation(wdl, Some(dtz), 1, false, || static_eval(game_after_move.get_game_state(), eval_type, evaluate_for)))
+    })
+}
+
+/**
+ * the move recommended by the tablebase for the active color together with the evaluation of the current position.
+ * Returns None if the position isn't in the tablebase or the move is an under-promotion this engine doesn't generate.
+ */
+pub(crate) fn get_tablebase_move(game: &Game, eval_type: StaticEvalType) -> Option<(Move, Evaluation)> {
+    with_tablebase_position(game, |tablebase, position| {
+        let (tablebase_move, _) = tablebase.best_move(position).ok()??;
+        let from = Position::from_index_unchecked(tablebase_move.from()?.to_usize());
+        let to = Position::from_index_unchecked(tablebase_move.to().to_usize());
+        let opt_promotion_type = match tablebase_move.promotion() {
+            None => None,
+            Some(Role::Queen) => Some(PromotionType::Queen),
+            Some(Role::Rook) => Some(PromotionType::Rook),
+            Some(Role::Bishop) => Some(PromotionType::Bishop),
+            Some(Role::Knight) => Some(PromotionType::Knight),
+            Some(_) => { return None; }
+        };
+        let a_move = find_reachable_move(game.get_reachable_moves(), from, to, opt_promotion_type)?;
+
+        let wdl = tablebase.probe_wdl(position).ok()?;
+        let dtz = tablebase.probe_dtz(position).ok()?.ignore_rounding().0;
+        let evaluate_for = game.get_game_state().turn_by;
+        let evaluation = to_evaluation(wdl, Some(dtz), 0, true, || static_eval(game.get_game_state(), eval_type, evaluate_for));
+        Some((a_move, evaluation))
+    })
+}
+
+fn with_tablebase_position<T, F>(game: &Game, probe: F) -> Option<T>
+where F: FnOnce(&Tablebase<Chess>, &Chess) -> Option<T> {
+    let max_pieces = get_tablebase_max_pieces();
+    if max_pieces == 0 {
+        return None;
+    }
+    let game_state = game.get_game_state();
+    let nr_of_figures = USIZE_RANGE_063
+        .filter(|state_index| !game_state.board.is_empty(Position::from_index_unchecked(*state_index)))
+        .count();
+    if nr_of_figures > max_pieces {
+        return None;
+    }
+    // tablebases don't contain positions with castling rights
+    if game_state.is_white_king_side_castling_still_possible.get_value()
+        || game_state.is_white_queen_side_castling_still_possible.get_value()
+        || game_state.is_black_king_side_castling_still_possible.get_value()
+        || game_state.is_black_queen_side_castling_still_possible.get_value() {
+        return None;
+    }
+
+    let position = to_shakmaty_position(game)?;
+    let tablebase_guard = TABLEBASE.read().unwrap();
+    let tablebase = tablebase_guard.as_ref()?;
+    probe(tablebase, &position)
+}
+
+fn to_shakmaty_position(game: &Game) -> Option<Chess> {
+    let fen = Fen::from_ascii(game.get_fen().as_bytes()).ok()?;
+    fen.into_position::<Chess>(CastlingMode::Standard)
+        .or_else(PositionError::ignore_invalid_ep_square)
+        .ok()
+}
+
+const MAX_WIN_IN: usize = 254;
+const MAX_LOSE_IN: usize = 255;
+
+/**
+ * Syzygy tables don't know the distance to mate, so the distance to the next capture or pawn move (dtz)
+ * is used instead. The resulting WinIn/LoseIn keep the parity of the ones found by the search
+ * (even for WinIn, odd for LoseIn) and a checkmate gets the same evaluation as in the search.
+ * Without a dtz the win or loss is evaluated as the longest one possible.
+ */
+fn to_evaluation<F>(
+    wdl: AmbiguousWdl,
+    opt_dtz: Option<i32>,
+    half_step: usize,
+    is_evaluate_for_active: bool,
+    get_static_eval: F,
+) -> Evaluation where F: FnOnce() -> f32 {
+    let dtz_plies = opt_dtz.map_or(MAX_LOSE_IN, |dtz| (dtz.unsigned_abs() as usize).max(1));
+    let does_active_color_win = match wdl {
+        AmbiguousWdl::Win | AmbiguousWdl::MaybeWin => true,
+        AmbiguousWdl::Loss | AmbiguousWdl::MaybeLoss => false,
+        AmbiguousWdl::Draw => { return Evaluation::Draw(DrawReason::EndgameTablebase); }
+        AmbiguousWdl::CursedWin | AmbiguousWdl::BlessedLoss => { return Evaluation::Draw(DrawReason::NoChangeIn50Moves); }
+    };
+    // the half step after which the losing color is to move and gets mated (at the earliest)
+    let decisive_half_step = if does_active_color_win {
+        half_step + dtz_plies
+    } else {
+        half_step + dtz_plies - 1
+    };
+
+    if does_active_color_win == is_evaluate_for_active {
+        let win_in = round_up_to_parity(decisive_half_step + 1, 0).min(MAX_WIN_IN);
+        Evaluation::WinIn(win_in as u8)
+    } else {
+        let lose_in = round_up_to_parity(decisive_half_step + 1, 1).min(MAX_LOSE_IN);
+        Evaluation::LoseIn(lose_in as u8, get_static_eval())
+    }
+}
+
+fn round_up_to_parity(half_step: usize, parity: usize) -> usize {
+    if half_step % 2 == parity {
+        half_step
+    } else {
+        half_step + 1
+    }
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest(
+    wdl, opt_dtz, half_step, is_evaluate_for_active, expected_evaluation,
+    case(AmbiguousWdl::Win, Some(1), 0, true, Evaluation::WinIn(2)), // like a checkmate played in the first half step
+    case(AmbiguousWdl::Loss, Some(-1), 1, false, Evaluation::WinIn(2)), // the opponent is checkmate
+    case(AmbiguousWdl::Loss, Some(-1), 2, true, Evaluation::LoseIn(3, 0.5)),
+    case(AmbiguousWdl::Win, Some(1), 1, false, Evaluation::LoseIn(3, 0.5)),
+    case(AmbiguousWdl::Win, Some(12), 2, true, Evaluation::WinIn(16)),
+    case(AmbiguousWdl::MaybeWin, Some(11), 2, true, Evaluation::WinIn(14)),
+    case(AmbiguousWdl::Loss, Some(-12), 3, false, Evaluation::WinIn(16)),
+    case(AmbiguousWdl::Win, Some(900), 1, true, Evaluation::WinIn(254)),
+    case(AmbiguousWdl::MaybeLoss, Some(-900), 1, true, Evaluation::LoseIn(255, 0.5)),
+    case(AmbiguousWdl::Draw, Some(0), 3, true, Evaluation::Draw(DrawReason::EndgameTablebase)),
+    case(AmbiguousWdl::CursedWin, Some(101), 3, true, Evaluation::Draw(DrawReason::NoChangeIn50Moves)),
+    case(AmbiguousWdl::BlessedLoss, Some(-101), 3, false, Evaluation::Draw(DrawReason::NoChangeIn50Moves)),
+    // without a dtz (within the search) the win or loss is the longest one possible
+    case(AmbiguousWdl::Win, None, 2, true, Evaluation::WinIn(254)),
+    case(AmbiguousWdl::Loss, None, 3, false, Evaluation::WinIn(254)),
+    case(AmbiguousWdl::Loss, None, 2, true, Evaluation::LoseIn(255, 0.5)),
+    case(AmbiguousWdl::Draw, None, 3, true, Evaluation::Draw(DrawReason::EndgameTablebase)),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_to_evaluation(
+        wdl: AmbiguousWdl,
+        opt_dtz: Option<i32>,
+        half_step: usize,
+        is_evaluate_for_active: bool,
+        expected_evaluation: Evaluation,
+    ) {
+        let actual_evaluation = to_evaluation(wdl, opt_dtz, half_step, is_evaluate_for_active, || 0.5);
+        assert_eq!(actual_evaluation, expected_evaluation);
+    }
+
+    #[rstest(
+    game_config,
+    case("white ♔b6 ♙a7 ♚a8"),
+    case("black ♔g3 ♖d1 ♚g1"),
+    case("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 40"),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_to_shakmaty_position(game_config: &str) {
+        let game = game_config.parse::<Game>().unwrap();
+        assert!(to_shakmaty_position(&game).is_some(), "fen: {}", game.get_fen());
+    }
+
+    #[rstest(
+    path,
+    case("this/directory/does/not/exist"),
+    case(env!("CARGO_MANIFEST_DIR")), // exists but doesn't contain any tables
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_set_tablebase_path_fails(path: &str) {
+        let err = set_tablebase_path(path).unwrap_err();
+        assert!(matches!(err.kind, ErrorKind::IllegalConfig), "error kind: {:?}", err.kind);
+    }
+
+    #[test]
+    fn test_probe_without_tablebase() {
+        let game = "white ♔b6 ♙a7 ♚a8".parse::<Game>().unwrap();
+        assert_eq!(probe_position(&game, 0, Color::White, StaticEvalType::Default), None);
+        assert_eq!(probe_move(&game, Move::from_code("a7Qa8"), StaticEvalType::Default), None);
+        assert!(get_tablebase_move(&game, StaticEvalType::Default).is_none());
+    }
+}
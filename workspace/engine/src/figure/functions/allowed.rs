@@ -2,31 +2,40 @@ use crate::base::{Move, MoveType, PromotionType};
 use crate::game::{Game, MoveResult, StoppedReason};
 
 pub fn get_allowed_moves(game_config: &str) -> Vec<Move> {
-    let mut movable_moves: Vec<Move> = vec!();
-    let game = match game_config.parse::<Game>() {
-        Err(_) => {
-            return movable_moves;
-        }
-        Ok(game) => {game}
-    };
+    match game_config.parse::<Game>() {
+        Err(_) => vec!(),
+        Ok(game) => get_allowed_moves_of(&game),
+    }
+}
 
-    game.get_reachable_moves().iter().filter(
-        |&&a_move| is_not_bound(&game, a_move)
-    ).filter(
-        |&&a_move| {
+/**
+ * the legal moves of the active color, of the promotions only the one to a queen
+ */
+pub fn get_allowed_moves_of(game: &Game) -> Vec<Move> {
+    let mut movable_moves: Vec<Move> = get_legal_moves_of(game);
+    movable_moves.retain(
+        |&a_move| {
             if let MoveType::PawnPromotion(promo_type) = a_move.move_type() {
                 promo_type==PromotionType::Queen
             } else {
                 true
             }
         }
-    ).for_each(
-        |&a_move| movable_moves.push(a_move)
     );
 
     movable_moves
 }
 
+/**
+ * the legal moves of the active color including the promotions to a knight
+ * (the move generation only produces promotions to a queen or a knight, the others are never better)
+ */
+pub fn get_legal_moves_of(game: &Game) -> Vec<Move> {
+    game.get_reachable_moves().iter().filter(
+        |&&a_move| is_not_bound(game, a_move)
+    ).copied().collect()
+}
+
 fn is_not_bound(game: &Game, a_move: Move) -> bool {
     match game.play(a_move) {
         MoveResult::Ongoing(_, _) => {true}
@@ -2,5 +2,6 @@ pub(crate) mod check_search;
 pub(crate) mod checkmate;
 pub(crate) mod reachable;
 pub(crate) mod count_reachable;
+pub(crate) mod static_exchange;
 mod castling;
 pub mod allowed;
@@ -0,0 +1,276 @@
+use crate::base::{Color, Direction, Move, MoveType, Position, ALL_DIRECTIONS};
+use crate::figure::FigureType;
+use crate::game::{Board, USIZE_RANGE_063};
+
+/**
+ * the material values (in centipawns) used by the static exchange evaluation.
+ * The king's value is only relevant for the ordering of attackers, it can't be captured.
+ */
+pub const fn exchange_value(fig_type: FigureType) -> i32 {
+    match fig_type {
+        FigureType::Pawn => 100,
+        FigureType::Knight => 300,
+        FigureType::Bishop => 300,
+        FigureType::Rook => 500,
+        FigureType::Queen => 900,
+        FigureType::King => 10000,
+    }
+}
+
+/**
+ * returns the material (in centipawns) attacker_color can win by starting a capture sequence on target.
+ * Since the attacker can always choose not to capture, the result is never negative.
+ * Pins aren't taken into account and pawns capturing on the last row aren't promoted.
+ */
+pub fn static_exchange_eval(target: Position, attacker_color: Color, board: &Board) -> i32 {
+    let victim = match board.get_figure(target) {
+        None => { return 0; }
+        Some(figure) => {
+            if figure.color == attacker_color {
+                return 0;
+            }
+            figure
+        }
+    };
+    let mut attackers = ExchangeAttackers::new(target, board);
+    match attackers.pop_least_valuable(attacker_color) {
+        None => 0,
+        Some(first_attacker_type) => {
+            exchange(exchange_value(victim.fig_type), first_attacker_type, attacker_color, &mut attackers).max(0)
+        }
+    }
+}
+
+/**
+ * returns the material balance (in centipawns) of the given capture followed by the best recaptures.
+ * Contrary to static_exchange_eval the first capture is forced, so the result can be negative.
+ * A move that doesn't capture (or doesn't start on a figure) is evaluated with 0.
+ */
+pub fn static_exchange_eval_of_capture(capture: Move, board: &Board) -> i32 {
+    let capturing_figure = match board.get_figure(capture.from()) {
+        None => { return 0; }
+        Some(figure) => figure,
+    };
+    let captured_value = if capture.move_type() == MoveType::EnPassant {
+        exchange_value(FigureType::Pawn)
+    } else {
+        match board.get_figure(capture.to()) {
+            None => { return 0; }
+            Some(figure) => exchange_value(figure.fig_type),
+        }
+    };
+    let mut attackers = ExchangeAttackers::new(capture.to(), board);
+    attackers.remove(capturing_figure.color, capture.from());
+    exchange(captured_value, capturing_figure.fig_type, capturing_figure.color, &mut attackers)
+}
+
+/**
+ * the classic swap algorithm: the gains of the capture sequence are collected
+ * and then folded back from the end since every side can stop capturing when it would lose material.
+ */
+fn exchange(
+    first_victim_value: i32,
+    first_attacker_type: FigureType,
+    first_attacker_color: Color,
+    attackers: &mut ExchangeAttackers,
+) -> i32 {
+    let mut gains: Vec<i32> = vec![first_victim_value];
+    let mut figure_on_target_value = exchange_value(first_attacker_type);
+    let mut color_to_capture = first_attacker_color.toggle();
+
+    loop {
+        let next_attacker_type = match attackers.pop_least_valuable(color_to_capture) {
+            None => { break; }
+            Some(fig_type) => fig_type,
+        };
+        if next_attacker_type == FigureType::King && attackers.has_attacker(color_to_capture.toggle()) {
+            // the king can't capture a defended figure
+            break;
+        }
+        let previous_gain = *gains.last().unwrap();
+        gains.push(figure_on_target_value - previous_gain);
+        figure_on_target_value = exchange_value(next_attacker_type);
+        color_to_capture = color_to_capture.toggle();
+    }
+
+    while gains.len() > 1 {
+        let last_gain = gains.pop().unwrap();
+        let previous_gain = gains.last_mut().unwrap();
+        *previous_gain = -((-*previous_gain).max(last_gain));
+    }
+    gains[0]
+}
+
+/**
+ * all figures that attack the target position, including the sliders that only become attackers
+ * once the figure in front of them has captured (x-ray attacks).
+ */
+struct ExchangeAttackers<'a> {
+    target: Position,
+    board: &'a Board,
+    white_attackers: Vec<(FigureType, Position)>,
+    black_attackers: Vec<(FigureType, Position)>,
+}
+
+impl ExchangeAttackers<'_> {
+    fn new(target: Position, board: &Board) -> ExchangeAttackers {
+        let mut attackers = ExchangeAttackers {
+            target,
+            board,
+            white_attackers: vec![],
+            black_attackers: vec![],
+        };
+
+        for direction in ALL_DIRECTIONS.iter() {
+            attackers.add_first_attacker_in_direction(target, *direction, true);
+        }
+        for state_index in USIZE_RANGE_063 {
+            let pos = Position::from_index_unchecked(state_index);
+            if let Some(figure) = board.get_figure(pos) {
+                if figure.fig_type == FigureType::Knight && pos.is_reachable_by_knight(target) {
+                    attackers.attackers_of(figure.color).push((FigureType::Knight, pos));
+                }
+            }
+        }
+        attackers
+    }
+
+    fn attackers_of(&mut self, color: Color) -> &mut Vec<(FigureType, Position)> {
+        match color {
+            Color::White => &mut self.white_attackers,
+            Color::Black => &mut self.black_attackers,
+        }
+    }
+
+    fn has_attacker(&self, color: Color) -> bool {
+        match color {
+            Color::White => !self.white_attackers.is_empty(),
+            Color::Black => !self.black_attackers.is_empty(),
+        }
+    }
+
+    /**
+     * looks for the first figure after start_pos in the given direction (as seen from the target)
+     * and adds it, if it can attack the target along that line.
+     * Pawns and kings only count if they are right next to the target.
+     */
+    fn add_first_attacker_in_direction(&mut self, start_pos: Position, direction: Direction, is_start_pos_target: bool) {
+        let mut pos = start_pos;
+        let mut is_next_to_target = is_start_pos_target;
+        loop {
+            pos = match pos.step(direction) {
+                None => { return; }
+                Some(new_pos) => new_pos,
+            };
+            if let Some(figure) = self.board.get_figure(pos) {
+                let can_attack = match figure.fig_type {
+                    FigureType::Rook => direction.is_straight(),
+                    FigureType::Bishop => direction.is_diagonal(),
+                    FigureType::Queen => true,
+                    FigureType::King => is_next_to_target,
+                    FigureType::Pawn => {
+                        // a pawn attacks diagonally forward, so it's found looking diagonally backward from the target
+                        let (backward_left, _, backward_right) = Direction::forward_directions(figure.color.toggle());
+                        is_next_to_target && (direction == backward_left || direction == backward_right)
+                    }
+                    FigureType::Knight => false,
+                };
+                if can_attack {
+                    self.attackers_of(figure.color).push((figure.fig_type, pos));
+                }
+                return;
+            }
+            is_next_to_target = false;
+        }
+    }
+
+    fn remove(&mut self, color: Color, pos: Position) {
+        let attackers = self.attackers_of(color);
+        if let Some(index) = attackers.iter().position(|(_, attacker_pos)| *attacker_pos == pos) {
+            attackers.remove(index);
+        }
+        self.add_attacker_from_behind(pos);
+    }
+
+    /**
+     * once the figure on freed_up_pos has captured, a slider behind it might attack the target
+     */
+    fn add_attacker_from_behind(&mut self, freed_up_pos: Position) {
+        if let Some(direction) = self.target.get_direction(freed_up_pos) {
+            self.add_first_attacker_in_direction(freed_up_pos, direction, false);
+        }
+    }
+
+    fn pop_least_valuable(&mut self, color: Color) -> Option<FigureType> {
+        let attackers = self.attackers_of(color);
+        let (index, _) = attackers.iter().enumerate().min_by_key(|(_, (fig_type, _))| exchange_value(*fig_type))?;
+        let (fig_type, pos) = attackers.remove(index);
+        self.add_attacker_from_behind(pos);
+        Some(fig_type)
+    }
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+    use crate::game::GameState;
+
+    //♔♕♗♘♖♙♚♛♝♞♜♟
+
+    #[rstest(
+    game_config, target_str, expected_gain,
+    case("white ♔a1 ♖e1 ♟e5 ♚h8", "e5", 100), // undefended pawn
+    case("white ♔a1 ♖e1 ♟e5 ♟d6 ♚h8", "e5", 0), // rook shouldn't take a defended pawn
+    case("white ♔a1 ♙d4 ♟e5 ♟d6 ♚h8", "e5", 0), // pawn takes pawn, pawn takes back
+    case("white ♔a1 ♘f3 ♞e5 ♟d6 ♚h8", "e5", 0), // knight for knight
+    case("white ♔a1 ♘f3 ♜e5 ♟d6 ♚h8", "e5", 200), // knight for rook
+    case("white ♔a1 ♖e1 ♖e2 ♞e5 ♜e8 ♚h8", "e5", 300), // the first rook is only traded for the knight if the second rook covers it
+    case("white ♔a1 ♖e1 ♞e5 ♜e8 ♚h8", "e5", 0),
+    case("white ♔a1 ♕e1 ♖e2 ♞e5 ♜e8 ♚h8", "e5", 300), // the rook in front captures first, the queen behind it (x-ray) secures the exchange
+    case("white ♔a1 ♗c3 ♕b2 ♞e5 ♟f6 ♚h8", "e5", 100), // bishop and queen on the same diagonal against a pawn-protected knight
+    case("white ♔a1 ♗c3 ♕b2 ♜e5 ♟f6 ♚h8", "e5", 300),
+    case("white ♔d4 ♞e5 ♚h8", "e5", 300), // king captures an undefended knight
+    case("white ♔d4 ♞e5 ♟f6 ♚h8", "e5", 0), // king can't capture a defended knight
+    case("white ♔a1 ♖e1 ♚h8", "e5", 0), // empty target
+    case("white ♔a1 ♖e1 ♖e5 ♚h8", "e5", 0), // own figure on target
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_static_exchange_eval(
+        game_config: &str,
+        target_str: &str,
+        expected_gain: i32,
+    ) {
+        let game_state = game_config.parse::<GameState>().unwrap();
+        let target = target_str.parse::<Position>().unwrap();
+        let actual_gain = static_exchange_eval(target, Color::White, &game_state.board);
+        assert_eq!(actual_gain, expected_gain, "white");
+
+        let toggled_game_state = game_state.toggle_colors();
+        let actual_gain = static_exchange_eval(target.toggle_row(), Color::Black, &toggled_game_state.board);
+        assert_eq!(actual_gain, expected_gain, "black");
+    }
+
+    #[rstest(
+    game_config, capture_str, expected_balance,
+    case("white ♔a1 ♖e1 ♟e5 ♟d6 ♚h8", "e1-e5", -400), // rook takes a defended pawn
+    case("white ♔a1 ♖e1 ♟e5 ♚h8", "e1-e5", 100),
+    case("white ♔a1 ♕e1 ♖e2 ♞e5 ♜e8 ♚h8", "e2-e5", 300),
+    case("white ♔a1 ♕e1 ♖e2 ♞e5 ♟d6 ♚h8", "e2-e5", -100),
+    case("white ♔a1 ♖e1 ♟e5 ♚h8", "e2-e5", 0), // the move doesn't start on a figure
+    case("white ♔a1 ♖e1 ♚h8", "e1-e5", 0), // the move doesn't capture
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_static_exchange_eval_of_capture(
+        game_config: &str,
+        capture_str: &str,
+        expected_balance: i32,
+    ) {
+        let game_state = game_config.parse::<GameState>().unwrap();
+        let capture = capture_str.parse::<Move>().unwrap();
+        let actual_balance = static_exchange_eval_of_capture(capture, &game_state.board);
+        assert_eq!(actual_balance, expected_balance);
+    }
+}
@@ -26,6 +26,20 @@ impl Figure {
             FigureType::King => {if self.color == Color::White {'K'} else {'k'}}
         }
     }
+
+    pub fn from_fen_char(fen_char: char) -> Option<Figure> {
+        let color = if fen_char.is_ascii_uppercase() {Color::White} else {Color::Black};
+        let fig_type = match fen_char.to_ascii_lowercase() {
+            'p' => FigureType::Pawn,
+            'r' => FigureType::Rook,
+            'n' => FigureType::Knight,
+            'b' => FigureType::Bishop,
+            'q' => FigureType::Queen,
+            'k' => FigureType::King,
+            _ => { return None; }
+        };
+        Some(Figure { fig_type, color })
+    }
 }
 
 impl str::FromStr for Figure {
@@ -77,7 +91,14 @@ impl str::FromStr for FigureAndPosition {
     type Err = ChessError;
 
     fn from_str(desc: &str) -> Result<Self, Self::Err> {
-        let split_point = desc.len()-2; // splitting is a bit more complicated since utf-8 chars like ♔ take more space than 1 byte
+        // splitting is a bit more complicated since utf-8 chars like ♔ take more space than 1 byte
+        let split_point = desc.len().saturating_sub(2);
+        if split_point == 0 || !desc.is_char_boundary(split_point) {
+            return Err(ChessError{
+                msg: format!("a figure followed by its position (like ♔e1) expected but got {}", desc),
+                kind: ErrorKind::IllegalFormat,
+            });
+        }
         let figure = desc[..split_point].parse::<Figure>()?;
         let pos = desc[split_point..].parse::<Position>()?;
 
@@ -54,6 +54,8 @@ type OneSidedBoardStates = TinyVec<BoardStateArray>;
 pub struct BoardStates {
     white_board_states_history: OneSidedBoardStates,
     black_board_states_history: OneSidedBoardStates,
+    // half moves without progress before the first known board state (e.g. the half-move clock of a fen)
+    earlier_half_moves_without_progress: usize,
 }
 
 impl BoardStates {
@@ -67,6 +69,14 @@ impl BoardStates {
         BoardStates {
             white_board_states_history: past_white_board_states,
             black_board_states_history: past_black_board_states,
+            earlier_half_moves_without_progress: 0,
+        }
+    }
+
+    pub fn with_earlier_half_moves_without_progress(self, earlier_half_moves_without_progress: usize) -> BoardStates {
+        BoardStates {
+            earlier_half_moves_without_progress,
+            ..self
         }
     }
 
@@ -164,17 +174,25 @@ impl BoardStates {
             new_black_board_states_history.len(),
         );
 
-        if new_white_board_states_history.len() == 50 && new_black_board_states_history.len() == 50 {
+        let earlier_half_moves_without_progress = if move_stats.did_move_pawn || move_stats.did_catch_figure {
+            0
+        } else {
+            self.earlier_half_moves_without_progress
+        };
+        let nr_of_board_states = new_white_board_states_history.len() + new_black_board_states_history.len();
+        if nr_of_board_states.saturating_add(earlier_half_moves_without_progress) >= 100 {
             Err(StoppedReason::NoChangeIn50Moves)
         } else {
             Ok(BoardStates {
                 white_board_states_history: new_white_board_states_history,
                 black_board_states_history: new_black_board_states_history,
+                earlier_half_moves_without_progress,
             })
         }
     }
 
     pub fn count_half_moves_without_progress(&self) -> usize {
-        self.white_board_states_history.len() + self.black_board_states_history.len() - 1
+        (self.white_board_states_history.len() + self.black_board_states_history.len() - 1)
+            .saturating_add(self.earlier_half_moves_without_progress)
     }
 }
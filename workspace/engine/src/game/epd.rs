@@ -0,0 +1,127 @@
+use std::str;
+use crate::base::{ChessError, ErrorKind, Move};
+use crate::game::{parse_san, Game};
+
+/**
+ * a position of a test suite in the Extended Position Description format, e.g.
+ * `2rr3k/pp3pp1/1nnqbN1p/3pN3/2pP4/2P3Q1/PPB4P/R4RK1 w - - bm Qg6; id "WAC.001";`
+ * Of the operations only bm (best moves), am (moves to avoid), dm (direct mate in n full moves) and id are read,
+ * the others (e.g. ce, c0, hmvc) are ignored.
+ */
+#[derive(Clone, Debug)]
+pub struct EpdRecord {
+    pub game: Game,
+    pub opt_id: Option<String>,
+    pub best_moves: Vec<Move>,
+    pub avoid_moves: Vec<Move>,
+    pub opt_mate_in: Option<usize>,
+}
+
+impl str::FromStr for EpdRecord {
+    type Err = ChessError;
+
+    fn from_str(epd: &str) -> Result<Self, Self::Err> {
+        let epd = epd.trim();
+        let mut fields_and_operations = epd.splitn(5, char::is_whitespace);
+        let fen_fields: Vec<&str> = fields_and_operations.by_ref().take(4).collect();
+        if fen_fields.len() != 4 {
+            return Err(epd_format_err(epd, "the first 4 fields of a fen are missing"));
+        }
+        // an epd has no half-move clock and full move number
+        let game = Game::from_fen(&format!("{} 0 1", fen_fields.join(" ")))?;
+
+        let mut record = EpdRecord {
+            game,
+            opt_id: None,
+            best_moves: vec![],
+            avoid_moves: vec![],
+            opt_mate_in: None,
+        };
+        for operation in split_outside_of_quotes(fields_and_operations.next().unwrap_or(""), ';') {
+            let operation = operation.trim();
+            if operation.is_empty() {
+                continue;
+            }
+            let (opcode, operands) = operation.split_once(char::is_whitespace).unwrap_or((operation, ""));
+            let operands = operands.trim();
+            match opcode {
+                "bm" => { record.best_moves = parse_san_operands(&record.game, operands)?; }
+                "am" => { record.avoid_moves = parse_san_operands(&record.game, operands)?; }
+                "dm" => {
+                    let mate_in = operands.parse::<usize>().map_err(|_| epd_format_err(epd, "dm expects a number of moves"))?;
+                    record.opt_mate_in = Some(mate_in);
+                }
+                "id" => { record.opt_id = Some(operands.trim_matches('"').to_string()); }
+                _ => {}
+            }
+        }
+        Ok(record)
+    }
+}
+
+fn parse_san_operands(game: &Game, operands: &str) -> Result<Vec<Move>, ChessError> {
+    operands.split_whitespace().map(|san| parse_san(game, san)).collect()
+}
+
+fn split_outside_of_quotes(text: &str, separator: char) -> Vec<&str> {
+    let mut parts: Vec<&str> = vec![];
+    let mut is_in_quotes = false;
+    let mut part_start = 0;
+    for (index, character) in text.char_indices() {
+        if character == '"' {
+            is_in_quotes = !is_in_quotes;
+        } else if character == separator && !is_in_quotes {
+            parts.push(&text[part_start..index]);
+            part_start = index + character.len_utf8();
+        }
+    }
+    parts.push(&text[part_start..]);
+    parts
+}
+
+fn epd_format_err(epd: &str, reason: &str) -> ChessError {
+    ChessError {
+        msg: format!("{} in epd {}", reason, epd),
+        kind: ErrorKind::IllegalFormat,
+    }
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[test]
+    fn test_parse_epd() {
+        let record = r#"2rr3k/pp3pp1/1nnqbN1p/3pN3/2pP4/2P3Q1/PPB4P/R4RK1 w - - bm Qg6; id "WAC.001";"#.parse::<EpdRecord>().unwrap();
+        assert_eq!(record.game.get_fen(), "2rr3k/pp3pp1/1nnqbN1p/3pN3/2pP4/2P3Q1/PPB4P/R4RK1 w - - 0 1");
+        assert_eq!(record.opt_id, Some("WAC.001".to_string()));
+        assert_eq!(record.best_moves, vec![Move::from_code("g3-g6")]);
+        assert!(record.avoid_moves.is_empty());
+        assert_eq!(record.opt_mate_in, None);
+    }
+
+    #[test]
+    fn test_parse_epd_with_several_operations() {
+        let record = r#"r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - am Ba6 Nxe5; c0 "quotes with ; inside"; dm 12; bm Bb5 Bc4; ce 30;"#
+            .parse::<EpdRecord>().unwrap();
+        assert_eq!(record.opt_id, None);
+        assert_eq!(record.best_moves, vec![Move::from_code("f1-b5"), Move::from_code("f1-c4")]);
+        assert_eq!(record.avoid_moves, vec![Move::from_code("f1-a6"), Move::from_code("f3-e5")]);
+        assert_eq!(record.opt_mate_in, Some(12));
+    }
+
+    #[rstest(
+    epd,
+    case("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq"),
+    case("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - bm e5;"),
+    case("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - dm two;"),
+    case("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP w KQkq - bm e4;"),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_parse_invalid_epd(epd: &str) {
+        assert!(epd.parse::<EpdRecord>().is_err());
+    }
+}
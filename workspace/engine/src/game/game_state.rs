@@ -7,6 +7,7 @@ use std::{fmt,str};
 use crate::base::rc_list::{RcList};
 use crate::figure::functions::count_reachable::count_reachable_moves;
 use crate::figure::functions::checkmate::is_active_king_checkmate;
+use crate::figure::functions::static_exchange::{static_exchange_eval, static_exchange_eval_of_capture};
 
 #[derive(Clone, Debug)]
 pub struct GameState {
@@ -198,6 +199,15 @@ impl GameState {
         Ok(game_state)
     }
 
+    /**
+     * parses the first four fields of a fen (board, turn, castling rights, en-passant position).
+     * The optional half-move clock and full-move number are validated but otherwise ignored,
+     * see Game::from_fen for a version that keeps them.
+     */
+    pub fn from_fen(fen: &str) -> Result<GameState, ChessError> {
+        parse_fen(fen).map(|(game_state, _, _)| game_state)
+    }
+
     pub fn do_move(&self, next_move: Move) -> (GameState, MoveStats) {
         debug_assert!(
             next_move.to() != self.white_king_pos && next_move.to() != self.black_king_pos,
@@ -370,6 +380,27 @@ impl GameState {
         )
     }
 
+    /**
+     * the same position, but the other color's turn (a "null move"). En passant isn't possible afterwards.
+     */
+    pub fn pass_turn(&self) -> GameState {
+        GameState {
+            turn_by: self.turn_by.toggle(),
+            en_passant_intercept_pos: None,
+            ..self.clone()
+        }
+    }
+
+    /**
+     * true if the active color has a figure besides its king and pawns,
+     * positions without are prone to zugzwang.
+     */
+    pub fn has_active_color_non_pawn_figures(&self) -> bool {
+        self.board.get_all_figures_of_color(self.turn_by).iter()
+            .map_while(|opt_figure| *opt_figure)
+            .any(|(figure, _)| figure.fig_type != FigureType::King && figure.fig_type != FigureType::Pawn)
+    }
+
     pub fn get_reachable_moves(&self) -> Moves {
         let mut move_collector: Moves = tiny_vec!();
         let figures_of_color_with_pos: [Option<(Figure, Position)>; 16] =
@@ -488,11 +519,39 @@ impl GameState {
         fen_part1to4
     }
 
-    pub fn get_moves_played(&self) -> String {
-        let debug_format = format!("{:?}", self.moves_played);
-        // remove the embracing '[' and ']'
-        let last_char_index = debug_format.len()-1;
-        debug_format[1..last_char_index].to_string()
+    /**
+     * the material (in centipawns) the active color can win by starting a capture sequence on target.
+     * Since nobody is forced to capture, the result is never negative.
+     */
+    pub fn static_exchange_eval(&self, target: Position) -> i32 {
+        static_exchange_eval(target, self.turn_by, &self.board)
+    }
+
+    /**
+     * the material balance (in centipawns) of the given capture followed by the best recaptures,
+     * negative if the capture loses material. Non-capturing moves (and moves from an empty field) are evaluated with 0.
+     */
+    pub fn static_exchange_eval_of(&self, capture: Move) -> i32 {
+        static_exchange_eval_of_capture(capture, &self.board)
+    }
+
+    /**
+     * a figure is hanging if its opponent can win material by capturing it (no matter whose turn it is)
+     */
+    pub fn is_figure_hanging(&self, pos: Position) -> bool {
+        match self.board.get_figure(pos) {
+            None => false,
+            Some(figure) => static_exchange_eval(pos, figure.color.toggle(), &self.board) > 0,
+        }
+    }
+
+    /**
+     * the moves played since the game started from its initial position (in the order they were played)
+     */
+    pub fn get_moves_played(&self) -> Vec<Move> {
+        let mut moves: Vec<Move> = self.moves_played.reverse_iter().copied().collect();
+        moves.reverse();
+        moves
     }
 }
 
@@ -504,6 +563,9 @@ impl str::FromStr for GameState {
         if trimmed_desc.is_empty() {
             return Ok(GameState::classic())
         }
+        if trimmed_desc.contains('/') {
+            return GameState::from_fen(trimmed_desc);
+        }
         let token_iter = trimmed_desc.split(' ');
 
         // let desc_contains_figures: bool = "♔♕♗♘♖♙♚♛♝♞♜♟".chars().any(|symbol|{desc.contains(symbol)});
@@ -564,6 +626,91 @@ fn game_by_figures_on_board(mut token_iter: str::Split<char>) -> Result<GameStat
     Ok(game_state)
 }
 
+/**
+ * returns the game state, the half-move clock and the full-move number
+ * (which default to 0 and 1 if they aren't part of the fen)
+ */
+pub(crate) fn parse_fen(fen: &str) -> Result<(GameState, usize, usize), ChessError> {
+    fn illegal_fen(fen: &str, reason: &str) -> ChessError {
+        ChessError {
+            msg: format!("illegal fen '{}': {}", fen, reason),
+            kind: ErrorKind::IllegalFormat,
+        }
+    }
+
+    let fields: Vec<&str> = fen.split_whitespace().collect();
+    if fields.len() != 4 && fields.len() != 6 {
+        return Err(illegal_fen(fen, "expected 4 or 6 space separated fields"));
+    }
+
+    let mut positioned_figures: Vec<FigureAndPosition> = vec![];
+    let rows: Vec<&str> = fields[0].split('/').collect();
+    if rows.len() != 8 {
+        return Err(illegal_fen(fen, "expected 8 rows"));
+    }
+    for (row_index, row_desc) in rows.iter().enumerate() {
+        let row = 7 - row_index as i8;
+        let mut column: i8 = 0;
+        for fen_char in row_desc.chars() {
+            if let Some(nr_of_empty_fields) = fen_char.to_digit(10) {
+                if !(1..=8).contains(&nr_of_empty_fields) {
+                    return Err(illegal_fen(fen, format!("the number of empty fields has to be between 1 and 8 but is {}", fen_char).as_str()));
+                }
+                column += nr_of_empty_fields as i8;
+                if column > 8 {
+                    return Err(illegal_fen(fen, "row too long"));
+                }
+            } else {
+                let figure = Figure::from_fen_char(fen_char).ok_or_else(|| illegal_fen(fen, format!("unknown figure {}", fen_char).as_str()))?;
+                let pos = Position::new_checked(column, row).ok_or_else(|| illegal_fen(fen, "row too long"))?;
+                positioned_figures.push(FigureAndPosition { figure, pos });
+                column += 1;
+            }
+        }
+        if column != 8 {
+            return Err(illegal_fen(fen, format!("row {} doesn't describe 8 fields", row + 1).as_str()));
+        }
+    }
+
+    let turn_by = match fields[1] {
+        "w" => Color::White,
+        "b" => Color::Black,
+        _ => { return Err(illegal_fen(fen, "the color has to be either 'w' or 'b'")); }
+    };
+
+    let castling_rights = fields[2];
+    if castling_rights != "-" && !castling_rights.chars().all(|it| "KQkq".contains(it)) {
+        return Err(illegal_fen(fen, "castling rights can only consist of 'KQkq' or '-'"));
+    }
+
+    let opt_en_passant_pos = if fields[3] == "-" {
+        None
+    } else if fields[3].len() != 2 {
+        return Err(illegal_fen(fen, "the en-passant position has to be either '-' or a position like 'e3'"));
+    } else {
+        Some(fields[3].parse::<Position>()?)
+    };
+
+    let (half_move_clock, full_move_number) = if fields.len() == 6 {
+        (
+            fields[4].parse::<usize>().map_err(|_| illegal_fen(fen, "the half-move clock has to be a number"))?,
+            fields[5].parse::<usize>().map_err(|_| illegal_fen(fen, "the full-move number has to be a number"))?.max(1),
+        )
+    } else {
+        (0, 1)
+    };
+
+    let mut game_state = GameState::from_manual_config(turn_by, opt_en_passant_pos, positioned_figures)?;
+    // from_manual_config only knows if kings and rooks are on their starting positions,
+    // the fen additionally knows if they've moved in between
+    if !castling_rights.contains('K') { game_state.is_white_king_side_castling_still_possible.deactivate(); }
+    if !castling_rights.contains('Q') { game_state.is_white_queen_side_castling_still_possible.deactivate(); }
+    if !castling_rights.contains('k') { game_state.is_black_king_side_castling_still_possible.deactivate(); }
+    if !castling_rights.contains('q') { game_state.is_black_queen_side_castling_still_possible.deactivate(); }
+
+    Ok((game_state, half_move_clock, full_move_number))
+}
+
 /**
 * returns if a figure gets caught by this move.
 */
@@ -830,6 +977,62 @@ mod tests {
         assert_eq!(actual_updated_board_fen, expected_updated_board_fen);
     }
 
+    #[rstest(
+    game_state_config, pos_str, expected_is_hanging,
+    case("white ♔a1 ♖e1 ♟e5 ♚h8", "e5", true),
+    case("white ♔a1 ♖e1 ♟e5 ♟d6 ♚h8", "e5", false),
+    case("black ♔a1 ♖e1 ♟e5 ♚h8", "e5", true),
+    case("white ♔a1 ♖e1 ♟e5 ♚h8", "e1", false),
+    case("white ♔a1 ♖e1 ♜e5 ♚h8", "e1", true),
+    case("white ♔a1 ♖e1 ♚h8", "e5", false),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_is_figure_hanging(
+        game_state_config: &str,
+        pos_str: &str,
+        expected_is_hanging: bool,
+    ) {
+        let game_state = game_state_config.parse::<GameState>().unwrap();
+        let pos = pos_str.parse::<Position>().unwrap();
+        assert_eq!(game_state.is_figure_hanging(pos), expected_is_hanging);
+    }
+
+    #[rstest(
+    fen,
+    case("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq -"),
+    case("rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq e6"),
+    case("rnbqkb1r/pppppppp/7n/8/8/N7/PPPPPPPP/1RBQKBNR b Kkq -"),
+    case("r3k2r/8/8/8/8/8/8/R3K2R b Qk -"),
+    case("4k3/8/8/8/8/8/8/3K3R w - -"),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_from_fen_round_trip(fen: &str) {
+        let game_state = GameState::from_fen(fen).unwrap();
+        assert_eq!(game_state.get_fen_part1to4(), fen);
+        let game_state = GameState::from_fen(format!("{} 0 1", fen).as_str()).unwrap();
+        assert_eq!(game_state.get_fen_part1to4(), fen);
+    }
+
+    #[rstest(
+    fen,
+    case("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP w KQkq -"),
+    case("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR x KQkq -"),
+    case("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNX w KQkq -"),
+    case("rnbqkbnr/pppppppp/9/8/8/8/PPPPPPPP/RNBQKBNR w KQkq -"),
+    case("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkx -"),
+    case("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0"),
+    case("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - zero 1"),
+    case("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQ1BNR w KQkq -"),
+    case("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq e"),
+    case("99999999999999999/8/8/8/8/8/8/8 w - - 0 1"),
+    case("rnbqkbnr/pppppppp/08/8/8/8/PPPPPPPP/RNBQKBNR w KQkq -"),
+    case("rnbqkbnr/pppppppp/44p/8/8/8/PPPPPPPP/RNBQKBNR w KQkq -"),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_from_fen_fails(fen: &str) {
+        assert!(GameState::from_fen(fen).is_err(), "fen {} should be rejected", fen);
+    }
+
     #[rstest(
     game_config_testing, expected_moves_played,
     case("", ""),
@@ -842,7 +1045,7 @@ mod tests {
         expected_moves_played: &str,
     ) {
         let game_state = game_config_testing.parse::<GameState>().unwrap();
-        let actual_moves_played = game_state.get_moves_played();
-        assert_eq!(actual_moves_played, expected_moves_played.to_string(), "moves played");
+        let actual_moves_played: Vec<String> = game_state.get_moves_played().iter().map(|a_move| a_move.to_string()).collect();
+        assert_eq!(actual_moves_played.join(", "), expected_moves_played.to_string(), "moves played");
     }
 }
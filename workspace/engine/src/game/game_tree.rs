@@ -0,0 +1,337 @@
+use crate::base::{ChessError, ErrorKind, Move};
+use crate::game::{Game, MoveResult, StoppedReason};
+
+/**
+ * the tree of the moves of an analysis: every node has the position after its move and the moves
+ * that were tried from there. The first child is the main line, the others are alternative variations.
+ * A node is addressed by its path, the indices of the children to go to from the root (the root's path is empty).
+ */
+#[derive(Clone, Debug)]
+pub struct GameTree {
+    root: TreeNode,
+    /**
+     * the tags of a pgn (like Event, White or Result) except FEN and SetUp, which are derived from the root game
+     */
+    pub tags: Vec<(String, String)>,
+}
+
+#[derive(Clone, Debug)]
+pub struct TreeNode {
+    opt_move: Option<Move>,
+    position: NodePosition,
+    opt_comment: Option<String>,
+    nags: Vec<u8>,
+    children: Vec<TreeNode>,
+}
+
+/**
+ * a move that ends the game by a draw rule (e.g. a threefold repetition) leaves no Game to continue with
+ */
+#[derive(Clone, Debug)]
+enum NodePosition {
+    Ongoing(Box<Game>),
+    Ended(StoppedReason),
+}
+
+impl GameTree {
+    pub fn new(root_game: Game) -> GameTree {
+        GameTree {
+            root: TreeNode::new(None, NodePosition::Ongoing(Box::new(root_game))),
+            tags: vec![],
+        }
+    }
+
+    pub fn get_root(&self) -> &TreeNode {
+        &self.root
+    }
+
+    pub fn get_root_game(&self) -> &Game {
+        self.root.get_game().expect("the root always has a game")
+    }
+
+    pub fn get_node(&self, path: &[usize]) -> Option<&TreeNode> {
+        path.iter().try_fold(&self.root, |node, child_index| node.children.get(*child_index))
+    }
+
+    /**
+     * to change the comment or the NAGs of a node
+     */
+    pub fn get_node_mut(&mut self, path: &[usize]) -> Option<&mut TreeNode> {
+        path.iter().try_fold(&mut self.root, |node, child_index| node.children.get_mut(*child_index))
+    }
+
+    /**
+     * adds the move as child of the node and returns the path of the child. If the move was already tried there,
+     * the existing child is returned. The first move added to a node becomes its main line, the later ones variations.
+     */
+    pub fn add_move(&mut self, path: &[usize], a_move: Move) -> Result<Vec<usize>, ChessError> {
+        let node = self.get_node_mut(path).ok_or_else(|| unknown_path_err(path))?;
+        let child_index = match node.children.iter().position(|child| child.opt_move == Some(a_move)) {
+            Some(child_index) => child_index,
+            None => {
+                let game = node.get_game().ok_or_else(|| ChessError {
+                    msg: format!("no move can follow {:?}, the game has already ended", node.opt_move),
+                    kind: ErrorKind::IllegalMove,
+                })?;
+                if !game.get_reachable_moves().contains(&a_move) {
+                    return Err(ChessError {
+                        msg: format!("move {} isn't possible in {}", a_move, game.get_fen()),
+                        kind: ErrorKind::IllegalMove,
+                    });
+                }
+                let position = match game.play(a_move) {
+                    MoveResult::Ongoing(new_game, _) => NodePosition::Ongoing(new_game),
+                    MoveResult::Stopped(StoppedReason::KingInCheckAfterMove, _) => {
+                        return Err(ChessError {
+                            msg: format!("move {} would leave the king in check in {}", a_move, game.get_fen()),
+                            kind: ErrorKind::IllegalMove,
+                        });
+                    }
+                    MoveResult::Stopped(stopped_reason, _) => NodePosition::Ended(stopped_reason),
+                };
+                node.children.push(TreeNode::new(Some(a_move), position));
+                node.children.len() - 1
+            }
+        };
+        let mut child_path = path.to_vec();
+        child_path.push(child_index);
+        Ok(child_path)
+    }
+
+    /**
+     * makes the variation the main line of its parent (the former main line becomes its first alternative).
+     * Returns the new path of the node.
+     */
+    pub fn promote_variation(&mut self, path: &[usize]) -> Result<Vec<usize>, ChessError> {
+        let (child_index, parent_path) = path.split_last().ok_or_else(|| root_err("promoted"))?;
+        let parent = self.get_node_mut(parent_path).filter(|parent| *child_index < parent.children.len())
+            .ok_or_else(|| unknown_path_err(path))?;
+        let variation = parent.children.remove(*child_index);
+        parent.children.insert(0, variation);
+        let mut new_path = parent_path.to_vec();
+        new_path.push(0);
+        Ok(new_path)
+    }
+
+    /**
+     * removes the node with all the moves after it. Deleting the main line makes the first variation the main line.
+     */
+    pub fn delete_variation(&mut self, path: &[usize]) -> Result<TreeNode, ChessError> {
+        let (child_index, parent_path) = path.split_last().ok_or_else(|| root_err("deleted"))?;
+        let parent = self.get_node_mut(parent_path).filter(|parent| *child_index < parent.children.len())
+            .ok_or_else(|| unknown_path_err(path))?;
+        Ok(parent.children.remove(*child_index))
+    }
+
+    /**
+     * the moves from the root following the first child of every node
+     */
+    pub fn get_main_line(&self) -> Vec<Move> {
+        let mut main_line: Vec<Move> = vec![];
+        let mut node = &self.root;
+        while let Some(main_child) = node.children.first() {
+            main_line.extend(main_child.opt_move);
+            node = main_child;
+        }
+        main_line
+    }
+
+    /**
+     * the moves from the root to the node
+     */
+    pub fn get_moves_to(&self, path: &[usize]) -> Option<Vec<Move>> {
+        let mut moves: Vec<Move> = vec![];
+        let mut node = &self.root;
+        for child_index in path {
+            node = node.children.get(*child_index)?;
+            moves.extend(node.opt_move);
+        }
+        Some(moves)
+    }
+}
+
+impl TreeNode {
+    fn new(opt_move: Option<Move>, position: NodePosition) -> TreeNode {
+        TreeNode {
+            opt_move,
+            position,
+            opt_comment: None,
+            nags: vec![],
+            children: vec![],
+        }
+    }
+
+    /**
+     * None for the root
+     */
+    pub fn get_move(&self) -> Option<Move> {
+        self.opt_move
+    }
+
+    /**
+     * the game after the move, None if the move ended the game by a draw rule
+     */
+    pub fn get_game(&self) -> Option<&Game> {
+        match &self.position {
+            NodePosition::Ongoing(game) => Some(game),
+            NodePosition::Ended(_) => None,
+        }
+    }
+
+    pub fn get_stopped_reason(&self) -> Option<StoppedReason> {
+        match self.position {
+            NodePosition::Ongoing(_) => None,
+            NodePosition::Ended(stopped_reason) => Some(stopped_reason),
+        }
+    }
+
+    /**
+     * the main line first, followed by the alternative variations
+     */
+    pub fn get_children(&self) -> &[TreeNode] {
+        &self.children
+    }
+
+    pub fn get_comment(&self) -> Option<&str> {
+        self.opt_comment.as_deref()
+    }
+
+    /**
+     * a comment can't contain '}', since that ends a comment in PGN (see GameTree::to_pgn)
+     */
+    pub fn set_comment(&mut self, opt_comment: Option<String>) -> Result<(), ChessError> {
+        if let Some(comment) = opt_comment.as_ref().filter(|comment| comment.contains('}')) {
+            return Err(ChessError {
+                msg: format!("a comment can't contain '}}' but was: {}", comment),
+                kind: ErrorKind::IllegalFormat,
+            });
+        }
+        self.opt_comment = opt_comment.filter(|comment| !comment.trim().is_empty());
+        Ok(())
+    }
+
+    /**
+     * the numeric annotation glyphs of the move, e.g. 1 for a good move (!) or 2 for a mistake (?)
+     */
+    pub fn get_nags(&self) -> &[u8] {
+        &self.nags
+    }
+
+    pub fn add_nag(&mut self, nag: u8) {
+        if !self.nags.contains(&nag) {
+            self.nags.push(nag);
+        }
+    }
+
+    pub fn remove_nag(&mut self, nag: u8) {
+        self.nags.retain(|other_nag| *other_nag != nag);
+    }
+}
+
+fn unknown_path_err(path: &[usize]) -> ChessError {
+    ChessError {
+        msg: format!("there is no node with the path {:?}", path),
+        kind: ErrorKind::IllegalConfig,
+    }
+}
+
+fn root_err(operation: &str) -> ChessError {
+    ChessError {
+        msg: format!("the root can't be {}", operation),
+        kind: ErrorKind::IllegalConfig,
+    }
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    fn tree_with(lines: &[&str]) -> GameTree {
+        let mut tree = GameTree::new(Game::classic());
+        for line in lines {
+            let mut path: Vec<usize> = vec![];
+            for move_str in line.split_whitespace() {
+                path = tree.add_move(&path, Move::from_code(move_str)).unwrap();
+            }
+        }
+        tree
+    }
+
+    fn moves(moves_str: &str) -> Vec<Move> {
+        moves_str.split_whitespace().map(Move::from_code).collect()
+    }
+
+    #[test]
+    fn test_add_move() {
+        let mut tree = tree_with(&["e2-e4 e7-e5 g1-f3", "e2-e4 c7-c5"]);
+        assert_eq!(tree.get_main_line(), moves("e2-e4 e7-e5 g1-f3"));
+        assert_eq!(tree.get_node(&[0]).unwrap().get_children().len(), 2);
+        assert_eq!(tree.get_moves_to(&[0, 1]), Some(moves("e2-e4 c7-c5")));
+        assert_eq!(tree.get_node(&[0, 1]).unwrap().get_game().unwrap().get_fen(), "e2-e4 c7-c5".parse::<Game>().unwrap().get_fen());
+
+        // an existing move isn't added twice
+        assert_eq!(tree.add_move(&[0], Move::from_code("c7-c5")).unwrap(), vec![0, 1]);
+        assert_eq!(tree.get_node(&[0]).unwrap().get_children().len(), 2);
+    }
+
+    #[rstest(
+    path, move_str,
+    case(vec![], "e2-e5"),
+    case(vec![0], "e2-e4"),
+    case(vec![3], "e7-e5"),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_add_illegal_move(path: Vec<usize>, move_str: &str) {
+        let mut tree = tree_with(&["e2-e4"]);
+        assert!(tree.add_move(&path, Move::from_code(move_str)).is_err());
+    }
+
+    #[test]
+    fn test_move_that_ends_the_game() {
+        let mut tree = tree_with(&["g1-f3 g8-f6 f3-g1 f6-g8 g1-f3 g8-f6 f3-g1"]);
+        let main_line_end: Vec<usize> = vec![0; 7];
+        let end_path = tree.add_move(&main_line_end, Move::from_code("f6-g8")).unwrap();
+        let end_node = tree.get_node(&end_path).unwrap();
+        assert!(end_node.get_game().is_none());
+        assert_eq!(end_node.get_stopped_reason(), Some(StoppedReason::ThreeTimesRepetition));
+        assert!(tree.add_move(&end_path, Move::from_code("g1-f3")).is_err());
+    }
+
+    #[test]
+    fn test_promote_and_delete_variation() {
+        let mut tree = tree_with(&["e2-e4 e7-e5", "e2-e4 c7-c5", "e2-e4 e7-e6"]);
+        assert_eq!(tree.promote_variation(&[0, 2]).unwrap(), vec![0, 0]);
+        assert_eq!(tree.get_main_line(), moves("e2-e4 e7-e6"));
+        assert_eq!(tree.get_moves_to(&[0, 1]), Some(moves("e2-e4 e7-e5")));
+
+        let deleted = tree.delete_variation(&[0, 0]).unwrap();
+        assert_eq!(deleted.get_move(), Some(Move::from_code("e7-e6")));
+        assert_eq!(tree.get_main_line(), moves("e2-e4 e7-e5"));
+        assert_eq!(tree.get_moves_to(&[0, 1]), Some(moves("e2-e4 c7-c5")));
+
+        assert!(tree.promote_variation(&[]).is_err());
+        assert!(tree.delete_variation(&[0, 5]).is_err());
+    }
+
+    #[test]
+    fn test_comments_and_nags() {
+        let mut tree = tree_with(&["e2-e4 e7-e5"]);
+        let node = tree.get_node_mut(&[0, 0]).unwrap();
+        node.set_comment(Some("the classical answer".to_string())).unwrap();
+        node.add_nag(1);
+        node.add_nag(1);
+        node.add_nag(14);
+        node.remove_nag(1);
+        assert_eq!(tree.get_node(&[0, 0]).unwrap().get_comment(), Some("the classical answer"));
+        assert_eq!(tree.get_node(&[0, 0]).unwrap().get_nags(), &[14]);
+
+        tree.get_node_mut(&[0, 0]).unwrap().set_comment(Some(" ".to_string())).unwrap();
+        assert_eq!(tree.get_node(&[0, 0]).unwrap().get_comment(), None);
+
+        assert!(tree.get_node_mut(&[0, 0]).unwrap().set_comment(Some("closed {or not}".to_string())).is_err());
+        assert_eq!(tree.get_node(&[0, 0]).unwrap().get_comment(), None);
+    }
+}
@@ -0,0 +1,275 @@
+use std::mem;
+use crate::base::{ChessError, ErrorKind, Move, Moves};
+use crate::game::{Game, MoveResult, StoppedReason};
+use crate::game::board_state::BoardStates;
+
+/**
+ * the games before and after the current one, so that a Game can be navigated like on an analysis board.
+ * Only make_move keeps a history, play starts the next game without one, since the engine plays a move
+ * for every position it searches.
+ */
+#[derive(Clone, Debug, Default)]
+pub(crate) struct History {
+    // the games before the current one (the initial game first)
+    earlier_games: Vec<Game>,
+    // the games that were undone (the one directly after the current game last)
+    undone_games: Vec<Game>,
+}
+
+impl Game {
+    /**
+     * plays the move and keeps the current game in the history, so that the move can be undone.
+     * Playing a move after going back discards the moves that were undone (like typing in an editor after an undo).
+     * A move that ends the game by a draw rule is kept as well, the resulting game has a stopped reason and no reachable moves.
+     */
+    pub fn make_move(&mut self, a_move: Move) -> Result<(), ChessError> {
+        if let Some(stopped_reason) = self.opt_stopped_reason {
+            return Err(ChessError {
+                msg: format!("move {} can't be played, the game has already ended because of {:?}", a_move, stopped_reason),
+                kind: ErrorKind::HighLevelErr(stopped_reason),
+            });
+        }
+        let illegal_move_err = || ChessError {
+            msg: format!("move {} isn't possible in {}", a_move, self.get_fen()),
+            kind: ErrorKind::IllegalMove,
+        };
+        if !self.reachable_moves.contains(&a_move) {
+            return Err(illegal_move_err());
+        }
+        let next_game = match self.play(a_move) {
+            MoveResult::Ongoing(next_game, _) => *next_game,
+            MoveResult::Stopped(StoppedReason::KingInCheckAfterMove, _) => {
+                return Err(illegal_move_err());
+            }
+            MoveResult::Stopped(stopped_reason, _) => self.ended_by(a_move, stopped_reason),
+        };
+        let mut history = mem::take(&mut self.history);
+        history.undone_games.clear();
+        history.earlier_games.push(mem::replace(self, next_game));
+        self.history = history;
+        Ok(())
+    }
+
+    /**
+     * the final game after a move that ended the game by a draw rule
+     */
+    fn ended_by(&self, a_move: Move, stopped_reason: StoppedReason) -> Game {
+        let (final_state, move_stats) = self.latest_state.do_move(a_move);
+        let board_states = BoardStates::new(final_state.board.encode(), final_state.turn_by)
+            .with_earlier_half_moves_without_progress(self.count_half_moves_without_progress_after(move_stats));
+        Game {
+            latest_state: final_state,
+            latest_move: Some(a_move),
+            reachable_moves: Moves::new(),
+            board_states,
+            half_moves_played: self.half_moves_played + 1,
+            opt_stopped_reason: Some(stopped_reason),
+            history: History::default(),
+        }
+    }
+
+    /**
+     * the draw rule that ended the game with the latest move (kept by make_move), None while the game goes on
+     */
+    pub fn get_stopped_reason(&self) -> Option<StoppedReason> {
+        self.opt_stopped_reason
+    }
+
+    /**
+     * goes back one move, false if there is no earlier game in the history
+     */
+    pub fn undo(&mut self) -> bool {
+        let Some(mut previous_game) = self.history.earlier_games.pop() else {
+            return false;
+        };
+        previous_game.history = mem::take(&mut self.history);
+        let current_game = mem::replace(self, previous_game);
+        self.history.undone_games.push(current_game);
+        true
+    }
+
+    /**
+     * plays the latest undone move again, false if there is none
+     */
+    pub fn redo(&mut self) -> bool {
+        let Some(mut next_game) = self.history.undone_games.pop() else {
+            return false;
+        };
+        next_game.history = mem::take(&mut self.history);
+        let current_game = mem::replace(self, next_game);
+        self.history.earlier_games.push(current_game);
+        true
+    }
+
+    /**
+     * the number of moves played since the first game of the history
+     */
+    pub fn get_current_ply(&self) -> usize {
+        self.history.earlier_games.len()
+    }
+
+    /**
+     * the number of moves that can be navigated, including the ones that were undone
+     */
+    pub fn get_nr_of_plies(&self) -> usize {
+        self.history.earlier_games.len() + self.history.undone_games.len()
+    }
+
+    pub fn get_initial_game(&self) -> &Game {
+        self.history.earlier_games.first().unwrap_or(self)
+    }
+
+    /**
+     * the moves that were undone and can be redone (the next one first)
+     */
+    pub fn get_moves_to_redo(&self) -> Vec<Move> {
+        self.history.undone_games.iter().rev()
+            .map(|game| game.latest_move.expect("an undone game was reached by a move"))
+            .collect()
+    }
+
+    /**
+     * makes the game after the given number of moves the current one (the moves after it can still be redone)
+     */
+    pub fn go_to_ply(&mut self, ply: usize) -> Result<(), ChessError> {
+        let nr_of_plies = self.get_nr_of_plies();
+        if ply > nr_of_plies {
+            return Err(ChessError {
+                msg: format!("ply {} doesn't exist, only {} moves were played", ply, nr_of_plies),
+                kind: ErrorKind::IllegalConfig,
+            });
+        }
+        while self.get_current_ply() > ply {
+            self.undo();
+        }
+        while self.get_current_ply() < ply {
+            self.redo();
+        }
+        Ok(())
+    }
+
+    /**
+     * goes to the ply and plays the moves from there on (discarding the moves that were played after the ply before).
+     * If one of the moves can't be played, the game stays unchanged.
+     */
+    pub fn replay_from(&mut self, ply: usize, moves: &[Move]) -> Result<(), ChessError> {
+        let mut new_game = self.clone();
+        new_game.go_to_ply(ply)?;
+        for a_move in moves {
+            new_game.make_move(*a_move)?;
+        }
+        *self = new_game;
+        Ok(())
+    }
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    fn moves(moves_str: &str) -> Vec<Move> {
+        moves_str.split_whitespace().map(Move::from_code).collect()
+    }
+
+    fn game_after(moves_str: &str) -> Game {
+        let mut game = Game::classic();
+        for a_move in moves(moves_str) {
+            game.make_move(a_move).unwrap();
+        }
+        game
+    }
+
+    #[test]
+    fn test_undo_and_redo() {
+        let mut game = game_after("e2-e4 e7-e5 g1-f3");
+        let fen_after_3_plies = game.get_fen();
+
+        assert!(game.undo());
+        assert!(game.undo());
+        assert_eq!(game.get_current_ply(), 1);
+        assert_eq!(game.get_moves_played(), moves("e2-e4"));
+        assert_eq!(game.get_moves_to_redo(), moves("e7-e5 g1-f3"));
+        assert_eq!(game.get_fen(), "e2-e4".parse::<Game>().unwrap().get_fen());
+
+        assert!(game.redo());
+        assert!(game.redo());
+        assert!(!game.redo());
+        assert_eq!(game.get_fen(), fen_after_3_plies);
+
+        assert!(game.go_to_ply(0).is_ok());
+        assert!(!game.undo());
+        assert_eq!(game.get_fen(), Game::classic().get_fen());
+        assert_eq!(game.get_nr_of_plies(), 3);
+    }
+
+    #[test]
+    fn test_make_move_after_undo_discards_the_undone_moves() {
+        let mut game = game_after("e2-e4 e7-e5 g1-f3");
+        game.go_to_ply(1).unwrap();
+        game.make_move(Move::from_code("c7-c5")).unwrap();
+        assert_eq!(game.get_moves_played(), moves("e2-e4 c7-c5"));
+        assert!(game.get_moves_to_redo().is_empty());
+        assert!(!game.redo());
+        assert_eq!(game.get_fen(), "e2-e4 c7-c5".parse::<Game>().unwrap().get_fen());
+    }
+
+    #[rstest(
+    ply, expected_is_ok,
+    case(0, true),
+    case(3, true),
+    case(4, false),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_go_to_ply(ply: usize, expected_is_ok: bool) {
+        let mut game = game_after("e2-e4 e7-e5 g1-f3");
+        assert_eq!(game.go_to_ply(ply).is_ok(), expected_is_ok);
+        assert_eq!(game.get_current_ply(), if expected_is_ok { ply } else { 3 });
+        assert_eq!(game.get_nr_of_plies(), 3);
+    }
+
+    #[test]
+    fn test_replay_from() {
+        let mut game = game_after("e2-e4 e7-e5 g1-f3");
+        game.replay_from(2, &moves("d2-d4 e5-d4")).unwrap();
+        assert_eq!(game.get_moves_played(), moves("e2-e4 e7-e5 d2-d4 e5-d4"));
+
+        assert!(game.replay_from(1, &moves("d7-d5 d2-d5")).is_err());
+        assert_eq!(game.get_moves_played(), moves("e2-e4 e7-e5 d2-d4 e5-d4"));
+    }
+
+    #[test]
+    fn test_illegal_moves_are_not_kept() {
+        let mut game = game_after("e2-e4");
+        let err = game.make_move(Move::from_code("e2-e4")).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::IllegalMove);
+        // would leave the king in check
+        let mut pinned_game = "white ♔e1 ♖e2 ♚e8 ♜e7".parse::<Game>().unwrap();
+        let err = pinned_game.make_move(Move::from_code("e2-d2")).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::IllegalMove);
+        assert_eq!(game.get_current_ply(), 1);
+        assert_eq!(pinned_game.get_current_ply(), 0);
+    }
+
+    #[test]
+    fn test_a_move_that_ends_the_game_by_a_draw_rule_is_kept() {
+        let mut game = game_after("g1-f3 g8-f6 f3-g1 f6-g8 g1-f3 g8-f6 f3-g1 f6-g8");
+        assert_eq!(game.get_current_ply(), 8);
+        assert_eq!(game.get_stopped_reason(), Some(StoppedReason::ThreeTimesRepetition));
+        assert!(game.get_reachable_moves().is_empty());
+        assert_eq!(game.get_fen(), "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 8 5");
+        let err = game.make_move(Move::from_code("e2-e4")).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::HighLevelErr(StoppedReason::ThreeTimesRepetition));
+
+        assert!(game.undo());
+        assert_eq!(game.get_stopped_reason(), None);
+        assert_eq!(game.get_moves_to_redo(), moves("f6-g8"));
+
+        game.go_to_ply(0).unwrap();
+        game.go_to_ply(8).unwrap();
+        assert_eq!(game.get_stopped_reason(), Some(StoppedReason::ThreeTimesRepetition));
+        assert_eq!(game.get_moves_played().last(), Some(&Move::from_code("f6-g8")));
+    }
+}
@@ -1,12 +1,23 @@
 mod game_state;
 mod board;
 mod board_state;
+mod perft;
+mod san;
+mod epd;
+mod history;
+mod game_tree;
+mod pgn;
 
 pub use crate::game::game_state::*;
 pub use crate::game::board::*;
-use crate::base::{Moves, ChessError, ErrorKind, Move, Position};
+pub use crate::game::perft::*;
+pub use crate::game::san::*;
+pub use crate::game::epd::*;
+pub use crate::game::game_tree::*;
+use crate::base::{Color, Moves, ChessError, ErrorKind, Move, Position};
 use std::{str, fmt};
 use crate::game::board_state::{BoardStates};
+use crate::game::history::History;
 
 #[derive(Clone, Debug)]
 pub struct Game {
@@ -15,6 +26,9 @@ pub struct Game {
     reachable_moves: Moves,
     board_states: BoardStates,
     half_moves_played: usize,
+    // only set for the final game of a history, whose latest move ended the game by a draw rule
+    opt_stopped_reason: Option<StoppedReason>,
+    history: History,
 }
 
 impl Game {
@@ -37,9 +51,24 @@ impl Game {
             reachable_moves,
             board_states: BoardStates::new(board_state, turn_by),
             half_moves_played: 0,
+            opt_stopped_reason: None,
+            history: History::default(),
         }
     }
 
+    /**
+     * The half-move clock of the fen counts towards the 50-move rule
+     * (the board states before it are unknown, so they can't count towards a repetition).
+     */
+    pub fn from_fen(fen: &str) -> Result<Game, ChessError> {
+        let (game_state, half_move_clock, full_move_number) = parse_fen(fen)?;
+        let is_blacks_turn = game_state.turn_by == Color::Black;
+        let mut game = verified_game_from_state(game_state)?;
+        game.half_moves_played = (full_move_number - 1) * 2 + if is_blacks_turn { 1 } else { 0 };
+        game.board_states = game.board_states.with_earlier_half_moves_without_progress(half_move_clock);
+        Ok(game)
+    }
+
     pub fn play(&self, a_move: Move) -> MoveResult {
         let (new_game_state, move_stats) = self.latest_state.do_move(a_move);
 
@@ -74,10 +103,26 @@ impl Game {
             reachable_moves,
             board_states: new_board_states,
             half_moves_played: self.half_moves_played + 1,
+            opt_stopped_reason: None,
+            history: History::default(),
         };
         MoveResult::Ongoing(Box::new(new_game), move_stats)
     }
 
+    /**
+     * lets the active color pass its turn (used by null-move pruning).
+     * Returns None if the active king is in check, since passing would be illegal then.
+     */
+    pub fn play_null_move(&self) -> Option<Game> {
+        if self.is_active_king_in_check() {
+            return None;
+        }
+        let mut null_move_game = Game::from_state(self.latest_state.pass_turn());
+        // positions before the null move can't be repeated in a real game, so the history starts anew
+        null_move_game.half_moves_played = self.half_moves_played + 1;
+        Some(null_move_game)
+    }
+
     pub fn get_reachable_moves(&self) -> &Moves {
         &self.reachable_moves
     }
@@ -86,6 +131,18 @@ impl Game {
         &self.latest_state
     }
 
+    pub fn get_half_moves_played(&self) -> usize {
+        self.half_moves_played
+    }
+
+    /**
+     * the moves played since the game was created from its initial position (e.g. a fen).
+     * Use make_move instead of play to be able to go back to an earlier position.
+     */
+    pub fn get_moves_played(&self) -> Vec<Move> {
+        self.latest_state.get_moves_played()
+    }
+
     pub fn is_passive_king_pos(&self, reachable_field: Position) -> bool {
         reachable_field == self.latest_state.get_passive_king_pos()
     }
@@ -106,6 +163,28 @@ impl Game {
         fen.push_str(((self.half_moves_played / 2) + 1).to_string().as_str());
         fen
     }
+
+    /**
+     * the fen after the given move. Unlike play(a_move).get_fen() this also works for a move that ends the game
+     * (e.g. by a three-fold repetition).
+     */
+    pub fn get_fen_after(&self, a_move: Move) -> String {
+        let (new_game_state, move_stats) = self.latest_state.do_move(a_move);
+        format!(
+            "{} {} {}",
+            new_game_state.get_fen_part1to4(),
+            self.count_half_moves_without_progress_after(move_stats),
+            ((self.half_moves_played + 1) / 2) + 1,
+        )
+    }
+
+    fn count_half_moves_without_progress_after(&self, move_stats: MoveStats) -> usize {
+        if move_stats.did_catch_figure || move_stats.did_move_pawn {
+            0
+        } else {
+            self.board_states.count_half_moves_without_progress() + 1
+        }
+    }
 }
 
 impl fmt::Display for Game {
@@ -122,6 +201,9 @@ impl str::FromStr for Game {
         if trimmed_desc.is_empty() {
             return Ok(Game::classic())
         }
+        if trimmed_desc.contains('/') {
+            return Game::from_fen(trimmed_desc);
+        }
         let token_iter = trimmed_desc.split(' ');
 
         // let desc_contains_figures: bool = "♔♕♗♘♖♙♚♛♝♞♜♟".chars().any(|symbol|{desc.contains(symbol)});
@@ -136,6 +218,10 @@ impl str::FromStr for Game {
 
 fn game_by_figures_on_board(trimmed_game_config: &str) -> Result<Game, ChessError> {
     let game_state = trimmed_game_config.parse::<GameState>()?;
+    verified_game_from_state(game_state)
+}
+
+fn verified_game_from_state(game_state: GameState) -> Result<Game, ChessError> {
     if let Err(stopped_reason) = verify_game_state(&game_state) {
         return Err(ChessError {
             msg: format!("game_state {} failed to pass verification: {:?}", game_state, &stopped_reason),
@@ -149,6 +235,12 @@ fn game_by_moves_from_start(token_iter: str::Split<char>) -> Result<Game, ChessE
     let mut game = Game::classic();
     for token in token_iter {
         let a_move = token.parse::<Move>()?;
+        if !game.get_reachable_moves().contains(&a_move) {
+            return Err(ChessError {
+                msg: format!("move {} isn't possible in {}", a_move, game.get_fen()),
+                kind: ErrorKind::IllegalMove,
+            });
+        }
         let move_result = game.play(a_move);
         match move_result {
             MoveResult::Ongoing(new_game, _) => {
@@ -235,6 +327,30 @@ mod tests {
         assert_eq!(is_insufficient_material(move_result), expected_is_insufficient_material);
     }
 
+    #[rstest(
+    game_config, expected_error_kind,
+    case("e2-", ErrorKind::IllegalFormat),
+    case("E2-E4", ErrorKind::IllegalFormat),
+    case("ä2-e4", ErrorKind::IllegalFormat),
+    case("e2-e4 e7", ErrorKind::IllegalFormat),
+    case("e2-e4 e2-e4", ErrorKind::IllegalMove),
+    case("e2-e5", ErrorKind::IllegalMove),
+    case("white ♔e1 ♚e8 ♙", ErrorKind::IllegalFormat),
+    case("white ♔e1 ♚e8 ♙a", ErrorKind::IllegalFormat),
+    case("white ♔e1 ♚e8 X", ErrorKind::IllegalFormat),
+    case("white ♔e1 ♚e8 ♙i9", ErrorKind::IllegalFormat),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_parse_malformed_game_config(
+        game_config: &str,
+        expected_error_kind: ErrorKind,
+    ) {
+        match game_config.parse::<Game>() {
+            Err(err) => assert_eq!(err.kind, expected_error_kind, "{}", err),
+            Ok(game) => panic!("expected an error, but got {}", game.get_fen()),
+        }
+    }
+
     #[rstest(
     game_config, expected_stop_reason,
     case("white ♔h8 ♚f8 ♞a7", StoppedReason::InsufficientMaterial),
@@ -279,6 +395,10 @@ mod tests {
     case("b1-a3 g8-h6 a1-b1 h8-g8", "rnbqkbr1/pppppppp/7n/8/8/N7/PPPPPPPP/1RBQKBNR w Kq - 4 3"),
     case("white ♔d1 ♖h1 ♚e8", "4k3/8/8/8/8/8/8/3K3R w - - 0 1"),
     case("black ♖a1 ♔e1 ♖h1 ♜a8 ♚e8 ♜h8", "r3k2r/8/8/8/8/8/8/R3K2R b KQkq - 0 1"),
+    case("rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq e6 0 2", "rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq e6 0 2"),
+    case("r3k2r/8/8/8/8/8/8/R3K2R b Kq - 0 23", "r3k2r/8/8/8/8/8/8/R3K2R b Kq - 0 23"),
+    case("4k3/8/8/8/8/8/8/3K3R w - -", "4k3/8/8/8/8/8/8/3K3R w - - 0 1"),
+    case("r3k2r/8/8/8/8/8/8/R3K2R b Kq - 7 12", "r3k2r/8/8/8/8/8/8/R3K2R b Kq - 7 12"),
     ::trace //This leads to the arguments being printed in front of the test result.
     )]
     fn test_get_fen(
@@ -289,4 +409,75 @@ mod tests {
         let actual_fen = game.get_fen();
         assert_eq!(actual_fen, String::from(expected_fen));
     }
-}
\ No newline at end of file
+
+    #[rstest(
+    fen, next_move, expected_is_50_move_draw,
+    case("4k3/8/8/8/8/8/8/3K3R w - - 97 80", "d1-c1", false),
+    case("4k3/8/8/8/8/8/8/3K3R w - - 98 80", "d1-c1", true),
+    case("4k3/8/8/8/8/8/8/3K3R w - - 150 80", "d1-c1", true),
+    case("4k3/8/8/8/8/8/7p/3K3R w - - 98 80", "h1-h2", false), // a capture resets the clock
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_half_move_clock_of_fen(
+        fen: &str,
+        next_move: &str,
+        expected_is_50_move_draw: bool,
+    ) {
+        let game = Game::from_fen(fen).unwrap();
+        let move_result = game.play(next_move.parse::<Move>().unwrap());
+        assert_eq!(matches!(move_result, MoveResult::Stopped(StoppedReason::NoChangeIn50Moves, _)), expected_is_50_move_draw);
+    }
+
+    #[rstest(
+    game_config, expected_fen,
+    case("white ♔e1 ♖h1 ♚e8", Some("4k3/8/8/8/8/8/8/4K2R b K - 0 1")),
+    case("e2-e4", Some("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 0 2")),
+    case("white ♔e1 ♜e4 ♚e8", None),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_play_null_move(
+        game_config: &str,
+        expected_fen: Option<&str>,
+    ) {
+        let game = game_config.parse::<Game>().unwrap();
+        let opt_fen = game.play_null_move().map(|null_move_game| null_move_game.get_fen());
+        assert_eq!(opt_fen.as_deref(), expected_fen);
+    }
+
+    #[rstest(
+    game_config, next_move, expected_fen,
+    case("", "g1-f3", "rnbqkbnr/pppppppp/8/8/8/5N2/PPPPPPPP/RNBQKB1R b KQkq - 1 1"),
+    case("e2-e4 d7-d5", "e4-d5", "rnbqkbnr/ppp1pppp/8/3P4/8/8/PPPP1PPP/RNBQKBNR b KQkq - 0 2"),
+    case("g1-f3 g8-f6 f3-g1 f6-g8 g1-f3 g8-f6 f3-g1", "f6-g8", "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 8 5"),
+    case("r3k2r/8/8/8/8/8/8/R3K2R b Kq - 7 12", "e8-d8", "r2k3r/8/8/8/8/8/8/R3K2R w K - 8 13"),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_get_fen_after(
+        game_config: &str,
+        next_move: &str,
+        expected_fen: &str,
+    ) {
+        let game = game_config.parse::<Game>().unwrap();
+        let next_move = next_move.parse::<Move>().unwrap();
+        assert_eq!(game.get_fen_after(next_move), expected_fen);
+        if let MoveResult::Ongoing(new_game, _) = game.play(next_move) {
+            assert_eq!(new_game.get_fen(), expected_fen);
+        }
+    }
+
+    #[rstest(
+    game_config, expected_moves,
+    case("", ""),
+    case("e2-e4 e7-e5 g1-f3", "e2-e4 e7-e5 g1-f3"),
+    case("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1", ""),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_get_moves_played(
+        game_config: &str,
+        expected_moves: &str,
+    ) {
+        let game = game_config.parse::<Game>().unwrap();
+        let expected_moves: Vec<Move> = expected_moves.split_whitespace().map(Move::from_code).collect();
+        assert_eq!(game.get_moves_played(), expected_moves);
+    }
+}
@@ -0,0 +1,124 @@
+use crate::base::{Move, MoveType, Moves, PromotionType};
+use crate::game::GameState;
+
+/**
+ * the number of legal move sequences of the given length (a way to verify the move generation).
+ * Like the perft numbers published elsewhere this ignores the draw rules (repetitions, 50 moves, insufficient material).
+ * The move generation only produces promotions to a queen or a knight (the others are never better),
+ * so the promotions to a rook and a bishop are added here to make the numbers comparable.
+ */
+pub fn perft(game_state: &GameState, depth: usize) -> u64 {
+    if depth == 0 {
+        return 1;
+    }
+    perft_with_moves(game_state, &game_state.get_reachable_moves(), depth)
+}
+
+/**
+ * perft split up by the first move (in the order the moves are generated), handy to find a bug in the move generation
+ */
+pub fn perft_divide(game_state: &GameState, depth: usize) -> Vec<(Move, u64)> {
+    if depth == 0 {
+        return vec![];
+    }
+    with_under_promotions(&game_state.get_reachable_moves()).into_iter().filter_map(|a_move| {
+        let (new_state, new_moves) = get_legal_state_after(game_state, a_move)?;
+        Some((a_move, perft_after(&new_state, &new_moves, depth - 1)))
+    }).collect()
+}
+
+fn perft_with_moves(game_state: &GameState, reachable_moves: &Moves, depth: usize) -> u64 {
+    with_under_promotions(reachable_moves).into_iter().map(|a_move| {
+        match get_legal_state_after(game_state, a_move) {
+            None => 0,
+            Some((new_state, new_moves)) => perft_after(&new_state, &new_moves, depth - 1),
+        }
+    }).sum()
+}
+
+/**
+ * the moves with a promotion to a rook and a bishop after every promotion to a queen
+ */
+fn with_under_promotions(reachable_moves: &Moves) -> Vec<Move> {
+    let mut moves: Vec<Move> = Vec::with_capacity(reachable_moves.len());
+    for a_move in reachable_moves.iter() {
+        moves.push(*a_move);
+        if a_move.move_type() == MoveType::PawnPromotion(PromotionType::Queen) {
+            for promotion_type in [PromotionType::Rook, PromotionType::Bishop] {
+                moves.push(Move::new(a_move.from(), a_move.to(), Some(MoveType::PawnPromotion(promotion_type))));
+            }
+        }
+    }
+    moves
+}
+
+fn perft_after(game_state: &GameState, reachable_moves: &Moves, depth: usize) -> u64 {
+    if depth == 0 {
+        1
+    } else {
+        perft_with_moves(game_state, reachable_moves, depth)
+    }
+}
+
+/**
+ * None if the move leaves the own king in check
+ */
+fn get_legal_state_after(game_state: &GameState, a_move: Move) -> Option<(GameState, Moves)> {
+    let (new_state, _) = game_state.do_move(a_move);
+    let new_moves = new_state.get_reachable_moves();
+    let passive_king_pos = new_state.get_passive_king_pos();
+    if new_moves.iter().any(|reachable_move| reachable_move.to() == passive_king_pos) {
+        None
+    } else {
+        Some((new_state, new_moves))
+    }
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+    use crate::game::Game;
+
+    //♔♕♗♘♖♙♚♛♝♞♜♟
+
+    #[rstest(
+    game_config, depth, expected_nodes,
+    case("", 0, 1),
+    case("", 1, 20),
+    case("", 2, 400),
+    case("", 3, 8902),
+    case("black ♔b6 ♙a7 ♚a8", 1, 0),
+    case("white ♔e1 ♖h1 ♚g8 ♝c3", 1, 4),
+    case("white ♔a1 ♚h8 ♖d4", 1, 17),
+    // the reference positions of the chess programming wiki
+    case("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1", 1, 48),
+    case("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1", 2, 2039),
+    case("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1", 3, 97862),
+    case("8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1", 3, 2812),
+    case("8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1", 4, 43238),
+    case("r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1", 1, 6),
+    case("r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1", 2, 264),
+    case("r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1", 3, 9467),
+    case("rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8", 1, 44),
+    case("rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8", 2, 1486),
+    case("rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8", 3, 62379),
+    case("r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10", 2, 2079),
+    case("r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10", 3, 89890),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_perft(game_config: &str, depth: usize, expected_nodes: u64) {
+        let game = game_config.parse::<Game>().unwrap();
+        assert_eq!(perft(game.get_game_state(), depth), expected_nodes);
+    }
+
+    #[test]
+    fn test_perft_divide_sums_up_to_perft() {
+        let game = "e2-e4 e7-e5".parse::<Game>().unwrap();
+        let divided = perft_divide(game.get_game_state(), 2);
+        assert_eq!(divided.len(), 29);
+        assert_eq!(divided.iter().map(|(_, nodes)| nodes).sum::<u64>(), perft(game.get_game_state(), 2));
+    }
+}
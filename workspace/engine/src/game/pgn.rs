@@ -0,0 +1,464 @@
+use crate::base::{ChessError, Color, ErrorKind};
+use crate::game::{parse_san, to_san, Game, GameTree, TreeNode};
+
+const MAX_PGN_LINE_LENGTH: usize = 80;
+const RESULTS: [&str; 4] = ["1-0", "0-1", "1/2-1/2", "*"];
+// the suffix annotations and the NAGs they stand for
+const SUFFIX_ANNOTATIONS: [(&str, u8); 6] = [("!", 1), ("?", 2), ("!!", 3), ("??", 4), ("!?", 5), ("?!", 6)];
+
+impl GameTree {
+    /**
+     * the tree in portable game notation with comments, NAGs and nested variations. The game ends with the
+     * Result tag (* if there is none). A root that isn't the starting position is given by the FEN tag.
+     */
+    pub fn to_pgn(&self) -> String {
+        let mut pgn = String::new();
+        for (key, value) in &self.tags {
+            pgn.push_str(&format_tag(key, value));
+        }
+        let root_fen = self.get_root_game().get_fen();
+        if root_fen != Game::classic().get_fen() {
+            pgn.push_str(&format_tag("SetUp", "1"));
+            pgn.push_str(&format_tag("FEN", &root_fen));
+        }
+        if !pgn.is_empty() {
+            pgn.push('\n');
+        }
+
+        let mut tokens: Vec<String> = vec![];
+        if let Some(comment) = self.get_root().get_comment() {
+            tokens.push(format_comment(comment));
+        }
+        write_line(self.get_root(), self.get_root_game(), &mut tokens);
+        let result = self.tags.iter().find(|(key, _)| key == "Result").map(|(_, value)| value.as_str()).unwrap_or("*");
+        tokens.push(result.to_string());
+
+        pgn.push_str(&wrap_tokens(&tokens));
+        pgn.push('\n');
+        pgn
+    }
+
+    /**
+     * reads the first game of the pgn (with comments, NAGs and nested variations).
+     * Suffix annotations like ! or ?! are read as their NAGs.
+     */
+    pub fn from_pgn(pgn: &str) -> Result<GameTree, ChessError> {
+        let tokens = tokenize(pgn)?;
+        let mut tags: Vec<(String, String)> = vec![];
+        let mut opt_fen: Option<String> = None;
+        for token in &tokens {
+            if let PgnToken::Tag(key, value) = token {
+                match key.as_str() {
+                    "FEN" => { opt_fen = Some(value.clone()); }
+                    "SetUp" => {}
+                    _ => { tags.push((key.clone(), value.clone())); }
+                }
+            }
+        }
+        let root_game = match &opt_fen {
+            None => Game::classic(),
+            Some(fen) => Game::from_fen(fen)?,
+        };
+        let mut tree = GameTree::new(root_game);
+        tree.tags = tags;
+
+        // the path of the node the next move is played from and the paths to go back to at the end of a variation
+        let mut current_path: Vec<usize> = vec![];
+        let mut variation_stack: Vec<Vec<usize>> = vec![];
+        for token in tokens {
+            match token {
+                PgnToken::Tag(_, _) => {}
+                PgnToken::Move(san) => {
+                    let (san, opt_suffix_nag) = split_suffix_annotation(&san);
+                    let game = tree.get_node(&current_path).and_then(TreeNode::get_game).ok_or_else(|| {
+                        pgn_format_err(&format!("the move {} follows a move that ended the game", san))
+                    })?;
+                    let a_move = parse_san(game, san)?;
+                    current_path = tree.add_move(&current_path, a_move)?;
+                    if let Some(nag) = opt_suffix_nag {
+                        tree.get_node_mut(&current_path).expect("the node was just added").add_nag(nag);
+                    }
+                }
+                PgnToken::Comment(comment) => {
+                    let node = tree.get_node_mut(&current_path).expect("the current path always exists");
+                    let joined_comment = match node.get_comment() {
+                        None => comment,
+                        Some(former_comment) => format!("{} {}", former_comment, comment),
+                    };
+                    node.set_comment(Some(joined_comment))?;
+                }
+                PgnToken::Nag(nag) => {
+                    tree.get_node_mut(&current_path).expect("the current path always exists").add_nag(nag);
+                }
+                PgnToken::VariationStart => {
+                    // a variation is an alternative to the latest move
+                    let Some((_, parent_path)) = current_path.split_last() else {
+                        return Err(pgn_format_err("a variation has to follow a move"));
+                    };
+                    let parent_path = parent_path.to_vec();
+                    variation_stack.push(current_path);
+                    current_path = parent_path;
+                }
+                PgnToken::VariationEnd => {
+                    current_path = variation_stack.pop().ok_or_else(|| pgn_format_err("a variation ends that didn't start"))?;
+                }
+                PgnToken::Result(result) => {
+                    // * (an unfinished game) is the default anyway
+                    if result != "*" && !tree.tags.iter().any(|(key, _)| key == "Result") {
+                        tree.tags.push(("Result".to_string(), result));
+                    }
+                    break;
+                }
+            }
+        }
+        if !variation_stack.is_empty() {
+            return Err(pgn_format_err("a variation isn't closed"));
+        }
+        Ok(tree)
+    }
+}
+
+/**
+ * the main line after the node, every move followed by its alternatives in parentheses
+ */
+fn write_line(node: &TreeNode, game: &Game, tokens: &mut Vec<String>) {
+    let mut node = node;
+    let mut game = game;
+    let mut needs_move_number = true;
+    while let Some((main_child, variations)) = node.get_children().split_first() {
+        needs_move_number = write_move(main_child, game, needs_move_number, tokens);
+        for variation in variations {
+            tokens.push("(".to_string());
+            write_move(variation, game, true, tokens);
+            if let Some(variation_game) = variation.get_game() {
+                write_line(variation, variation_game, tokens);
+            }
+            tokens.push(")".to_string());
+            needs_move_number = true;
+        }
+        match main_child.get_game() {
+            Some(main_game) => {
+                node = main_child;
+                game = main_game;
+            }
+            None => { break; }
+        }
+    }
+}
+
+/**
+ * the move (with its number if it's white's or needs_move_number is set), the NAGs and the comment.
+ * Returns if the next move needs its number (which is the case after a comment).
+ */
+fn write_move(node: &TreeNode, game_before: &Game, needs_move_number: bool, tokens: &mut Vec<String>) -> bool {
+    let a_move = node.get_move().expect("only the root has no move");
+    let move_number = game_before.get_half_moves_played() / 2 + 1;
+    let mut move_token = match game_before.get_game_state().turn_by {
+        Color::White => format!("{}. ", move_number),
+        Color::Black if needs_move_number => format!("{}... ", move_number),
+        Color::Black => String::new(),
+    };
+    move_token.push_str(&to_san(game_before, a_move));
+    tokens.push(move_token);
+    tokens.extend(node.get_nags().iter().map(|nag| format!("${}", nag)));
+    match node.get_comment() {
+        Some(comment) => {
+            tokens.push(format_comment(comment));
+            true
+        }
+        None => false,
+    }
+}
+
+fn format_tag(key: &str, value: &str) -> String {
+    format!("[{} \"{}\"]\n", key, value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn format_comment(comment: &str) -> String {
+    // TreeNode::set_comment makes sure that the comment doesn't contain the closing brace
+    format!("{{{}}}", comment)
+}
+
+fn wrap_tokens(tokens: &[String]) -> String {
+    let mut lines: Vec<String> = vec![];
+    let mut line = String::new();
+    for token in tokens {
+        if !line.is_empty() && line.len() + 1 + token.len() > MAX_PGN_LINE_LENGTH {
+            lines.push(line);
+            line = String::new();
+        }
+        // no space after an opening and before a closing parenthesis
+        if !line.is_empty() && !line.ends_with('(') && token != ")" {
+            line.push(' ');
+        }
+        line.push_str(token);
+    }
+    lines.push(line);
+    lines.join("\n")
+}
+
+fn split_suffix_annotation(san: &str) -> (&str, Option<u8>) {
+    let trimmed_san = san.trim_end_matches(['!', '?']);
+    let suffix = &san[trimmed_san.len()..];
+    let opt_nag = SUFFIX_ANNOTATIONS.iter().find(|(annotation, _)| *annotation == suffix).map(|(_, nag)| *nag);
+    (trimmed_san, opt_nag)
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+enum PgnToken {
+    Tag(String, String),
+    Move(String),
+    Comment(String),
+    Nag(u8),
+    VariationStart,
+    VariationEnd,
+    Result(String),
+}
+
+/**
+ * the tokens of the first game. It ends with its result or, if the result is missing, where the tags of the next game start.
+ */
+fn tokenize(pgn: &str) -> Result<Vec<PgnToken>, ChessError> {
+    let mut tokens: Vec<PgnToken> = vec![];
+    let mut char_iter = pgn.chars().peekable();
+    while let Some(character) = char_iter.next() {
+        match character {
+            _ if character.is_whitespace() => {}
+            '[' => {
+                if tokens.iter().any(|token| !matches!(token, PgnToken::Tag(_, _))) {
+                    break;
+                }
+                let tag = read_tag(&mut char_iter)?;
+                tokens.push(parse_tag(&tag)?);
+            }
+            '{' => {
+                let comment: String = char_iter.by_ref().take_while(|comment_char| *comment_char != '}').collect();
+                tokens.push(PgnToken::Comment(comment.split_whitespace().collect::<Vec<&str>>().join(" ")));
+            }
+            ';' => {
+                let comment: String = char_iter.by_ref().take_while(|comment_char| *comment_char != '\n').collect();
+                tokens.push(PgnToken::Comment(comment.trim().to_string()));
+            }
+            '(' => { tokens.push(PgnToken::VariationStart); }
+            ')' => { tokens.push(PgnToken::VariationEnd); }
+            _ => {
+                let mut symbol = String::from(character);
+                while let Some(symbol_char) = char_iter.peek() {
+                    if symbol_char.is_whitespace() || "[]{}();".contains(*symbol_char) {
+                        break;
+                    }
+                    symbol.push(*symbol_char);
+                    char_iter.next();
+                }
+                if let Some(token) = parse_symbol(&symbol)? {
+                    let is_result = matches!(token, PgnToken::Result(_));
+                    tokens.push(token);
+                    if is_result {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+/**
+ * the content of a tag up to its closing bracket (which is consumed). A bracket within the quoted value doesn't end the tag.
+ */
+fn read_tag(char_iter: &mut impl Iterator<Item=char>) -> Result<String, ChessError> {
+    let mut tag = String::new();
+    let mut is_in_quotes = false;
+    let mut is_escaped = false;
+    for tag_char in char_iter.by_ref() {
+        match tag_char {
+            ']' if !is_in_quotes => { return Ok(tag); }
+            '"' if !is_escaped => { is_in_quotes = !is_in_quotes; }
+            _ => {}
+        }
+        is_escaped = is_in_quotes && tag_char == '\\' && !is_escaped;
+        tag.push(tag_char);
+    }
+    Err(pgn_format_err(&format!("the tag [{} isn't closed", tag)))
+}
+
+/**
+ * None for a move number
+ */
+fn parse_symbol(symbol: &str) -> Result<Option<PgnToken>, ChessError> {
+    if RESULTS.contains(&symbol) {
+        return Ok(Some(PgnToken::Result(symbol.to_string())));
+    }
+    if let Some(nag_str) = symbol.strip_prefix('$') {
+        let nag = nag_str.parse::<u8>().map_err(|_| pgn_format_err(&format!("{} isn't a numeric annotation glyph", symbol)))?;
+        return Ok(Some(PgnToken::Nag(nag)));
+    }
+    // a move number can be written in front of the move without a space, e.g. "1.e4" or "3...Nf6"
+    let san = symbol.trim_start_matches(|symbol_char: char| symbol_char.is_ascii_digit() || symbol_char == '.');
+    if san.is_empty() {
+        return Ok(None);
+    }
+    let has_move_number = san.len() < symbol.len();
+    if has_move_number && !symbol[..symbol.len() - san.len()].contains('.') {
+        return Err(pgn_format_err(&format!("{} isn't a move", symbol)));
+    }
+    Ok(Some(PgnToken::Move(san.to_string())))
+}
+
+fn parse_tag(tag: &str) -> Result<PgnToken, ChessError> {
+    let (key, quoted_value) = tag.trim().split_once(char::is_whitespace)
+        .ok_or_else(|| pgn_format_err(&format!("a tag looks like [Event \"name\"] but was [{}]", tag)))?;
+    let value = quoted_value.trim().strip_prefix('"').and_then(|value| value.strip_suffix('"'))
+        .ok_or_else(|| pgn_format_err(&format!("the value of the tag {} isn't quoted", key)))?;
+    Ok(PgnToken::Tag(key.to_string(), unescape_tag_value(value)))
+}
+
+/**
+ * the reverse of the escaping in format_tag: a backslash keeps the following character (a quote or a backslash)
+ */
+fn unescape_tag_value(value: &str) -> String {
+    let mut unescaped = String::new();
+    let mut value_chars = value.chars();
+    while let Some(value_char) = value_chars.next() {
+        match value_char {
+            '\\' => unescaped.extend(value_chars.next()),
+            _ => unescaped.push(value_char),
+        }
+    }
+    unescaped
+}
+
+fn pgn_format_err(reason: &str) -> ChessError {
+    ChessError {
+        msg: format!("couldn't read the pgn: {}", reason),
+        kind: ErrorKind::IllegalFormat,
+    }
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+    use crate::base::Move;
+
+    const ANNOTATED_PGN: &str = r#"[Event "Analysis"]
+[White "Anderssen"]
+[Result "1-0"]
+
+{Starting position} 1. e4 e5 (1... c5 $1 {Sicilian} 2. Nf3 (2. c3) 2... d6)
+2. Nf3 $14 Nc6 3. Bb5 1-0
+"#;
+
+    #[test]
+    fn test_pgn_round_trip() {
+        let tree = GameTree::from_pgn(ANNOTATED_PGN).unwrap();
+        assert_eq!(tree.to_pgn(), ANNOTATED_PGN);
+        assert_eq!(tree.tags, vec![
+            ("Event".to_string(), "Analysis".to_string()),
+            ("White".to_string(), "Anderssen".to_string()),
+            ("Result".to_string(), "1-0".to_string()),
+        ]);
+        assert_eq!(tree.get_root().get_comment(), Some("Starting position"));
+        let sicilian = tree.get_node(&[0, 1]).unwrap();
+        assert_eq!(sicilian.get_move(), Some(Move::from_code("c7-c5")));
+        assert_eq!(sicilian.get_nags(), &[1]);
+        assert_eq!(sicilian.get_comment(), Some("Sicilian"));
+        assert_eq!(tree.get_moves_to(&[0, 1, 1]), Some(vec![Move::from_code("e2-e4"), Move::from_code("c7-c5"), Move::from_code("c2-c3")]));
+    }
+
+    #[test]
+    fn test_from_pgn_with_other_notations() {
+        let pgn = "1.e4 e5!? 2.Nf3 {a ; comment} ; a comment until the end of the line\n 2...Nc6? (2...d6!) 3. Bc4 *";
+        let tree = GameTree::from_pgn(pgn).unwrap();
+        assert_eq!(tree.get_main_line().len(), 5);
+        assert_eq!(tree.get_node(&[0, 0]).unwrap().get_nags(), &[5]);
+        assert_eq!(tree.get_node(&[0, 0, 0]).unwrap().get_comment(), Some("a ; comment a comment until the end of the line"));
+        assert_eq!(tree.get_node(&[0, 0, 0, 0]).unwrap().get_nags(), &[2]);
+        assert_eq!(tree.get_node(&[0, 0, 0, 1]).unwrap().get_nags(), &[1]);
+        assert_eq!(
+            tree.to_pgn(),
+            "1. e4 e5 $5 2. Nf3 {a ; comment a comment until the end of the line} 2... Nc6 $2\n(2... d6 $1) 3. Bc4 *\n",
+        );
+    }
+
+    #[test]
+    fn test_pgn_with_fen() {
+        let pgn = "[SetUp \"1\"]\n[FEN \"6k1/5ppp/8/8/8/8/5PPP/R5K1 w - - 0 30\"]\n\n30. Ra8# *\n";
+        let tree = GameTree::from_pgn(pgn).unwrap();
+        assert_eq!(tree.get_root_game().get_fen(), "6k1/5ppp/8/8/8/8/5PPP/R5K1 w - - 0 30");
+        assert_eq!(tree.get_main_line(), vec![Move::from_code("a1-a8")]);
+        assert!(tree.tags.is_empty());
+        assert_eq!(tree.to_pgn(), pgn);
+    }
+
+    #[rstest(
+    pgn,
+    case("[Event \"First\"]\n\n1. e4 e5 1-0\n\n[Event \"Second\"]\n[SetUp \"1\"]\n[FEN \"6k1/5ppp/8/8/8/8/5PPP/R5K1 w - - 0 30\"]\n\n30. Ra8# 1-0\n"),
+    case("[Event \"First\"]\n\n1. e4 e5\n\n[Event \"Second\"]\n[SetUp \"1\"]\n[FEN \"6k1/5ppp/8/8/8/8/5PPP/R5K1 w - - 0 30\"]\n\n30. Ra8# 1-0\n"),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_pgn_with_several_games_reads_the_first(pgn: &str) {
+        let tree = GameTree::from_pgn(pgn).unwrap();
+        assert_eq!(tree.get_root_game().get_fen(), Game::classic().get_fen());
+        assert_eq!(tree.get_main_line(), vec![Move::from_code("e2-e4"), Move::from_code("e7-e5")]);
+        assert_eq!(tree.tags.iter().filter(|(key, _)| key == "Event").collect::<Vec<_>>(), vec![&("Event".to_string(), "First".to_string())]);
+    }
+
+    #[rstest(
+    value,
+    case("Analysis [draft]"),
+    case("a ] in the middle"),
+    case(r#"quoted "]" bracket"#),
+    case(r#"ends with a backslash \"#),
+    case(r#"a backslash \" before a quote"#),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_tag_value_round_trip(value: &str) {
+        let mut tree = GameTree::new(Game::classic());
+        tree.tags.push(("Event".to_string(), value.to_string()));
+        tree.add_move(&[], Move::from_code("e2-e4")).unwrap();
+        let pgn = tree.to_pgn();
+        let read_tree = GameTree::from_pgn(&pgn).unwrap();
+        assert_eq!(read_tree.tags, tree.tags, "pgn: {}", pgn);
+        assert_eq!(read_tree.get_main_line(), vec![Move::from_code("e2-e4")]);
+    }
+
+    #[test]
+    fn test_pgn_ending_with_repetition() {
+        let pgn = "1. Nf3 Nf6 2. Ng1 Ng8 3. Nf3 Nf6 4. Ng1 Ng8 *\n";
+        let tree = GameTree::from_pgn(pgn).unwrap();
+        assert_eq!(tree.get_main_line().len(), 8);
+        assert_eq!(tree.to_pgn(), pgn);
+        assert!(GameTree::from_pgn("1. Nf3 Nf6 2. Ng1 Ng8 3. Nf3 Nf6 4. Ng1 Ng8 5. Nf3 *").is_err());
+    }
+
+    #[test]
+    fn test_new_tree_to_pgn() {
+        let mut tree = GameTree::new(Game::classic());
+        assert_eq!(tree.to_pgn(), "*\n");
+        let path = tree.add_move(&[], Move::from_code("d2-d4")).unwrap();
+        tree.add_move(&path, Move::from_code("g8-f6")).unwrap();
+        tree.add_move(&path, Move::from_code("d7-d5")).unwrap();
+        tree.get_node_mut(&path).unwrap().set_comment(Some("closed {or not".to_string())).unwrap();
+        assert_eq!(tree.to_pgn(), "1. d4 {closed {or not} 1... Nf6 (1... d5) *\n");
+        assert_eq!(GameTree::from_pgn(&tree.to_pgn()).unwrap().to_pgn(), tree.to_pgn());
+    }
+
+    #[rstest(
+    pgn,
+    case("1. e5 *"),
+    case("1. e4 e5 (2. Nf3 *"),
+    case("(1. e4) *"),
+    case("1. e4 e5) *"),
+    case("[Event Analysis] 1. e4 *"),
+    case("[Event \"Analysis] 1. e4 *"),
+    case("1. e4 $x *"),
+    case("12e4 *"),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_invalid_pgn(pgn: &str) {
+        assert!(GameTree::from_pgn(pgn).is_err(), "{:?}", GameTree::from_pgn(pgn).map(|tree| tree.to_pgn()));
+    }
+}
@@ -0,0 +1,277 @@
+use crate::base::{CastlingType, ChessError, Color, ErrorKind, Move, MoveType, Position, PromotionType};
+use crate::figure::{Figure, FigureType};
+use crate::figure::functions::allowed::get_legal_moves_of;
+use crate::game::{Game, MoveResult};
+
+/**
+ * finds the legal move given in standard algebraic notation (e.g. "Nf3", "exd5", "e8=Q+", "O-O-O", "R1e2").
+ * Check and annotation suffixes like +, #, ! and ? are ignored.
+ * Promotions to a rook or a bishop aren't supported by the engine and lead to an error.
+ */
+pub fn parse_san(game: &Game, san: &str) -> Result<Move, ChessError> {
+    let trimmed_san = san.trim().trim_end_matches(['+', '#', '!', '?']).trim_end_matches("e.p.").trim_end();
+    let legal_moves = get_legal_moves_of(game);
+
+    let castling_type = match trimmed_san {
+        "O-O" | "0-0" => Some(CastlingType::KingSide),
+        "O-O-O" | "0-0-0" => Some(CastlingType::QueenSide),
+        _ => None,
+    };
+    if let Some(castling_type) = castling_type {
+        return legal_moves.into_iter()
+            .find(|a_move| a_move.move_type() == MoveType::Castling(castling_type))
+            .ok_or_else(|| illegal_move_err(game, san));
+    }
+
+    let san_move = SanMove::parse(trimmed_san).ok_or_else(|| ChessError {
+        msg: format!("a move in standard algebraic notation looks like Nf3, exd5, e8=Q or O-O but was {}", san),
+        kind: ErrorKind::IllegalFormat,
+    })?;
+    if matches!(san_move.opt_promotion_type, Some(PromotionType::Rook) | Some(PromotionType::Bishop)) {
+        return Err(ChessError {
+            msg: format!("the underpromotion {} is unsupported, pawns can only be promoted to a queen or a knight", san),
+            kind: ErrorKind::IllegalFormat,
+        });
+    }
+    let board = &game.get_game_state().board;
+    let mut matching_moves = legal_moves.into_iter().filter(|a_move| {
+        let is_castling = matches!(a_move.move_type(), MoveType::Castling(_));
+        let opt_promotion_type = match a_move.move_type() {
+            MoveType::PawnPromotion(promotion_type) => Some(promotion_type),
+            _ => None,
+        };
+        !is_castling &&
+            a_move.to() == san_move.to &&
+            board.get_figure(a_move.from()).map(|figure| figure.fig_type) == Some(san_move.fig_type) &&
+            san_move.opt_from_column.is_none_or(|column| a_move.from().column() == column) &&
+            san_move.opt_from_row.is_none_or(|row| a_move.from().row() == row) &&
+            opt_promotion_type == san_move.opt_promotion_type
+    });
+    match (matching_moves.next(), matching_moves.next()) {
+        (Some(a_move), None) => Ok(a_move),
+        (Some(_), Some(_)) => Err(ChessError {
+            msg: format!("the move {} is ambiguous in {}", san, game.get_fen()),
+            kind: ErrorKind::IllegalFormat,
+        }),
+        (None, _) => Err(illegal_move_err(game, san)),
+    }
+}
+
+/**
+ * the legal move in standard algebraic notation (with + for check and # for checkmate)
+ */
+pub fn to_san(game: &Game, a_move: Move) -> String {
+    let mut san = match a_move.move_type() {
+        MoveType::Castling(CastlingType::KingSide) => "O-O".to_string(),
+        MoveType::Castling(CastlingType::QueenSide) => "O-O-O".to_string(),
+        _ => get_san_without_suffix(game, a_move),
+    };
+    match game.play(a_move) {
+        MoveResult::Ongoing(new_game, _) => {
+            if new_game.is_active_king_in_check() {
+                san.push(if get_legal_moves_of(&new_game).is_empty() { '#' } else { '+' });
+            }
+        }
+        MoveResult::Stopped(_, new_game_state) => {
+            // a game ended by a draw rule can't be checkmate
+            if new_game_state.is_active_king_in_check(Some(a_move)) {
+                san.push('+');
+            }
+        }
+    }
+    san
+}
+
+fn get_san_without_suffix(game: &Game, a_move: Move) -> String {
+    let board = &game.get_game_state().board;
+    let from = a_move.from();
+    let fig_type = board.get_figure(from).map(|figure| figure.fig_type).unwrap_or(FigureType::Pawn);
+    let is_capture = !board.is_empty(a_move.to()) || a_move.move_type() == MoveType::EnPassant;
+
+    let mut san = String::new();
+    if fig_type == FigureType::Pawn {
+        if is_capture {
+            san.push(column_to_char(from.column()));
+        }
+    } else {
+        san.push(get_figure_char(fig_type));
+        let ambiguous_froms: Vec<Position> = get_legal_moves_of(game).into_iter()
+            .filter(|other_move| other_move.to() == a_move.to() && other_move.from() != from)
+            .filter(|other_move| !matches!(other_move.move_type(), MoveType::Castling(_)))
+            .filter(|other_move| board.get_figure(other_move.from()).map(|figure| figure.fig_type) == Some(fig_type))
+            .map(|other_move| other_move.from())
+            .collect();
+        if !ambiguous_froms.is_empty() {
+            if ambiguous_froms.iter().all(|other_from| other_from.column() != from.column()) {
+                san.push(column_to_char(from.column()));
+            } else if ambiguous_froms.iter().all(|other_from| other_from.row() != from.row()) {
+                san.push(row_to_char(from.row()));
+            } else {
+                san.push_str(&from.to_string());
+            }
+        }
+    }
+    if is_capture {
+        san.push('x');
+    }
+    san.push_str(&a_move.to().to_string());
+    if let MoveType::PawnPromotion(promotion_type) = a_move.move_type() {
+        san.push('=');
+        san.push(get_figure_char(promotion_type.get_figure_type()));
+    }
+    san
+}
+
+struct SanMove {
+    fig_type: FigureType,
+    opt_from_column: Option<i8>,
+    opt_from_row: Option<i8>,
+    to: Position,
+    opt_promotion_type: Option<PromotionType>,
+}
+
+impl SanMove {
+    fn parse(san: &str) -> Option<SanMove> {
+        if !san.is_ascii() {
+            return None;
+        }
+        let (san, fig_type) = match san.chars().next()? {
+            'K' => (&san[1..], FigureType::King),
+            'Q' => (&san[1..], FigureType::Queen),
+            'R' => (&san[1..], FigureType::Rook),
+            'B' => (&san[1..], FigureType::Bishop),
+            'N' => (&san[1..], FigureType::Knight),
+            _ => (san, FigureType::Pawn),
+        };
+        // the promotion can be given with or without = (e8=Q or e8Q)
+        let (san, opt_promotion_type) = match san.chars().last()? {
+            'Q' => (&san[..san.len() - 1], Some(PromotionType::Queen)),
+            'R' => (&san[..san.len() - 1], Some(PromotionType::Rook)),
+            'B' => (&san[..san.len() - 1], Some(PromotionType::Bishop)),
+            'N' => (&san[..san.len() - 1], Some(PromotionType::Knight)),
+            _ => (san, None),
+        };
+        let san = san.strip_suffix('=').unwrap_or(san);
+        if san.len() < 2 || (opt_promotion_type.is_some() && fig_type != FigureType::Pawn) {
+            return None;
+        }
+        let (disambiguation, to_str) = san.split_at(san.len() - 2);
+        let to = to_str.parse::<Position>().ok()?;
+        let disambiguation = disambiguation.strip_suffix('x').unwrap_or(disambiguation);
+        let mut opt_from_column: Option<i8> = None;
+        let mut opt_from_row: Option<i8> = None;
+        for disambiguation_char in disambiguation.chars() {
+            match disambiguation_char {
+                'a'..='h' if opt_from_column.is_none() => { opt_from_column = Some(disambiguation_char as i8 - 'a' as i8); }
+                '1'..='8' if opt_from_row.is_none() => { opt_from_row = Some(disambiguation_char as i8 - '1' as i8); }
+                _ => { return None; }
+            }
+        }
+        Some(SanMove { fig_type, opt_from_column, opt_from_row, to, opt_promotion_type })
+    }
+}
+
+fn get_figure_char(fig_type: FigureType) -> char {
+    Figure { fig_type, color: Color::White }.get_fen_char()
+}
+
+fn column_to_char(column: i8) -> char {
+    (b'a' + column as u8) as char
+}
+
+fn row_to_char(row: i8) -> char {
+    (b'1' + row as u8) as char
+}
+
+fn illegal_move_err(game: &Game, san: &str) -> ChessError {
+    ChessError {
+        msg: format!("move {} isn't possible in {}", san, game.get_fen()),
+        kind: ErrorKind::IllegalMove,
+    }
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    //♔♕♗♘♖♙♚♛♝♞♜♟
+
+    #[rstest(
+    game_config, san, expected_move_str,
+    case("", "e4", "e2-e4"),
+    case("", "Nf3", "g1-f3"),
+    case("e2-e4 d7-d5", "exd5", "e4-d5"),
+    case("e2-e4 d7-d5", "Bb5+", "f1-b5"),
+    case("a2-a4 a7-a6 a4-a5 b7-b5", "axb6", "a5eb6"),
+    case("a2-a4 a7-a6 a4-a5 b7-b5", "axb6 e.p.", "a5eb6"),
+    case("white ♔e1 ♖h1 ♚g8", "O-O", "e1cg1"),
+    case("white ♔e1 ♖a1 ♚g8", "0-0-0", "e1Cc1"),
+    case("white ♔e1 ♙a7 ♚g8", "a8=Q+", "a7Qa8"),
+    case("white ♔e1 ♙a7 ♚g8", "a8N", "a7Ka8"),
+    case("white ♔e1 ♘b1 ♘f1 ♙h2 ♚g8", "Nbd2", "b1-d2"),
+    case("white ♔e1 ♖a1 ♖a3 ♚g8", "R1a2", "a1-a2"),
+    case("white ♔e1 ♕a1 ♕a3 ♕c1 ♚g8", "Qa1b2", "a1-b2"),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_parse_san(game_config: &str, san: &str, expected_move_str: &str) {
+        let game = game_config.parse::<Game>().unwrap();
+        assert_eq!(parse_san(&game, san).unwrap(), expected_move_str.parse::<Move>().unwrap());
+    }
+
+    #[rstest(
+    game_config, san,
+    case("", "e5"),
+    case("", "Ke2"),
+    case("", "Nd4"),
+    case("", "O-O"),
+    case("white ♔e1 ♘b1 ♘f1 ♙h2 ♚g8", "Nd2"),
+    case("", "Xe4"),
+    case("", "Nf3=Q"),
+    case("", "e"),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_parse_san_fails(game_config: &str, san: &str) {
+        let game = game_config.parse::<Game>().unwrap();
+        assert!(parse_san(&game, san).is_err(), "{:?}", parse_san(&game, san));
+    }
+
+    #[rstest(
+    san,
+    case("a8=R"),
+    case("a8B+"),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_parse_san_fails_on_unsupported_underpromotion(san: &str) {
+        let game = "white ♔e1 ♙a7 ♚h8".parse::<Game>().unwrap();
+        let err = parse_san(&game, san).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::IllegalFormat);
+        assert!(err.msg.contains("underpromotion"), "{}", err.msg);
+    }
+
+    #[rstest(
+    game_config, move_str, expected_san,
+    case("", "e2-e4", "e4"),
+    case("", "b1-c3", "Nc3"),
+    case("e2-e4 d7-d5", "e4-d5", "exd5"),
+    case("e2-e4 d7-d5", "f1-b5", "Bb5+"),
+    case("a2-a4 a7-a6 a4-a5 b7-b5", "a5eb6", "axb6"),
+    case("white ♔e1 ♖h1 ♚g8", "e1cg1", "O-O"),
+    case("white ♔e1 ♖a1 ♚g8", "e1Cc1", "O-O-O"),
+    case("white ♔e1 ♙a7 ♚g8", "a7Ka8", "a8=N"),
+    case("white ♔e1 ♙a7 ♚h8", "a7Qa8", "a8=Q+"),
+    case("white ♔e1 ♘b1 ♘f1 ♙h2 ♚g8", "f1-d2", "Nfd2"),
+    case("white ♔e1 ♖a1 ♖a3 ♚g8", "a3-a2", "R3a2"),
+    case("white ♔e1 ♕a1 ♕a3 ♕c1 ♚g8", "a1-b2", "Qa1b2"),
+    case("white ♔g3 ♖d2 ♚g1 ♙c2 ♙d3", "d2-d1", "Rd1#"),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_to_san(game_config: &str, move_str: &str, expected_san: &str) {
+        let game = game_config.parse::<Game>().unwrap();
+        let a_move = move_str.parse::<Move>().unwrap();
+        assert_eq!(to_san(&game, a_move), expected_san);
+        assert_eq!(parse_san(&game, expected_san).unwrap(), a_move);
+    }
+}
@@ -0,0 +1,138 @@
+use std::time::Duration;
+use engine_core::engine::limits::SearchLimits;
+use engine_core::engine::min_max::pruner::*;
+use engine_core::engine::search_config::SearchConfig;
+use engine_core::engine::static_eval::EvalParams;
+
+static PRUNERS_BY_NAME: [(&str, &Pruner); 9] = [
+    ("L0", &PRUNER_L0),
+    ("L1", &PRUNER_L1),
+    ("L2", &PRUNER_L2),
+    ("L3", &PRUNER_L3),
+    ("1_1_3_3", &PRUNER_1_1_3_3),
+    ("1_2_3_4", &PRUNER_1_2_3_4),
+    ("2_2_3_4", &PRUNER_2_2_3_4),
+    ("2_4_5_8", &PRUNER_2_4_5_8),
+    ("3_4_4_6", &PRUNER_3_4_4_6),
+];
+
+pub const DEFAULT_ENGINE_SPEC: &str = "pruner=1_1_3_3";
+
+/**
+ * one of the two engines of a match
+ */
+#[derive(Debug, Clone)]
+pub struct EngineConfig {
+    pub name: String,
+    pub search_config: SearchConfig,
+    pub limits: SearchLimits,
+}
+
+impl EngineConfig {
+    /**
+     * parses a comma separated list of settings like "pruner=2_2_3_4,nmp=2,params=tuned.json,nodes=50000".
+     * Settings that aren't given keep their default (see the usage of the match runner).
+     */
+    pub fn from_spec(spec: &str) -> Result<EngineConfig, String> {
+        let mut pruner = PRUNER_1_1_3_3;
+        let mut opt_null_move_reduction: Option<usize> = None;
+        let mut opt_late_move_reduction: Option<(usize, usize)> = None;
+        let mut opt_eval_params: Option<EvalParams> = None;
+        let mut contempt_in_centipawns: i32 = 0;
+        let mut limits = SearchLimits::default();
+        let mut opt_name: Option<String> = None;
+
+        for setting in spec.split(',').map(str::trim).filter(|setting| !setting.is_empty()) {
+            let Some((key, value)) = setting.split_once('=') else {
+                return Err(format!("expected a setting like key=value but got {}", setting));
+            };
+            match key {
+                "name" => { opt_name = Some(value.to_string()); }
+                "pruner" => { pruner = get_pruner_by_name(value)?; }
+                "nmp" => { opt_null_move_reduction = Some(parse_number(key, value)?); }
+                "lmr" => {
+                    let (after_nr_of_moves, half_step_reduction) = value.split_once(':')
+                        .ok_or_else(|| format!("lmr has to be given as <after_nr_of_moves>:<half_step_reduction> but was {}", value))?;
+                    opt_late_move_reduction = Some((parse_number(key, after_nr_of_moves)?, parse_number(key, half_step_reduction)?));
+                }
+                "params" => { opt_eval_params = Some(EvalParams::from_file(value).map_err(|err| err.to_string())?); }
+                "contempt" => {
+                    contempt_in_centipawns = value.parse::<i32>().map_err(|_| format!("contempt has to be a number of centipawns but was {}", value))?;
+                }
+                "time" => { limits = limits.with_time_limit(Duration::from_millis(parse_number(key, value)?)); }
+                "nodes" => { limits = limits.with_node_limit(parse_number(key, value)?); }
+                _ => { return Err(format!("unknown engine setting {}", key)); }
+            }
+        }
+
+        if let Some(half_step_reduction) = opt_null_move_reduction {
+            pruner = pruner.with_null_move_pruning(half_step_reduction);
+        }
+        if let Some((after_nr_of_moves, half_step_reduction)) = opt_late_move_reduction {
+            pruner = pruner.with_late_move_reductions(after_nr_of_moves, half_step_reduction);
+        }
+        let mut search_config = SearchConfig::new(pruner).with_contempt(contempt_in_centipawns);
+        if let Some(eval_params) = opt_eval_params {
+            search_config = search_config.with_eval_params(eval_params);
+        }
+        Ok(EngineConfig {
+            name: opt_name.unwrap_or_else(|| spec.to_string()),
+            search_config,
+            limits,
+        })
+    }
+}
+
+fn get_pruner_by_name(name: &str) -> Result<Pruner, String> {
+    PRUNERS_BY_NAME.iter()
+        .find(|(pruner_name, _)| *pruner_name == name)
+        .map(|(_, pruner)| **pruner)
+        .ok_or_else(|| {
+            let names: Vec<&str> = PRUNERS_BY_NAME.iter().map(|(pruner_name, _)| *pruner_name).collect();
+            format!("unknown pruner {}, known are: {}", name, names.join(", "))
+        })
+}
+
+fn parse_number<T: std::str::FromStr>(key: &str, value: &str) -> Result<T, String> {
+    value.parse::<T>().map_err(|_| format!("{} has to be a positive number but was {}", key, value))
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[test]
+    fn test_default_spec() {
+        let engine = EngineConfig::from_spec(DEFAULT_ENGINE_SPEC).unwrap();
+        assert_eq!(engine.name, DEFAULT_ENGINE_SPEC);
+        assert_eq!(engine.search_config.pruner, PRUNER_1_1_3_3);
+        assert!(engine.search_config.opt_eval_params.is_none());
+        assert_eq!(engine.limits, SearchLimits::default());
+    }
+
+    #[test]
+    fn test_full_spec() {
+        let engine = EngineConfig::from_spec("name=new, pruner=L2, nmp=2, lmr=4:1, contempt=-15, time=250, nodes=10000").unwrap();
+        assert_eq!(engine.name, "new");
+        assert_eq!(engine.search_config.pruner, PRUNER_L2.with_null_move_pruning(2).with_late_move_reductions(4, 1));
+        assert_eq!(engine.search_config.contempt_in_centipawns, -15);
+        assert_eq!(engine.limits, SearchLimits::default().with_time_limit(Duration::from_millis(250)).with_node_limit(10000));
+    }
+
+    #[rstest(
+    spec,
+    case("pruner=L9"),
+    case("pruner"),
+    case("nodes=-3"),
+    case("lmr=4"),
+    case("depth=3"),
+    case("params=does_not_exist.json"),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_invalid_spec(spec: &str) {
+        assert!(EngineConfig::from_spec(spec).is_err());
+    }
+}
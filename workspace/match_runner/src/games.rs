@@ -0,0 +1,111 @@
+use engine_core::base::{Color, Move};
+use engine_core::engine::evaluations::frontend::{GameEndResult, GameEvaluation};
+use engine_core::engine::limits::evaluate_game_with_limits;
+use engine_core::engine::search_config::MoveSelection;
+use engine_core::figure::functions::allowed::get_allowed_moves_of;
+use engine_core::game::{Game, MoveResult, StoppedReason};
+use crate::engines::EngineConfig;
+
+/**
+ * a game that neither ends by checkmate nor by a draw rule before is adjudicated as draw
+ */
+pub const MAX_HALF_MOVES_PER_GAME: usize = 400;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Outcome {
+    Win(Color),
+    Draw,
+}
+
+#[derive(Debug, Clone)]
+pub struct GameRecord {
+    pub outcome: Outcome,
+    pub reason: String,
+    pub moves: Vec<Move>,
+}
+
+/**
+ * plays a game from the opening position to its end. The seed makes the choice among equally good moves reproducible.
+ */
+pub fn play_game(opening: &str, white: &EngineConfig, black: &EngineConfig, seed: u64) -> Result<GameRecord, String> {
+    let mut game = opening.parse::<Game>().map_err(|err| format!("couldn't read the opening {}: {}", opening, err))?;
+    let mut moves: Vec<Move> = vec![];
+    loop {
+        let active_color = game.get_game_state().turn_by;
+        let end_by = |outcome: Outcome, reason: &str, moves: Vec<Move>| Ok(GameRecord { outcome, reason: reason.to_string(), moves });
+        if get_allowed_moves_of(&game).is_empty() {
+            return if game.is_active_king_in_check() {
+                end_by(Outcome::Win(active_color.toggle()), "checkmate", moves)
+            } else {
+                end_by(Outcome::Draw, "stalemate", moves)
+            };
+        }
+        if moves.len() >= MAX_HALF_MOVES_PER_GAME {
+            return end_by(Outcome::Draw, "adjudicated because of the game length", moves);
+        }
+
+        let engine = if active_color == Color::White { white } else { black };
+        let search_config = engine.search_config.with_move_selection(MoveSelection::Seeded(seed.wrapping_add(moves.len() as u64)));
+        let next_move = match evaluate_game_with_limits(&game, &search_config, engine.limits) {
+            GameEvaluation::MoveToPlay(next_move, _) => next_move,
+            GameEvaluation::GameEnded(GameEndResult::EngineLost) => { return end_by(Outcome::Win(active_color.toggle()), "checkmate", moves); }
+            GameEvaluation::GameEnded(GameEndResult::EngineWon) => { return end_by(Outcome::Win(active_color), "checkmate", moves); }
+            GameEvaluation::GameEnded(GameEndResult::Draw(_)) => { return end_by(Outcome::Draw, "draw", moves); }
+            GameEvaluation::Err(err) => { return Err(format!("{} failed to find a move: {}", engine.name, err)); }
+        };
+        moves.push(next_move);
+        match game.play(next_move) {
+            MoveResult::Ongoing(next_game, _) => { game = *next_game; }
+            MoveResult::Stopped(stopped_reason, _) => {
+                let outcome = match stopped_reason {
+                    // an engine that leaves its king in check loses
+                    StoppedReason::KingInCheckAfterMove => Outcome::Win(active_color.toggle()),
+                    StoppedReason::InsufficientMaterial | StoppedReason::ThreeTimesRepetition | StoppedReason::NoChangeIn50Moves => Outcome::Draw,
+                };
+                return end_by(outcome, &format!("{:?}", stopped_reason), moves);
+            }
+        }
+    }
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    //♔♕♗♘♖♙♚♛♝♞♜♟
+
+    #[rstest(
+    opening, expected_outcome, expected_nr_of_moves,
+    case("white ♔g3 ♖d2 ♚g1 ♙c2 ♙d3", Outcome::Win(Color::White), 1),
+    case("black ♔g3 ♖d1 ♚g1 ♙c2 ♙d3", Outcome::Win(Color::White), 0),
+    case("black ♔b6 ♙a7 ♚a8", Outcome::Draw, 0),
+    case("white ♔b6 ♖h1 ♚a8 ♜h8", Outcome::Win(Color::White), 1),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_play_game(opening: &str, expected_outcome: Outcome, expected_nr_of_moves: usize) {
+        let engine = EngineConfig::from_spec("pruner=L1").unwrap();
+        let record = play_game(opening, &engine, &engine, 0).unwrap();
+        assert_eq!(record.outcome, expected_outcome, "{:?}", record);
+        assert_eq!(record.moves.len(), expected_nr_of_moves, "{:?}", record);
+    }
+
+    #[test]
+    fn test_play_game_is_reproducible() {
+        let opening = "white ♔e1 ♖a1 ♚e8 ♜h8 ♙c2 ♟c7";
+        let white = EngineConfig::from_spec("pruner=L0").unwrap();
+        let black = EngineConfig::from_spec("pruner=L1").unwrap();
+        let first_record = play_game(opening, &white, &black, 7).unwrap();
+        let second_record = play_game(opening, &white, &black, 7).unwrap();
+        assert_eq!(first_record.moves, second_record.moves);
+        assert_eq!(first_record.outcome, second_record.outcome);
+    }
+
+    #[test]
+    fn test_invalid_opening() {
+        let engine = EngineConfig::from_spec("pruner=L0").unwrap();
+        assert!(play_game("e2-e5", &engine, &engine, 0).is_err());
+    }
+}
@@ -0,0 +1,245 @@
+use std::env;
+use std::fs;
+use std::process::ExitCode;
+use engine_core::base::Color;
+use engine_core::game::Game;
+use crate::engines::{EngineConfig, DEFAULT_ENGINE_SPEC};
+use crate::games::{play_game, Outcome};
+use crate::stats::{MatchStats, SprtParams, SprtVerdict};
+
+mod engines;
+mod games;
+mod stats;
+
+const USAGE: &str = "usage: voidchess_match [--engine1 <spec>] [--engine2 <spec>] [--games <n>] [--openings <file>] [--seed <n>] [--sprt <elo0>,<elo1>[,<alpha>,<beta>]]
+
+Plays n games (default 20) between two engine configurations. Every opening is played twice with swapped colors.
+The results are from the point of view of engine1. With --sprt the match stops as soon as the SPRT accepts a hypothesis
+(the verdict is always reported, by default for elo0=0, elo1=10, alpha=beta=0.05).
+
+An engine spec is a comma separated list of settings (default \"pruner=1_1_3_3\"):
+  name=<name>          the name in the report (default: the spec)
+  pruner=<preset>      L0, L1, L2, L3, 1_1_3_3, 1_2_3_4, 2_2_3_4, 2_4_5_8 or 3_4_4_6
+  nmp=<n>              null move pruning with a reduction of n half steps
+  lmr=<after>:<n>      late move reductions of n half steps after the first <after> moves
+  params=<file>        evaluation weights (.json or .toml), otherwise the default ones
+  contempt=<n>         contempt for draws in centipawns
+  time=<ms>            time limit per move
+  nodes=<n>            node limit per move
+The limits are checked between the steps of an iterative deepening up to the pruner.
+
+The openings file contains one fen per line (lines starting with # are ignored), by default a suite of 10 openings is used.";
+
+const DEFAULT_OPENINGS: &str = include_str!("../openings.txt");
+
+struct MatchArgs {
+    engine1: EngineConfig,
+    engine2: EngineConfig,
+    nr_of_games: u32,
+    openings: Vec<String>,
+    seed: u64,
+    sprt_params: SprtParams,
+    stop_on_sprt_verdict: bool,
+}
+
+fn parse_args(args: &[String]) -> Result<MatchArgs, String> {
+    let mut engine1_spec = DEFAULT_ENGINE_SPEC.to_string();
+    let mut engine2_spec = DEFAULT_ENGINE_SPEC.to_string();
+    let mut nr_of_games: u32 = 20;
+    let mut opt_openings_path: Option<String> = None;
+    let mut seed: u64 = 0;
+    let mut sprt_params = SprtParams::default();
+    let mut stop_on_sprt_verdict = false;
+
+    let mut arg_iter = args.iter();
+    while let Some(arg) = arg_iter.next() {
+        let mut next_value = |option: &str| arg_iter.next().cloned().ok_or_else(|| format!("missing value for {}", option));
+        match arg.as_str() {
+            "--engine1" => { engine1_spec = next_value("--engine1")?; }
+            "--engine2" => { engine2_spec = next_value("--engine2")?; }
+            "--games" => {
+                let games_str = next_value("--games")?;
+                nr_of_games = games_str.parse::<u32>().map_err(|_| format!("games has to be a positive number but was {}", games_str))?;
+            }
+            "--openings" => { opt_openings_path = Some(next_value("--openings")?); }
+            "--seed" => {
+                let seed_str = next_value("--seed")?;
+                seed = seed_str.parse::<u64>().map_err(|_| format!("seed has to be a positive number but was {}", seed_str))?;
+            }
+            "--sprt" => {
+                sprt_params = parse_sprt_params(&next_value("--sprt")?)?;
+                stop_on_sprt_verdict = true;
+            }
+            _ => { return Err(format!("unknown option {}", arg)); }
+        }
+    }
+
+    let openings_text = match &opt_openings_path {
+        None => DEFAULT_OPENINGS.to_string(),
+        Some(openings_path) => fs::read_to_string(openings_path)
+            .map_err(|err| format!("couldn't read the openings from {}: {}", openings_path, err))?,
+    };
+    Ok(MatchArgs {
+        engine1: EngineConfig::from_spec(&engine1_spec)?,
+        engine2: EngineConfig::from_spec(&engine2_spec)?,
+        nr_of_games,
+        openings: parse_openings(&openings_text)?,
+        seed,
+        sprt_params,
+        stop_on_sprt_verdict,
+    })
+}
+
+fn parse_sprt_params(value: &str) -> Result<SprtParams, String> {
+    let numbers = value.split(',')
+        .map(|number| number.trim().parse::<f64>().map_err(|_| format!("sprt expects numbers but got {}", number)))
+        .collect::<Result<Vec<f64>, String>>()?;
+    match numbers[..] {
+        [elo0, elo1] if elo0 < elo1 => Ok(SprtParams { elo0, elo1, ..SprtParams::default() }),
+        [elo0, elo1, alpha, beta] if elo0 < elo1 && 0.0 < alpha && alpha < 0.5 && 0.0 < beta && beta < 0.5 => {
+            Ok(SprtParams { elo0, elo1, alpha, beta })
+        }
+        _ => Err(format!("sprt expects <elo0>,<elo1>[,<alpha>,<beta>] with elo0 < elo1 and alpha/beta between 0 and 0.5 but got {}", value)),
+    }
+}
+
+/**
+ * one fen per line, empty lines and lines starting with # are ignored
+ */
+fn parse_openings(openings_text: &str) -> Result<Vec<String>, String> {
+    let openings: Vec<String> = openings_text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect();
+    if let Some(invalid_opening) = openings.iter().find(|opening| opening.parse::<Game>().is_err()) {
+        return Err(format!("the opening {} isn't a valid position", invalid_opening));
+    }
+    if openings.is_empty() {
+        return Err("there are no openings to play".to_string());
+    }
+    Ok(openings)
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let match_args = match parse_args(&args) {
+        Ok(match_args) => match_args,
+        Err(msg) => {
+            eprintln!("{}\n\n{}", msg, USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match run(match_args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(msg) => {
+            eprintln!("{}", msg);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(match_args: MatchArgs) -> Result<(), String> {
+    let MatchArgs { engine1, engine2, nr_of_games, openings, seed, sprt_params, stop_on_sprt_verdict } = match_args;
+    println!("{} vs {}: {} games from {} openings", engine1.name, engine2.name, nr_of_games, openings.len());
+
+    let mut match_stats = MatchStats::default();
+    for game_index in 0..nr_of_games {
+        let opening = &openings[(game_index / 2) as usize % openings.len()];
+        // the same opening is played twice in a row, the second time with swapped colors
+        let engine1_color = if game_index % 2 == 0 { Color::White } else { Color::Black };
+        let (white, black) = if engine1_color == Color::White { (&engine1, &engine2) } else { (&engine2, &engine1) };
+        let record = play_game(opening, white, black, seed.wrapping_add(game_index as u64 * 1_000_003))?;
+        let result_str = match record.outcome {
+            Outcome::Win(color) if color == engine1_color => { match_stats.wins += 1; "win" }
+            Outcome::Win(_) => { match_stats.losses += 1; "loss" }
+            Outcome::Draw => { match_stats.draws += 1; "draw" }
+        };
+        println!(
+            "game {}/{}: {} as {} {} ({} after {} half moves), W/D/L: {}/{}/{}",
+            game_index + 1, nr_of_games, engine1.name, engine1_color, result_str, record.reason, record.moves.len(),
+            match_stats.wins, match_stats.draws, match_stats.losses,
+        );
+        if stop_on_sprt_verdict && match_stats.sprt(&sprt_params).verdict != SprtVerdict::Continue {
+            println!("stopping early, the SPRT reached a verdict");
+            break;
+        }
+    }
+
+    println!("\n{}", get_report(&match_stats, &sprt_params, &engine1.name, &engine2.name));
+    Ok(())
+}
+
+fn get_report(match_stats: &MatchStats, sprt_params: &SprtParams, engine1_name: &str, engine2_name: &str) -> String {
+    let mut report = vec![format!(
+        "{} vs {}: W/D/L {}/{}/{} in {} games",
+        engine1_name, engine2_name, match_stats.wins, match_stats.draws, match_stats.losses, match_stats.nr_of_games(),
+    )];
+    if let Some(score) = match_stats.score() {
+        report.push(format!("score: {:.1}%", score * 100.0));
+    }
+    match match_stats.elo_difference() {
+        Some(estimate) if estimate.error_margin.is_finite() => {
+            report.push(format!("elo difference: {:+.1} ± {:.1} (95% confidence)", estimate.elo, estimate.error_margin));
+        }
+        Some(estimate) => report.push(format!("elo difference: {:+.1} (too few games for an error margin)", estimate.elo)),
+        None => report.push("elo difference: unknown (needs games that aren't all won or all lost)".to_string()),
+    }
+    let sprt_result = match_stats.sprt(sprt_params);
+    let verdict = match sprt_result.verdict {
+        SprtVerdict::AcceptH1 => format!("H1 accepted, {} is at least {} Elo stronger", engine1_name, sprt_params.elo1),
+        SprtVerdict::AcceptH0 => format!("H0 accepted, {} isn't more than {} Elo stronger", engine1_name, sprt_params.elo0),
+        SprtVerdict::Continue => "no verdict yet, more games are needed".to_string(),
+    };
+    report.push(format!(
+        "SPRT [{}, {}] (alpha {}, beta {}): LLR {:.2} [{:.2}, {:.2}], {}",
+        sprt_params.elo0, sprt_params.elo1, sprt_params.alpha, sprt_params.beta,
+        sprt_result.llr, sprt_result.lower_bound, sprt_result.upper_bound, verdict,
+    ));
+    report.join("\n")
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[test]
+    fn test_default_openings_are_valid() {
+        assert_eq!(parse_openings(DEFAULT_OPENINGS).unwrap().len(), 10);
+    }
+
+    #[rstest(
+    openings_text, expected_ok,
+    case("# only a comment\n\n", false),
+    case("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1\n", true),
+    case("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1\nnot a fen\n", false),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_parse_openings(openings_text: &str, expected_ok: bool) {
+        assert_eq!(parse_openings(openings_text).is_ok(), expected_ok);
+    }
+
+    #[rstest(
+    value, expected_opt_params,
+    case("0,5", Some(SprtParams { elo0: 0.0, elo1: 5.0, alpha: 0.05, beta: 0.05 })),
+    case("-3, 2, 0.1, 0.2", Some(SprtParams { elo0: -3.0, elo1: 2.0, alpha: 0.1, beta: 0.2 })),
+    case("5,0", None),
+    case("0,5,0.7,0.1", None),
+    case("zero,5", None),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_parse_sprt_params(value: &str, expected_opt_params: Option<SprtParams>) {
+        assert_eq!(parse_sprt_params(value).ok(), expected_opt_params);
+    }
+
+    #[test]
+    fn test_get_report() {
+        let match_stats = MatchStats { wins: 30, draws: 10, losses: 10 };
+        let report = get_report(&match_stats, &SprtParams::default(), "new", "old");
+        assert!(report.starts_with("new vs old: W/D/L 30/10/10 in 50 games\nscore: 70.0%\nelo difference: +147.2 ± "), "{}", report);
+    }
+}
@@ -0,0 +1,199 @@
+/**
+ * the results of a match from the point of view of the first engine
+ */
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub struct MatchStats {
+    pub wins: u32,
+    pub draws: u32,
+    pub losses: u32,
+}
+
+// the quantile of the normal distribution for a 95% confidence interval
+const Z_95: f64 = 1.959964;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct EloEstimate {
+    pub elo: f64,
+    /**
+     * the 95% confidence interval is elo ± error_margin (infinite if there are too few games to tell)
+     */
+    pub error_margin: f64,
+}
+
+/**
+ * the hypotheses of a sequential probability ratio test: H0 is that the first engine is elo0 stronger (usually 0),
+ * H1 that it is elo1 stronger. alpha is the probability to accept H1 although H0 is true, beta the other way around.
+ */
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct SprtParams {
+    pub elo0: f64,
+    pub elo1: f64,
+    pub alpha: f64,
+    pub beta: f64,
+}
+
+impl Default for SprtParams {
+    fn default() -> Self {
+        SprtParams { elo0: 0.0, elo1: 10.0, alpha: 0.05, beta: 0.05 }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum SprtVerdict {
+    AcceptH0,
+    AcceptH1,
+    Continue,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct SprtResult {
+    pub llr: f64,
+    pub lower_bound: f64,
+    pub upper_bound: f64,
+    pub verdict: SprtVerdict,
+}
+
+impl MatchStats {
+    pub fn nr_of_games(&self) -> u32 {
+        self.wins + self.draws + self.losses
+    }
+
+    /**
+     * the average points per game (1 for a win, 0.5 for a draw)
+     */
+    pub fn score(&self) -> Option<f64> {
+        let nr_of_games = self.nr_of_games();
+        (nr_of_games > 0).then(|| (self.wins as f64 + self.draws as f64 / 2.0) / nr_of_games as f64)
+    }
+
+    /**
+     * the variance of the points of a single game
+     */
+    fn variance_per_game(&self, score: f64) -> f64 {
+        let nr_of_games = self.nr_of_games() as f64;
+        (self.wins as f64 * (1.0 - score).powi(2) + self.draws as f64 * (0.5 - score).powi(2) + self.losses as f64 * score.powi(2)) / nr_of_games
+    }
+
+    /**
+     * None as long as there are no games or only wins or only losses (the difference would be infinite)
+     */
+    pub fn elo_difference(&self) -> Option<EloEstimate> {
+        let score = self.score()?;
+        if score <= 0.0 || score >= 1.0 {
+            return None;
+        }
+        let standard_error = (self.variance_per_game(score) / self.nr_of_games() as f64).sqrt();
+        let lower_score = score - Z_95 * standard_error;
+        let upper_score = score + Z_95 * standard_error;
+        let error_margin = if lower_score <= 0.0 || upper_score >= 1.0 {
+            f64::INFINITY
+        } else {
+            (elo_from_score(upper_score) - elo_from_score(lower_score)) / 2.0
+        };
+        Some(EloEstimate {
+            elo: elo_from_score(score),
+            error_margin,
+        })
+    }
+
+    /**
+     * the generalized SPRT with the normal approximation of the log likelihood ratio (like fishtest did for a long time)
+     */
+    pub fn sprt(&self, params: &SprtParams) -> SprtResult {
+        let lower_bound = (params.beta / (1.0 - params.alpha)).ln();
+        let upper_bound = ((1.0 - params.beta) / params.alpha).ln();
+        let llr = match self.score() {
+            None => 0.0,
+            Some(score) => {
+                let variance = self.variance_per_game(score);
+                if variance <= 0.0 {
+                    0.0
+                } else {
+                    let score0 = score_from_elo(params.elo0);
+                    let score1 = score_from_elo(params.elo1);
+                    self.nr_of_games() as f64 * (score1 - score0) * (2.0 * score - score0 - score1) / (2.0 * variance)
+                }
+            }
+        };
+        let verdict = if llr >= upper_bound {
+            SprtVerdict::AcceptH1
+        } else if llr <= lower_bound {
+            SprtVerdict::AcceptH0
+        } else {
+            SprtVerdict::Continue
+        };
+        SprtResult { llr, lower_bound, upper_bound, verdict }
+    }
+}
+
+pub fn elo_from_score(score: f64) -> f64 {
+    -400.0 * (1.0 / score - 1.0).log10()
+}
+
+pub fn score_from_elo(elo: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf(-elo / 400.0))
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    fn stats(wins: u32, draws: u32, losses: u32) -> MatchStats {
+        MatchStats { wins, draws, losses }
+    }
+
+    #[rstest(
+    score, expected_elo,
+    case(0.5, 0.0),
+    case(0.75, 190.85),
+    case(0.25, -190.85),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_elo_from_score(score: f64, expected_elo: f64) {
+        assert!((elo_from_score(score) - expected_elo).abs() < 0.01, "{}", elo_from_score(score));
+        assert!((score_from_elo(expected_elo) - score).abs() < 0.0001);
+    }
+
+    #[rstest(
+    match_stats, expected_opt_elo,
+    case(stats(0, 0, 0), None),
+    case(stats(10, 0, 0), None),
+    case(stats(10, 20, 10), Some(0.0)),
+    case(stats(30, 10, 10), Some(147.19)),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_elo_difference(match_stats: MatchStats, expected_opt_elo: Option<f64>) {
+        let opt_estimate = match_stats.elo_difference();
+        assert_eq!(opt_estimate.is_some(), expected_opt_elo.is_some());
+        if let (Some(estimate), Some(expected_elo)) = (opt_estimate, expected_opt_elo) {
+            assert!((estimate.elo - expected_elo).abs() < 0.01, "{:?}", estimate);
+            assert!(estimate.error_margin > 0.0);
+        }
+    }
+
+    #[test]
+    fn test_error_margin_shrinks_with_more_games() {
+        let few_games = stats(6, 8, 4).elo_difference().unwrap();
+        let many_games = stats(600, 800, 400).elo_difference().unwrap();
+        assert!((few_games.elo - many_games.elo).abs() < 0.01);
+        assert!(many_games.error_margin * 5.0 < few_games.error_margin, "{:?} {:?}", few_games, many_games);
+        assert_eq!(stats(2, 1, 1).elo_difference().unwrap().error_margin, f64::INFINITY);
+    }
+
+    #[rstest(
+    match_stats, expected_verdict,
+    case(stats(0, 0, 0), SprtVerdict::Continue),
+    case(stats(5, 3, 4), SprtVerdict::Continue),
+    case(stats(900, 1000, 600), SprtVerdict::AcceptH1),
+    case(stats(1000, 2000, 1100), SprtVerdict::AcceptH0),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_sprt(match_stats: MatchStats, expected_verdict: SprtVerdict) {
+        let result = match_stats.sprt(&SprtParams::default());
+        assert_eq!(result.verdict, expected_verdict, "{:?}", result);
+        assert!(result.lower_bound < 0.0 && result.upper_bound > 0.0);
+    }
+}
@@ -0,0 +1,104 @@
+use std::env;
+use std::fs;
+use std::process::ExitCode;
+use engine_core::engine::static_eval::EvalParams;
+use crate::tuning::{find_best_k, load_dataset, mean_squared_error, tune};
+
+mod tuning;
+
+const USAGE: &str = "usage: voidchess_tuner <dataset-file> [--params <start-params.json|toml>] [--out <tuned-params.json>] [--k <scaling-constant>] [--iterations <max-iterations>]
+
+Every line of the dataset contains a quiet position as fen followed by the result of its game
+(\"1-0\", \"0-1\", \"1/2-1/2\" or [1.0], [0.5], [0.0]).";
+
+struct TunerArgs {
+    dataset_path: String,
+    opt_params_path: Option<String>,
+    out_path: String,
+    opt_k: Option<f32>,
+    max_iterations: usize,
+}
+
+fn parse_args(args: &[String]) -> Result<TunerArgs, String> {
+    let mut opt_dataset_path: Option<String> = None;
+    let mut opt_params_path: Option<String> = None;
+    let mut out_path = "tuned_eval_params.json".to_string();
+    let mut opt_k: Option<f32> = None;
+    let mut max_iterations: usize = 1000;
+
+    let mut arg_iter = args.iter();
+    while let Some(arg) = arg_iter.next() {
+        let mut next_value = |option: &str| arg_iter.next().cloned().ok_or_else(|| format!("missing value for {}", option));
+        match arg.as_str() {
+            "--params" => { opt_params_path = Some(next_value("--params")?); }
+            "--out" => { out_path = next_value("--out")?; }
+            "--k" => {
+                let k_str = next_value("--k")?;
+                opt_k = Some(k_str.parse::<f32>().map_err(|_| format!("k has to be a number but was {}", k_str))?);
+            }
+            "--iterations" => {
+                let iterations_str = next_value("--iterations")?;
+                max_iterations = iterations_str.parse::<usize>().map_err(|_| format!("iterations has to be a positive number but was {}", iterations_str))?;
+            }
+            _ if arg.starts_with("--") => { return Err(format!("unknown option {}", arg)); }
+            _ => {
+                if opt_dataset_path.is_some() {
+                    return Err(format!("only one dataset file expected, but got another one: {}", arg));
+                }
+                opt_dataset_path = Some(arg.clone());
+            }
+        }
+    }
+
+    Ok(TunerArgs {
+        dataset_path: opt_dataset_path.ok_or_else(|| "no dataset file given".to_string())?,
+        opt_params_path,
+        out_path,
+        opt_k,
+        max_iterations,
+    })
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let tuner_args = match parse_args(&args) {
+        Ok(tuner_args) => tuner_args,
+        Err(msg) => {
+            eprintln!("{}\n\n{}", msg, USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match run(tuner_args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(msg) => {
+            eprintln!("{}", msg);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(tuner_args: TunerArgs) -> Result<(), String> {
+    let dataset = load_dataset(&tuner_args.dataset_path).map_err(|err| err.to_string())?;
+    if dataset.is_empty() {
+        return Err(format!("dataset {} doesn't contain any positions", tuner_args.dataset_path));
+    }
+    println!("loaded {} positions from {}", dataset.len(), tuner_args.dataset_path);
+
+    let start_params = match &tuner_args.opt_params_path {
+        None => EvalParams::default(),
+        Some(params_path) => EvalParams::from_file(params_path).map_err(|err| err.to_string())?,
+    };
+
+    let k = tuner_args.opt_k.unwrap_or_else(|| find_best_k(&dataset, &start_params));
+    println!("scaling constant k: {:.4}, start error: {:.6}", k, mean_squared_error(&dataset, &start_params, k));
+
+    let result = tune(&dataset, start_params, k, tuner_args.max_iterations, |intermediate_result| {
+        println!("iteration {}: error {:.6}", intermediate_result.iterations, intermediate_result.error);
+    });
+
+    fs::write(&tuner_args.out_path, result.params.to_json())
+        .map_err(|err| format!("couldn't write tuned params to {}: {}", tuner_args.out_path, err))?;
+    println!("tuned params (error {:.6} after {} iterations) written to {}:\n{}", result.error, result.iterations, tuner_args.out_path, result.params.to_json());
+    Ok(())
+}
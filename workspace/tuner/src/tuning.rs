@@ -0,0 +1,289 @@
+use std::fs;
+use std::path::Path;
+use engine_core::base::{ChessError, Color, ErrorKind};
+use engine_core::engine::static_eval::{static_eval, EvalParams, StaticEvalType, NR_OF_EVAL_PARAMS};
+use engine_core::game::GameState;
+
+/**
+ * a (quiet) position together with the result of the game it was taken from.
+ * The result is seen from white's perspective: 1.0 = white won, 0.5 = draw, 0.0 = black won
+ */
+#[derive(Debug, Clone)]
+pub struct LabeledPosition {
+    pub game_state: GameState,
+    pub result: f32,
+}
+
+/**
+ * a line consists of a fen followed by the result. Supported result formats are
+ * "1-0"/"0-1"/"1/2-1/2" (optionally in quotes and followed by a ';') and [1.0]/[0.5]/[0.0].
+ * Empty lines and lines starting with '#' are skipped (returning Ok(None)).
+ */
+pub fn parse_labeled_position(line: &str) -> Result<Option<LabeledPosition>, ChessError> {
+    let trimmed_line = line.trim();
+    if trimmed_line.is_empty() || trimmed_line.starts_with('#') {
+        return Ok(None);
+    }
+
+    let tokens: Vec<&str> = trimmed_line.split_whitespace().collect();
+    let result_token = tokens.iter().rev().find_map(|token| parse_result(token)).ok_or_else(|| ChessError {
+        msg: format!("no game result found in line: {}", trimmed_line),
+        kind: ErrorKind::IllegalFormat,
+    })?;
+
+    // a fen consists of 4 fields, optionally followed by the half-move clock and the full-move number
+    let nr_of_fen_fields = if tokens.len() >= 6 && tokens[4].parse::<usize>().is_ok() && tokens[5].parse::<usize>().is_ok() {
+        6
+    } else {
+        4
+    };
+    if tokens.len() <= nr_of_fen_fields {
+        return Err(ChessError {
+            msg: format!("expected a fen followed by a result in line: {}", trimmed_line),
+            kind: ErrorKind::IllegalFormat,
+        });
+    }
+    let fen = tokens[..nr_of_fen_fields].join(" ");
+    let game_state = GameState::from_fen(fen.as_str())?;
+
+    Ok(Some(LabeledPosition {
+        game_state,
+        result: result_token,
+    }))
+}
+
+fn parse_result(token: &str) -> Option<f32> {
+    let trimmed_token = token.trim_end_matches(';').trim_matches('"');
+    match trimmed_token {
+        "1-0" | "[1.0]" | "[1]" => Some(1.0),
+        "1/2-1/2" | "[0.5]" => Some(0.5),
+        "0-1" | "[0.0]" | "[0]" => Some(0.0),
+        _ => None,
+    }
+}
+
+pub fn load_dataset<P: AsRef<Path>>(path: P) -> Result<Vec<LabeledPosition>, ChessError> {
+    let path = path.as_ref();
+    let content = fs::read_to_string(path).map_err(|err| ChessError {
+        msg: format!("couldn't read dataset {}: {}", path.display(), err),
+        kind: ErrorKind::IllegalConfig,
+    })?;
+    let mut dataset: Vec<LabeledPosition> = vec![];
+    for (line_index, line) in content.lines().enumerate() {
+        match parse_labeled_position(line) {
+            Ok(Some(labeled_position)) => dataset.push(labeled_position),
+            Ok(None) => {},
+            Err(err) => {
+                return Err(ChessError {
+                    msg: format!("line {}: {}", line_index + 1, err.msg),
+                    kind: err.kind,
+                });
+            }
+        }
+    }
+    Ok(dataset)
+}
+
+/**
+ * maps a static evaluation (in pawns, from white's perspective) to an expected score between 0.0 and 1.0
+ */
+pub fn sigmoid(eval: f32, k: f32) -> f32 {
+    1.0 / (1.0 + 10_f32.powf(-k * eval / 4.0))
+}
+
+pub fn mean_squared_error(dataset: &[LabeledPosition], params: &EvalParams, k: f32) -> f64 {
+    if dataset.is_empty() {
+        return 0.0;
+    }
+    let eval_type = StaticEvalType::Parameterised(*params);
+    let sum_of_squared_errors: f64 = dataset.iter().map(|labeled_position| {
+        let eval = static_eval(&labeled_position.game_state, eval_type, Color::White);
+        let error = (labeled_position.result - sigmoid(eval, k)) as f64;
+        error * error
+    }).sum();
+    sum_of_squared_errors / dataset.len() as f64
+}
+
+/**
+ * the scaling constant k is chosen so that the untuned params fit the dataset best,
+ * so that the tuning afterwards changes the weights and not the scale of the evaluation.
+ */
+pub fn find_best_k(dataset: &[LabeledPosition], params: &EvalParams) -> f32 {
+    let mut best_k = 1.0;
+    let mut best_error = mean_squared_error(dataset, params, best_k);
+    let mut step = 0.5;
+    while step > 0.001 {
+        let mut improved = false;
+        for candidate_k in [best_k - step, best_k + step] {
+            if candidate_k <= 0.0 {
+                continue;
+            }
+            let error = mean_squared_error(dataset, params, candidate_k);
+            if error < best_error {
+                best_error = error;
+                best_k = candidate_k;
+                improved = true;
+            }
+        }
+        if !improved {
+            step /= 2.0;
+        }
+    }
+    best_k
+}
+
+// every weight is changed by this fraction of its initial value
+const RELATIVE_STEP: f32 = 0.02;
+const MIN_STEP: f32 = 0.0001;
+const MAX_NR_OF_STEP_HALVINGS: usize = 4;
+
+#[derive(Debug, Copy, Clone)]
+pub struct TuningResult {
+    pub params: EvalParams,
+    pub error: f64,
+    pub iterations: usize,
+}
+
+/**
+ * Texel's local search: every weight is increased and decreased by a small step,
+ * changes that lower the error are kept. When no change helps anymore, the steps are halved.
+ * on_iteration is called after every iteration with the current best result.
+ */
+pub fn tune<F>(
+    dataset: &[LabeledPosition],
+    start_params: EvalParams,
+    k: f32,
+    max_iterations: usize,
+    mut on_iteration: F,
+) -> TuningResult where F: FnMut(&TuningResult) {
+    let mut best_values = start_params.to_array();
+    let mut best_error = mean_squared_error(dataset, &start_params, k);
+    let mut steps: [f32; NR_OF_EVAL_PARAMS] = best_values.map(|value| (value.abs() * RELATIVE_STEP).max(MIN_STEP));
+    let mut nr_of_step_halvings = 0;
+    let mut iterations = 0;
+
+    while iterations < max_iterations {
+        iterations += 1;
+        let mut improved = false;
+        for param_index in 0..NR_OF_EVAL_PARAMS {
+            for direction in [1.0, -1.0] {
+                let mut candidate_values = best_values;
+                candidate_values[param_index] += direction * steps[param_index];
+                let candidate_error = mean_squared_error(dataset, &EvalParams::from_array(candidate_values), k);
+                if candidate_error < best_error {
+                    best_values = candidate_values;
+                    best_error = candidate_error;
+                    improved = true;
+                    break;
+                }
+            }
+        }
+
+        on_iteration(&TuningResult {
+            params: EvalParams::from_array(best_values),
+            error: best_error,
+            iterations,
+        });
+
+        if !improved {
+            if nr_of_step_halvings == MAX_NR_OF_STEP_HALVINGS {
+                break;
+            }
+            nr_of_step_halvings += 1;
+            steps = steps.map(|step| step / 2.0);
+        }
+    }
+
+    TuningResult {
+        params: EvalParams::from_array(best_values),
+        error: best_error,
+        iterations,
+    }
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest(
+    line, expected_result,
+    case("4k3/8/8/8/8/8/8/3K3R w - - 0 1 \"1-0\";", 1.0),
+    case("4k3/8/8/8/8/8/8/3K3R w - - c9 \"1/2-1/2\";", 0.5),
+    case("4k3/8/8/8/8/8/8/3K3R b - - 0-1", 0.0),
+    case("4k3/8/8/8/8/8/8/3K3R w - - 12 40 [0.5]", 0.5),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_parse_labeled_position(line: &str, expected_result: f32) {
+        let labeled_position = parse_labeled_position(line).unwrap().unwrap();
+        assert_eq!(labeled_position.result, expected_result);
+        assert_eq!(labeled_position.game_state.get_fen_part1to4().split(' ').next(), Some("4k3/8/8/8/8/8/8/3K3R"));
+    }
+
+    #[rstest(
+    line,
+    case(""),
+    case("   "),
+    case("# a comment"),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_parse_labeled_position_skips_line(line: &str) {
+        assert!(parse_labeled_position(line).unwrap().is_none());
+    }
+
+    #[rstest(
+    line,
+    case("4k3/8/8/8/8/8/8/3K3R w - - 0 1"),
+    case("1-0"),
+    case("4k3/8/8/8/8/8/8/3K3X w - - 1-0"),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_parse_labeled_position_fails(line: &str) {
+        assert!(parse_labeled_position(line).is_err(), "line should be rejected: {}", line);
+    }
+
+    #[test]
+    fn test_sigmoid() {
+        assert_eq!(sigmoid(0.0, 1.0), 0.5);
+        assert!(sigmoid(3.0, 1.0) > 0.8);
+        assert!(sigmoid(-3.0, 1.0) < 0.2);
+    }
+
+    fn dataset_of(lines: &[&str]) -> Vec<LabeledPosition> {
+        lines.iter().map(|line| parse_labeled_position(line).unwrap().unwrap()).collect()
+    }
+
+    #[test]
+    fn test_tuning_lowers_the_error() {
+        // a queen against a rook is usually a win, but in this (made up) dataset it's always a draw,
+        // so the tuning should reduce the difference between the queen value and the rook value
+        let dataset = dataset_of(&[
+            "3qk3/8/8/8/8/8/8/3RK3 w - - 1/2-1/2",
+            "3rk3/8/8/8/8/8/8/3QK3 w - - 1/2-1/2",
+            "4k3/8/2q5/8/8/5R2/8/4K3 b - - 1/2-1/2",
+            "4k3/8/2r5/8/8/5Q2/8/4K3 b - - 1/2-1/2",
+        ]);
+        let start_params = EvalParams::default();
+        let start_error = mean_squared_error(&dataset, &start_params, 1.0);
+
+        let result = tune(&dataset, start_params, 1.0, 50, |_| {});
+
+        assert!(result.error < start_error, "error {} should be lower than {}", result.error, start_error);
+        let start_difference = start_params.queen_value - start_params.rook_value;
+        let tuned_difference = result.params.queen_value - result.params.rook_value;
+        assert!(tuned_difference < start_difference, "difference {} should be lower than {}", tuned_difference, start_difference);
+    }
+
+    #[test]
+    fn test_find_best_k_is_positive() {
+        let dataset = dataset_of(&[
+            "3qk3/8/8/8/8/8/8/4K3 w - - 0-1",
+            "4k3/8/8/8/8/8/8/3QK3 w - - 1-0",
+            "4k3/8/8/8/8/8/8/3RK3 w - - 1/2-1/2",
+        ]);
+        let k = find_best_k(&dataset, &EvalParams::default());
+        assert!(k > 0.0, "k: {}", k);
+    }
+}
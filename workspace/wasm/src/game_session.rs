@@ -0,0 +1,386 @@
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::base::{ChessError, ErrorKind, Move};
+use crate::engine::evaluate_game_with_config;
+use crate::engine::evaluations::DrawReason;
+use crate::engine::evaluations::frontend::{GameEndResult, GameEvaluation};
+use crate::engine::search_config::{MoveSelection, SearchConfig};
+use crate::engine::skill::{evaluate_game_with_skill, SkillLevel};
+use crate::engine::static_eval::EvalParams;
+use crate::figure::functions::allowed::get_allowed_moves_of;
+use crate::game::{Game, StoppedReason};
+use crate::{to_js, to_serializable_with_fen, JsFenResult, JsGameEvaluation, MoveList, SerializableDrawReason, SerializableErrorKind, SerializableGameEvaluation, WasmError, WasmResult, PRUNER};
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_TYPES: &'static str = r#"
+/** the draw_reason is only set for a Draw */
+export interface SessionStatus {
+    status: "Ongoing" | "Check" | "Checkmate" | "Draw";
+    draw_reason: DrawReason | null;
+}
+
+/** with a skill_level the eval_params are ignored */
+export interface EvaluateOptions {
+    skill_level?: number;
+    eval_params?: object;
+    contempt_in_centipawns?: number;
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "SessionStatus")]
+    pub type JsSessionStatus;
+    #[wasm_bindgen(typescript_type = "EvaluateOptions")]
+    pub type JsEvaluateOptions;
+}
+
+/**
+ * A game that stays alive in the browser between the moves, so that playing, undoing, redoing
+ * and evaluating a move doesn't re-parse (and replay) the whole game every time.
+ * The methods return the same typescript types as the free functions of this crate.
+ */
+#[wasm_bindgen]
+pub struct GameSession {
+    game: Game,
+}
+
+#[wasm_bindgen]
+impl GameSession {
+    /**
+     * the game_config is anything the free functions accept: moves from the start, figures on the board or a fen.
+     * Throws a WasmError if the game_config can't be parsed.
+     */
+    #[wasm_bindgen(constructor)]
+    pub fn new(game_config: &str) -> Result<GameSession, JsValue> {
+        GameSession::from_config(game_config).map_err(|err| to_js(&WasmError::from(err)))
+    }
+
+    /**
+     * plays the move (e.g. "e2-e4", "a7Qa8") and returns the fen after the move
+     * or the reason why the move couldn't be played
+     */
+    pub fn play(&mut self, move_str: &str) -> JsFenResult {
+        to_js(&self.play_move(move_str))
+    }
+
+    /**
+     * takes back the latest move. Returns false if there was no move to take back.
+     */
+    pub fn undo(&mut self) -> bool {
+        self.game.undo()
+    }
+
+    /**
+     * plays the latest undone move again. Returns false if there is none.
+     */
+    pub fn redo(&mut self) -> bool {
+        self.game.redo()
+    }
+
+    /**
+     * goes to the position after the given number of moves (0 is the initial position),
+     * the moves after it can still be redone. Returns false if there is no such position.
+     */
+    pub fn go_to_ply(&mut self, ply: usize) -> bool {
+        self.game.go_to_ply(ply).is_ok()
+    }
+
+    /**
+     * the number of moves played to reach the current position
+     */
+    pub fn ply(&self) -> usize {
+        self.game.get_current_ply()
+    }
+
+    /**
+     * the legal moves (of the promotions only the one to a queen), empty if the game has ended
+     */
+    pub fn legal_moves(&self) -> MoveList {
+        to_js(&self.get_legal_moves())
+    }
+
+    pub fn fen(&self) -> String {
+        self.game.get_fen()
+    }
+
+    /**
+     * the moves played since the session started up to the current position, separated by spaces
+     */
+    pub fn moves_played(&self) -> String {
+        let moves_played = self.game.get_moves_played();
+        // a game_config with moves from the start already played some of them
+        let moves_played_in_session = &moves_played[moves_played.len() - self.game.get_current_ply()..];
+        moves_played_in_session.iter().map(|a_move| a_move.to_string()).collect::<Vec<String>>().join(" ")
+    }
+
+    pub fn status(&self) -> JsSessionStatus {
+        to_js(&self.get_status())
+    }
+
+    /**
+     * evaluates the current position like evaluate_position_after,
+     * e.g. with { skill_level: 5 } or { eval_params: { queen_value: 9.5 }, contempt_in_centipawns: 20 }.
+     * All options are optional.
+     */
+    pub fn evaluate(&self, opt_options: Option<JsEvaluateOptions>) -> JsGameEvaluation {
+        let game_evaluation = match EvaluateOptions::from_js(opt_options) {
+            Ok(options) => self.evaluate_with(&options),
+            Err(err) => SerializableGameEvaluation::from_error(err),
+        };
+        to_js(&game_evaluation)
+    }
+}
+
+impl GameSession {
+    fn from_config(game_config: &str) -> Result<GameSession, ChessError> {
+        let game = game_config.parse::<Game>()?;
+        Ok(GameSession { game })
+    }
+
+    fn play_move(&mut self, move_str: &str) -> WasmResult<String> {
+        WasmResult::from(self.try_play(move_str))
+    }
+
+    /**
+     * returns the fen after the move
+     */
+    fn try_play(&mut self, move_str: &str) -> Result<String, ChessError> {
+        if self.game.get_stopped_reason().is_some() {
+            return Err(ChessError {
+                msg: "the game has already ended".to_string(),
+                kind: ErrorKind::IllegalConfig,
+            });
+        }
+        let a_move = move_str.trim().parse::<Move>()?;
+        match self.game.make_move(a_move) {
+            Ok(()) => Ok(self.game.get_fen()),
+            Err(_) => Err(ChessError {
+                msg: format!("{} is not a legal move in this position", a_move),
+                kind: ErrorKind::IllegalConfig,
+            }),
+        }
+    }
+
+    fn get_legal_moves(&self) -> Vec<String> {
+        get_allowed_moves_of(&self.game).iter().map(|a_move| a_move.to_string()).collect()
+    }
+
+    fn get_status(&self) -> StatusResult {
+        let (status, opt_draw_reason) = if let Some(stopped_reason) = self.game.get_stopped_reason() {
+            (SessionStatus::Draw, Some(to_draw_reason(stopped_reason)))
+        } else {
+            let game = &self.game;
+            let is_check = game.is_active_king_in_check();
+            let has_legal_move = !get_allowed_moves_of(game).is_empty();
+            match (is_check, has_legal_move) {
+                (true, true) => (SessionStatus::Check, None),
+                (true, false) => (SessionStatus::Checkmate, None),
+                (false, true) => (SessionStatus::Ongoing, None),
+                (false, false) => (SessionStatus::Draw, Some(DrawReason::StaleMate)),
+            }
+        };
+        StatusResult {
+            status,
+            draw_reason: opt_draw_reason.map(SerializableDrawReason::from),
+        }
+    }
+
+    fn evaluate_with(&self, options: &EvaluateOptions) -> SerializableGameEvaluation {
+        let status = self.get_status();
+        let game_evaluation = match (status.status, status.draw_reason) {
+            (SessionStatus::Checkmate, _) => GameEvaluation::GameEnded(GameEndResult::EngineLost),
+            (SessionStatus::Draw, Some(draw_reason)) => GameEvaluation::GameEnded(GameEndResult::Draw(draw_reason.into())),
+            _ => match options.to_search() {
+                Err(err) => GameEvaluation::Err(err),
+                Ok(Search::WithSkill(skill_level)) => evaluate_game_with_skill(&self.game, skill_level, MoveSelection::Random),
+                Ok(Search::WithConfig(config)) => evaluate_game_with_config(&self.game, &config),
+            },
+        };
+        let game = &self.game;
+        to_serializable_with_fen(game_evaluation, |chosen_move| Ok(game.get_fen_after(chosen_move)))
+    }
+}
+
+fn to_draw_reason(stopped_reason: StoppedReason) -> DrawReason {
+    match stopped_reason {
+        StoppedReason::InsufficientMaterial => DrawReason::InsufficientMaterial,
+        StoppedReason::ThreeTimesRepetition => DrawReason::ThreeTimesRepetition,
+        StoppedReason::NoChangeIn50Moves => DrawReason::NoChangeIn50Moves,
+        StoppedReason::KingInCheckAfterMove => unreachable!("an illegal move doesn't end the game"),
+    }
+}
+
+#[derive(Serialize, Debug, Copy, Clone, Eq, PartialEq)]
+enum SessionStatus {
+    Ongoing,
+    Check,
+    Checkmate,
+    Draw,
+}
+
+#[derive(Serialize, Debug, Eq, PartialEq)]
+struct StatusResult {
+    status: SessionStatus,
+    draw_reason: Option<SerializableDrawReason>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct EvaluateOptions {
+    skill_level: Option<u8>,
+    eval_params: Option<EvalParams>,
+    contempt_in_centipawns: i32,
+}
+
+enum Search {
+    WithSkill(SkillLevel),
+    WithConfig(SearchConfig),
+}
+
+impl EvaluateOptions {
+    pub(crate) fn from_js(opt_options: Option<JsEvaluateOptions>) -> Result<EvaluateOptions, WasmError> {
+        match opt_options {
+            None => Ok(EvaluateOptions::default()),
+            Some(options) => serde_wasm_bindgen::from_value::<EvaluateOptions>(options.into()).map_err(|err| WasmError {
+                kind: SerializableErrorKind::IllegalFormat,
+                msg: format!("couldn't read the evaluate options: {}", err),
+            }),
+        }
+    }
+
+    fn to_search(&self) -> Result<Search, ChessError> {
+        match self.skill_level {
+            Some(level) => SkillLevel::new(level)
+                .map(|skill_level| Search::WithSkill(skill_level
+                    .with_time_limit(skill_level.get_suggested_time_limit())
+                    .with_contempt(self.contempt_in_centipawns))),
+            None => self.to_search_config().map(Search::WithConfig),
+        }
+    }
+
+    /**
+     * the skill levels pick their move from all evaluations at once, so they can't be used with split searches
+     */
+    pub(crate) fn to_search_config(&self) -> Result<SearchConfig, ChessError> {
+        if self.skill_level.is_some() {
+            return Err(ChessError {
+                msg: "a skill_level can't be combined with a split search".to_string(),
+                kind: ErrorKind::IllegalConfig,
+            });
+        }
+        let mut config = SearchConfig::new(PRUNER).with_contempt(self.contempt_in_centipawns);
+        if let Some(eval_params) = self.eval_params {
+            config = config.with_eval_params(eval_params);
+        }
+        Ok(config)
+    }
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    //♔♕♗♘♖♙♚♛♝♞♜♟
+
+    fn fen_result(fen: &str) -> WasmResult<String> {
+        WasmResult { is_ok: true, value: Some(fen.to_string()), error: None }
+    }
+
+    fn status_result(status: SessionStatus, opt_draw_reason: Option<SerializableDrawReason>) -> StatusResult {
+        StatusResult { status, draw_reason: opt_draw_reason }
+    }
+
+    #[test]
+    fn test_play_and_undo() {
+        let mut session = GameSession::from_config("").unwrap();
+        let start_fen = session.fen();
+        assert!(!session.undo());
+
+        assert_eq!(session.play_move("e2-e4"), fen_result("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1"));
+        assert!(!session.play_move("e2-e4").is_ok);
+        assert!(!session.play_move("e7-e4").is_ok);
+        session.play_move("e7-e5");
+        assert_eq!(session.moves_played(), "e2-e4 e7-e5");
+
+        assert!(session.undo());
+        assert!(session.undo());
+        assert_eq!(session.fen(), start_fen);
+        assert_eq!(session.moves_played(), "");
+    }
+
+    #[test]
+    fn test_redo_and_go_to_ply() {
+        let mut session = GameSession::from_config("").unwrap();
+        session.play_move("e2-e4");
+        session.play_move("e7-e5");
+        session.play_move("g1-f3");
+        assert!(session.go_to_ply(1));
+        assert_eq!(session.ply(), 1);
+        assert_eq!(session.moves_played(), "e2-e4");
+        assert!(session.redo());
+        assert_eq!(session.moves_played(), "e2-e4 e7-e5");
+        assert!(!session.go_to_ply(4));
+
+        session.play_move("d2-d4");
+        assert!(!session.redo());
+        assert_eq!(session.moves_played(), "e2-e4 e7-e5 d2-d4");
+        assert_eq!(session.ply(), 3);
+    }
+
+    #[test]
+    fn test_legal_moves() {
+        let session = GameSession::from_config("white ♔e1 ♖h1 ♚g8 ♝c3").unwrap();
+        let moves = session.get_legal_moves();
+        assert_eq!(moves.len(), 4, "moves: {:?}", moves);
+    }
+
+    #[test]
+    fn test_status() {
+        let mut session = GameSession::from_config("white ♔g3 ♖d2 ♚g1 ♙c2 ♙d3").unwrap();
+        assert_eq!(session.get_status(), status_result(SessionStatus::Ongoing, None));
+        session.play_move("d2-d1");
+        assert_eq!(session.get_status(), status_result(SessionStatus::Checkmate, None));
+        assert!(session.get_legal_moves().is_empty());
+
+        let mut session = GameSession::from_config("g1-f3 g8-f6 f3-g1 f6-g8 g1-f3 g8-f6 f3-g1").unwrap();
+        assert!(session.play_move("f6-g8").is_ok);
+        assert_eq!(session.get_status(), status_result(SessionStatus::Draw, Some(SerializableDrawReason::ThreeTimesRepetition)));
+        assert_eq!(serde_json::to_string(&session.get_status()).unwrap(), r#"{"status":"Draw","draw_reason":"ThreeTimesRepetition"}"#);
+        assert!(!session.play_move("g1-f3").is_ok);
+        assert!(!session.redo());
+        assert!(session.undo());
+        assert_eq!(session.get_status(), status_result(SessionStatus::Ongoing, None));
+    }
+
+    #[test]
+    fn test_a_draw_after_undo_discards_the_undone_moves() {
+        let mut session = GameSession::from_config("g1-f3 g8-f6 f3-g1 f6-g8 g1-f3 g8-f6 f3-g1").unwrap();
+        session.play_move("b8-c6");
+        assert!(session.undo());
+        assert!(session.play_move("f6-g8").is_ok);
+
+        assert!(session.undo());
+        assert!(session.redo());
+        assert!(!session.redo());
+        assert_eq!(session.moves_played(), "f6-g8");
+        assert_eq!(session.get_status(), status_result(SessionStatus::Draw, Some(SerializableDrawReason::ThreeTimesRepetition)));
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let session = GameSession::from_config("white ♔g3 ♖d2 ♚g1 ♙c2 ♙d3").unwrap();
+        let options: EvaluateOptions = serde_json::from_str(r#"{"contempt_in_centipawns": 10}"#).unwrap();
+        match session.evaluate_with(&options) {
+            SerializableGameEvaluation::MoveToPlay { move_to_play, .. } => assert_eq!(move_to_play, "d2-d1"),
+            game_evaluation => panic!("unexpected evaluation: {:?}", game_evaluation),
+        }
+
+        let options: EvaluateOptions = serde_json::from_str(r#"{"skill_level": 0}"#).unwrap();
+        assert!(matches!(session.evaluate_with(&options), SerializableGameEvaluation::Err { .. }));
+        assert!(serde_json::from_str::<EvaluateOptions>(r#"{"unknown": 0}"#).is_err());
+    }
+}
@@ -9,14 +9,22 @@ use web_sys::console;
 
 use engine_core::*;
 
-use crate::base::Move;
-use crate::engine::{choose_next_move, evaluate_single_move};
-pub use crate::engine::evaluate;
+use crate::base::{ChessError, ErrorKind, Move};
+use crate::engine::{choose_next_move, evaluate_single_move, evaluate_single_move_with_params};
+pub use crate::engine::{evaluate, evaluate_with_params};
+use crate::engine::static_eval::EvalParams;
+use crate::engine::search_config::MoveSelection;
+use crate::engine::skill::{evaluate_with_skill, SkillLevel};
 use crate::engine::evaluations::{DrawReason, EvaluatedMove};
 use crate::engine::evaluations::frontend::{GameEndResult, GameEvaluation, MoveEvaluation};
 pub use crate::engine::min_max::pruner::*;
-pub use crate::figure::functions::allowed::get_allowed_moves;
-pub use crate::game::Game;
+pub use crate::figure::functions::allowed::{get_allowed_moves, get_allowed_moves_of};
+pub use crate::game::{Game, StoppedReason};
+
+mod game_session;
+mod root_split;
+
+pub use crate::game_session::GameSession;
 
 // This is like the `main` function, except for JavaScript.
 #[wasm_bindgen(start)]
@@ -35,59 +43,190 @@ pub fn main_js() -> Result<(), JsValue> {
 
 static PRUNER: Pruner = PRUNER_1_1_3_3;
 
+#[wasm_bindgen(typescript_custom_section)]
+const TS_TYPES: &'static str = r#"
+export type DrawReason = "StaleMate" | "InsufficientMaterial" | "ThreeTimesRepetition" | "NoChangeIn50Moves" | "EndgameTablebase";
+
+/** the numeric evaluations are in pawns from the point of view of the engine, EngineGetsCheckMatedIn also contains the numeric evaluation as tie-break */
+export type MoveEvaluation =
+    | { EngineCheckMatesIn: number }
+    | { Numeric: number }
+    | { Draw: DrawReason }
+    | { EngineGetsCheckMatedIn: [number, number] };
+
+/** GameEnded if the game_config is an already ended game (or checkmate/stalemate), else an evaluation or an error */
+export type GameEvaluation =
+    | { result_type: "MoveToPlay", move_to_play: string, eval: MoveEvaluation, fen: string }
+    | { result_type: "GameEnded", msg: string }
+    | { result_type: "Err", kind: ErrorKind, msg: string };
+
+/** moves like "e2-e4", "e1cg1" (castling) or "a7Qa8" (promotion) */
+export type MoveList = string[];
+
+/** GameEnded if a move of the game_config ends the game, so that no move can follow it */
+export type ErrorKind = "IllegalFormat" | "IllegalConfig" | "IllegalMove" | "GameEnded";
+
+export interface WasmError {
+    kind: ErrorKind;
+    msg: string;
+}
+
+/** the exported functions don't throw, they return the error instead */
+export type WasmResult<T> = { is_ok: true, value: T } | { is_ok: false, error: WasmError };
+
+export type FenResult = WasmResult<string>;
+
+export type MoveListResult = WasmResult<MoveList>;
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "GameEvaluation")]
+    pub type JsGameEvaluation;
+    #[wasm_bindgen(typescript_type = "GameEvaluation[]")]
+    pub type GameEvaluationArray;
+    #[wasm_bindgen(typescript_type = "MoveList")]
+    pub type MoveList;
+    #[wasm_bindgen(typescript_type = "MoveListResult")]
+    pub type MoveListResult;
+    #[wasm_bindgen(typescript_type = "FenResult")]
+    pub type JsFenResult;
+}
+
 #[wasm_bindgen]
 pub fn get_greeting_for(name: &str) -> JsValue {
     let greeting = format!("Hello, {}", name);
     JsValue::from_str(greeting.as_str())
 }
 
+/**
+ * the legal moves (of the promotions only the one to a queen)
+ */
 #[wasm_bindgen]
-pub fn get_concatenated_allowed_moves(game_config: &str) -> JsValue {
-    let moves = get_allowed_moves(game_config);
-    let moves_as_str: Vec<String> = moves.iter().map(|it|format!("{}", it)).collect();
-    JsValue::from_str(format!("{:?}", moves_as_str).as_str())
+pub fn get_allowed_move_list(game_config: &str) -> MoveListResult {
+    to_js(&WasmResult::from(get_allowed_move_strs(game_config)))
+}
+
+fn get_allowed_move_strs(game_config: &str) -> Result<Vec<String>, ChessError> {
+    let game = game_config.parse::<Game>()?;
+    Ok(get_allowed_moves_of(&game).iter().map(|it|format!("{}", it)).collect())
 }
 
 #[wasm_bindgen]
-pub fn get_fen(game_config: &str) -> JsValue {
-    let fen_result = match game_config.parse::<Game>() {
-        Ok(game) => {
-            let fen = game.get_fen();
-            FenResult {
-                is_ok: true,
-                value: fen,
-            }
+pub fn get_fen(game_config: &str) -> JsFenResult {
+    to_js(&WasmResult::from(game_config.parse::<Game>().map(|game| game.get_fen())))
+}
+
+/**
+ * the result of an exported function that can fail, handed over to javascript (see the WasmResult typescript type)
+ */
+#[derive(Serialize, Debug, Eq, PartialEq)]
+struct WasmResult<T> {
+    is_ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    value: Option<T>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<WasmError>,
+}
+
+impl<T, E: Into<WasmError>> From<Result<T, E>> for WasmResult<T> {
+    fn from(result: Result<T, E>) -> Self {
+        match result {
+            Ok(value) => WasmResult { is_ok: true, value: Some(value), error: None },
+            Err(err) => WasmResult { is_ok: false, value: None, error: Some(err.into()) },
         }
-        Err(err) => {
-            let error_msg = format!("{:?}: {}", err.kind, err.msg);
-            FenResult {
-                is_ok: false,
-                value: error_msg,
-            }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+struct WasmError {
+    kind: SerializableErrorKind,
+    msg: String,
+}
+
+impl From<ChessError> for WasmError {
+    fn from(err: ChessError) -> Self {
+        WasmError {
+            kind: err.kind.into(),
+            msg: err.msg,
         }
-    };
-    let json = serde_json::to_string(&fen_result).unwrap();
-    JsValue::from_str(json.as_str())
+    }
 }
 
-// fn get_result_json(is_ok: bool, value: String) -> String {
-//     format!("{}{}{}{}{}", "{\"isOk\":", is_ok, ", \"value\":\"", value, "\"}")
-// }
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Eq, PartialEq)]
+enum SerializableErrorKind {
+    IllegalFormat,
+    IllegalConfig,
+    IllegalMove,
+    GameEnded,
+}
 
-#[derive(Serialize, Deserialize, Debug)]
-struct FenResult {
-    is_ok: bool,
-    value: String,
+impl From<ErrorKind> for SerializableErrorKind {
+    fn from(kind: ErrorKind) -> Self {
+        match kind {
+            ErrorKind::IllegalFormat => SerializableErrorKind::IllegalFormat,
+            ErrorKind::IllegalConfig => SerializableErrorKind::IllegalConfig,
+            ErrorKind::IllegalMove | ErrorKind::HighLevelErr(StoppedReason::KingInCheckAfterMove) => SerializableErrorKind::IllegalMove,
+            ErrorKind::HighLevelErr(_) => SerializableErrorKind::GameEnded,
+        }
+    }
 }
 
 #[wasm_bindgen]
-pub fn evaluate_position_after(game_config: &str) -> JsValue {
+pub fn evaluate_position_after(game_config: &str) -> JsGameEvaluation {
     let evaluation = evaluate(game_config, PRUNER);
-    let json = eval_to_json(evaluation, game_config);
-    JsValue::from_str(json.as_str())
+    to_js(&to_serializable(evaluation, game_config))
 }
 
-fn eval_to_json(game_eval: GameEvaluation, game_config: &str) -> String {
+/**
+ * like evaluate_position_after, but the evaluation weights are given as json,
+ * e.g. '{"queen_value": 9.5}'. Weights that aren't mentioned keep their default value.
+ */
+#[wasm_bindgen]
+pub fn evaluate_position_after_with_params(game_config: &str, eval_params_json: &str) -> JsGameEvaluation {
+    to_js(&evaluate_position_with_params_json(game_config, eval_params_json))
+}
+
+fn evaluate_position_with_params_json(game_config: &str, eval_params_json: &str) -> SerializableGameEvaluation {
+    match EvalParams::from_json(eval_params_json) {
+        Err(err) => {
+            SerializableGameEvaluation::from_error(err)
+        }
+        Ok(eval_params) => {
+            let evaluation = evaluate_with_params(game_config, PRUNER, Some(eval_params));
+            to_serializable(evaluation, game_config)
+        }
+    }
+}
+
+/**
+ * like evaluate_position_after, but the engine plays like a player of the given skill level (1 weakest - 20 strongest)
+ */
+#[wasm_bindgen]
+pub fn evaluate_position_with_skill(game_config: &str, skill_level: u8) -> JsGameEvaluation {
+    let game_evaluation = match SkillLevel::new(skill_level) {
+        Err(err) => {
+            SerializableGameEvaluation::from_error(err)
+        }
+        Ok(skill_level) => {
+            let skill_level = skill_level.with_time_limit(skill_level.get_suggested_time_limit());
+            let evaluation = evaluate_with_skill(game_config, skill_level, MoveSelection::Random);
+            to_serializable(evaluation, game_config)
+        }
+    };
+    to_js(&game_evaluation)
+}
+
+fn to_serializable(game_eval: GameEvaluation, game_config: &str) -> SerializableGameEvaluation {
+    to_serializable_with_fen(game_eval, |chosen_move| {
+        game_config.parse::<Game>().map(|game| game.get_fen_after(chosen_move))
+    })
+}
+
+fn to_serializable_with_fen(
+    game_eval: GameEvaluation,
+    get_fen_after: impl FnOnce(Move) -> Result<String, ChessError>,
+) -> SerializableGameEvaluation {
     match game_eval {
         GameEvaluation::GameEnded(result) => {
             let text = match result {
@@ -99,150 +238,159 @@ fn eval_to_json(game_eval: GameEvaluation, game_config: &str) -> String {
                         DrawReason::InsufficientMaterial => {"draw because of insufficient material"}
                         DrawReason::ThreeTimesRepetition => {"draw because of three-fold repetition"}
                         DrawReason::NoChangeIn50Moves => {"draw because of no progress in 50 moves"}
+                        DrawReason::EndgameTablebase => {"draw according to the endgame tablebase"}
                     }
                 }
             };
-            get_eval_json_end_or_err("GameEnded", String::from(text))
+            SerializableGameEvaluation::GameEnded { msg: String::from(text) }
         }
         GameEvaluation::MoveToPlay(chosen_move, eval) => {
-            let new_game_config = format!("{} {}", game_config, chosen_move);
-            let fen = new_game_config.as_str().parse::<Game>().unwrap().get_fen();
-            move_to_play_to_json(chosen_move, eval, fen)
+            match get_fen_after(chosen_move) {
+                Ok(fen) => SerializableGameEvaluation::MoveToPlay {
+                    move_to_play: chosen_move.to_string(),
+                    eval: SerializableMoveEvaluation::from(eval),
+                    fen,
+                },
+                Err(err) => SerializableGameEvaluation::from_error(err),
+            }
         }
-        GameEvaluation::Err(msg) => {
-            get_eval_json_end_or_err("Err", msg)
+        GameEvaluation::Err(err) => {
+            SerializableGameEvaluation::from_error(err)
         }
     }
 }
 
-fn move_to_play_to_json(chosen_move: Move, eval: MoveEvaluation, fen: String) -> String {
-    let eval_string = serde_json::to_string(&SerializableMoveEvaluation::from(eval)).unwrap();
-    let result = GameEvaluationResultMoveToPlay {
-        result_type: "MoveToPlay".to_string(),
-        move_to_play: chosen_move.to_string(),
-        eval: eval_string,
-        fen
-    };
-    serde_json::to_string(&result).unwrap()
-}
-
-fn get_eval_json_end_or_err(
-    result_type: &str,
-    msg: String,
-) -> String {
-    let result = GameEvaluationResultEndOrErr {
-        result_type: result_type.to_string(),
-        msg,
-    };
-    serde_json::to_string(&result).unwrap()
-}
-
-#[derive(Serialize, Deserialize, Debug)]
-struct GameEvaluationResultEndOrErr {
-    result_type: String,
-    msg: String,
-}
-
-#[derive(Serialize, Deserialize, Debug, Eq, PartialEq)]
-struct GameEvaluationResultMoveToPlay {
-    result_type: String,
-    move_to_play: String,
-    eval: String,
-    fen: String,
+/**
+ * converts to a plain js object, the same one JSON.parse would return for the json of the value
+ */
+fn to_js<T: Serialize, JsType: JsCast>(value: &T) -> JsType {
+    value.serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        // the types of this crate can always be serialized, but a panic would take down the whole wasm instance
+        .unwrap_or_else(JsValue::from)
+        .unchecked_into()
 }
 
-#[derive(Serialize, Deserialize, Debug)]
-enum GameEvaluationResult {
-    EndOrErr(GameEvaluationResultEndOrErr),
-    MoveToPlay(GameEvaluationResultMoveToPlay),
+#[wasm_bindgen]
+pub fn evaluate_move_after(game_config: &str, move_str: &str) -> JsGameEvaluation {
+    to_js(&evaluate_move_str(game_config, move_str))
 }
 
-#[wasm_bindgen]
-pub fn evaluate_move_after(game_config: &str, move_str: &str) -> JsValue {
-    let json = match move_str.parse::<Move>() {
+fn evaluate_move_str(game_config: &str, move_str: &str) -> SerializableGameEvaluation {
+    match move_str.parse::<Move>() {
         Err(err) => {
-            let err_msg = format!("{}", err);
-            get_eval_json_end_or_err("Err", err_msg)
+            SerializableGameEvaluation::from_error(err)
         }
         Ok(move_to_evaluate) => {
             let evaluation = evaluate_single_move(game_config, move_to_evaluate, PRUNER);
-            eval_to_json(evaluation, game_config)
+            to_serializable(evaluation, game_config)
         }
-    };
-
-    JsValue::from_str(json.as_str())
+    }
 }
 
+/**
+ * like evaluate_move_after, but the evaluation weights are given as json (see evaluate_position_after_with_params).
+ */
 #[wasm_bindgen]
-pub fn pick_move_to_play(game_eval_result_array_str: &str) -> JsValue {
-
-    console::log_1(&JsValue::from_str(format!("pick_move_to_play param: {}", game_eval_result_array_str).as_str()));
+pub fn evaluate_move_after_with_params(game_config: &str, move_str: &str, eval_params_json: &str) -> JsGameEvaluation {
+    to_js(&evaluate_move_str_with_params_json(game_config, move_str, eval_params_json))
+}
 
-    let game_eval_results: Vec<GameEvaluationResult> = game_eval_result_array_str.split('|').map(|result_str|{
-        match serde_json::from_str::<GameEvaluationResultMoveToPlay>(result_str) {
-            Ok(move_to_play_result) => {
-                GameEvaluationResult::MoveToPlay(move_to_play_result)
-            }
-            Err(_) => {
-                match serde_json::from_str::<GameEvaluationResultEndOrErr>(result_str) {
-                    Ok(end_or_err_result) => {
-                        GameEvaluationResult::EndOrErr(end_or_err_result)
-                    }
-                    Err(_) => {
-                        panic!("json was neither GameEvalMoveToPlay nor GameEvalEnd/Err: {}", result_str);
-                    }
-                }
-            }
+fn evaluate_move_str_with_params_json(game_config: &str, move_str: &str, eval_params_json: &str) -> SerializableGameEvaluation {
+    match (move_str.parse::<Move>(), EvalParams::from_json(eval_params_json)) {
+        (Err(err), _) | (_, Err(err)) => {
+            SerializableGameEvaluation::from_error(err)
         }
-    }).collect();
-
-    // let game_eval_results: Vec<GameEvaluationResult> = serde_json::from_str(game_eval_result_array_str).unwrap();
-    let opt_game_eval_result_end_or_err = game_eval_results.iter().find(|result|{
-        match result {
-            GameEvaluationResult::EndOrErr(_) => {true}
-            GameEvaluationResult::MoveToPlay(_) => {false}
+        (Ok(move_to_evaluate), Ok(eval_params)) => {
+            let evaluation = evaluate_single_move_with_params(game_config, move_to_evaluate, PRUNER, Some(eval_params));
+            to_serializable(evaluation, game_config)
         }
-    });
-    if let Some(game_eval_result_end_or_err) = opt_game_eval_result_end_or_err {
-        let err_or_game_ended_json = match game_eval_result_end_or_err {
-            GameEvaluationResult::EndOrErr(end_or_err) => {serde_json::to_string(end_or_err).unwrap()}
-            GameEvaluationResult::MoveToPlay(_) => { get_eval_json_end_or_err("Err", "filtered by EndOrErr, still got MoveToPlay".to_string())}
-        };
-        return JsValue::from_str(err_or_game_ended_json.as_str());
     }
-    // everything should be move to play
+}
+
+/**
+ * picks the move to play from the evaluations of the single moves (see evaluate_move_after).
+ * If one of the evaluations is GameEnded or Err, that one is returned.
+ */
+#[wasm_bindgen]
+pub fn pick_move_to_play(game_evaluations: GameEvaluationArray) -> JsGameEvaluation {
+    let game_evaluation = match serde_wasm_bindgen::from_value::<Vec<SerializableGameEvaluation>>(game_evaluations.into()) {
+        Err(err) => SerializableGameEvaluation::from_error(WasmError {
+            kind: SerializableErrorKind::IllegalFormat,
+            msg: format!("expected an array of GameEvaluations: {}", err),
+        }),
+        Ok(game_evaluations) => pick_move_to_play_from(game_evaluations),
+    };
+    to_js(&game_evaluation)
+}
+
+fn pick_move_to_play_from(game_evaluations: Vec<SerializableGameEvaluation>) -> SerializableGameEvaluation {
     let mut fen_by_move = HashMap::new();
-    let moves_to_pick_from: Vec<EvaluatedMove> = game_eval_results.iter().map(|game_result|{
-        match game_result {
-            GameEvaluationResult::EndOrErr(_) => {panic!("only MoveToPlay expected at this point")}
-            GameEvaluationResult::MoveToPlay(type4) => {
-                let move_played:Move = type4.move_to_play.parse().unwrap();
-                let eval: MoveEvaluation = serde_json::from_str::<SerializableMoveEvaluation>(type4.eval.as_str()).unwrap().into();
-                let fen = type4.fen.as_str();
+    let mut moves_to_pick_from: Vec<EvaluatedMove> = vec![];
+    for game_evaluation in game_evaluations {
+        match game_evaluation {
+            SerializableGameEvaluation::MoveToPlay { move_to_play, eval, fen } => {
+                let move_played: Move = match move_to_play.parse() {
+                    Ok(move_played) => move_played,
+                    Err(err) => { return SerializableGameEvaluation::from_error(err); }
+                };
                 fen_by_move.insert(move_played, fen);
-                EvaluatedMove {
+                moves_to_pick_from.push(EvaluatedMove {
                     a_move: move_played,
-                    evaluation: eval,
-                }
+                    evaluation: eval.into(),
+                });
             }
+            end_or_err => { return end_or_err; }
         }
-    }).collect();
+    }
+    if moves_to_pick_from.is_empty() {
+        return SerializableGameEvaluation::from_error(WasmError {
+            kind: SerializableErrorKind::IllegalConfig,
+            msg: "there were no evaluated moves to pick from".to_string(),
+        });
+    }
 
     let next_move = choose_next_move(moves_to_pick_from);
-    let fen = fen_by_move.get(&next_move.a_move).unwrap().to_string();
-    let json = move_to_play_to_json(    next_move.a_move, next_move.evaluation, fen);
+    SerializableGameEvaluation::MoveToPlay {
+        move_to_play: next_move.a_move.to_string(),
+        eval: next_move.evaluation.into(),
+        fen: fen_by_move.remove(&next_move.a_move).expect("the fen of every move was stored"),
+    }
+}
 
-    console::log_1(&JsValue::from_str(format!("json of the picked gameEval: {}", json).as_str()));
+/**
+ * the result of an evaluation as it is handed over to javascript (see the GameEvaluation typescript type)
+ */
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(tag = "result_type")]
+enum SerializableGameEvaluation {
+    MoveToPlay {
+        move_to_play: String,
+        eval: SerializableMoveEvaluation,
+        fen: String,
+    },
+    GameEnded {
+        msg: String,
+    },
+    Err {
+        kind: SerializableErrorKind,
+        msg: String,
+    },
+}
 
-    JsValue::from_str(json.as_str())
+impl SerializableGameEvaluation {
+    fn from_error(err: impl Into<WasmError>) -> SerializableGameEvaluation {
+        let WasmError { kind, msg } = err.into();
+        SerializableGameEvaluation::Err { kind, msg }
+    }
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
 pub enum SerializableDrawReason {
     StaleMate,
     InsufficientMaterial,
     ThreeTimesRepetition,
     NoChangeIn50Moves,
+    EndgameTablebase,
 }
 
 impl From<DrawReason> for SerializableDrawReason {
@@ -252,6 +400,7 @@ impl From<DrawReason> for SerializableDrawReason {
             DrawReason::InsufficientMaterial => SerializableDrawReason::InsufficientMaterial,
             DrawReason::ThreeTimesRepetition => SerializableDrawReason::ThreeTimesRepetition,
             DrawReason::NoChangeIn50Moves => SerializableDrawReason::NoChangeIn50Moves,
+            DrawReason::EndgameTablebase => SerializableDrawReason::EndgameTablebase,
         }
     }
 }
@@ -263,11 +412,12 @@ impl From<SerializableDrawReason> for DrawReason {
             SerializableDrawReason::InsufficientMaterial => DrawReason::InsufficientMaterial,
             SerializableDrawReason::ThreeTimesRepetition => DrawReason::ThreeTimesRepetition,
             SerializableDrawReason::NoChangeIn50Moves => DrawReason::NoChangeIn50Moves,
+            SerializableDrawReason::EndgameTablebase => DrawReason::EndgameTablebase,
         }
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub enum SerializableMoveEvaluation {
     EngineCheckMatesIn(u8),
     Numeric(f32),
@@ -304,44 +454,140 @@ impl From<SerializableMoveEvaluation> for MoveEvaluation {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rstest::*;
 
     #[test]
-    #[allow(non_snake_case)]
-    fn test_serialize_deserialize_GameEvaluationResultMoveToPlay() {
-        let move_to_play_result = GameEvaluationResultMoveToPlay {
-            result_type: "MoveToPlay".to_string(),
+    fn test_game_evaluation_json_is_tagged_by_result_type() {
+        let move_to_play = SerializableGameEvaluation::MoveToPlay {
             move_to_play: "b7-b6".to_string(),
-            eval: "{\"Numeric\":-1.0050015}".to_string(),
+            eval: SerializableMoveEvaluation::Numeric(-1.0),
             fen: "rnbqkbnr/p1pppppp/1p6/8/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 0 2".to_string()
         };
-        let serialized = serde_json::to_string(&move_to_play_result).unwrap();
-        let deserialized: GameEvaluationResultMoveToPlay = serde_json::from_str(serialized.as_str()).unwrap();
+        let json = serde_json::to_string(&move_to_play).unwrap();
         assert_eq!(
-            deserialized,
-            move_to_play_result,
+            json,
+            r#"{"result_type":"MoveToPlay","move_to_play":"b7-b6","eval":{"Numeric":-1.0},"fen":"rnbqkbnr/p1pppppp/1p6/8/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 0 2"}"#,
         );
+        assert_eq!(serde_json::from_str::<SerializableGameEvaluation>(&json).unwrap(), move_to_play);
+
+        let game_ended = SerializableGameEvaluation::GameEnded { msg: "stale mate".to_string() };
+        assert_eq!(serde_json::to_string(&game_ended).unwrap(), r#"{"result_type":"GameEnded","msg":"stale mate"}"#);
+        let draw = SerializableMoveEvaluation::Draw(SerializableDrawReason::StaleMate);
+        assert_eq!(serde_json::to_string(&draw).unwrap(), r#"{"Draw":"StaleMate"}"#);
     }
 
     #[test]
-    #[allow(non_snake_case)]
-    fn test_deserialize_GameEvaluationResultMoveToPlay() {
+    fn test_to_serializable() {
         let chosen_move = "a2-a4".parse::<Move>().unwrap();
-        let eval = MoveEvaluation::Numeric(5.5);
-        let fen = "rnbqkbnr/p1pppppp/1p6/8/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 0 2".to_string();
-        let json = move_to_play_to_json(chosen_move, eval, fen);
-        let deserialized: GameEvaluationResultMoveToPlay = serde_json::from_str(json.as_str()).unwrap();
+        let game_evaluation = to_serializable(GameEvaluation::MoveToPlay(chosen_move, MoveEvaluation::Numeric(5.5)), "");
         assert_eq!(
-            deserialized.move_to_play,
-            "a2-a4",
-        );
-        assert_eq!(
-            deserialized.fen,
-            "rnbqkbnr/p1pppppp/1p6/8/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 0 2".to_string(),
-        );
-        let deserialized_move_eval: MoveEvaluation = serde_json::from_str::<SerializableMoveEvaluation>(deserialized.eval.as_str()).unwrap().into();
-        assert_eq!(
-            deserialized_move_eval,
-            MoveEvaluation::Numeric(5.5),
+            game_evaluation,
+            SerializableGameEvaluation::MoveToPlay {
+                move_to_play: "a2-a4".to_string(),
+                eval: SerializableMoveEvaluation::Numeric(5.5),
+                fen: "rnbqkbnr/pppppppp/8/8/P7/8/1PPPPPPP/RNBQKBNR b KQkq a3 0 1".to_string(),
+            },
         );
     }
+
+    #[test]
+    fn test_pick_move_to_play_from() {
+        let move_to_play = |move_str: &str, pawns: f32| SerializableGameEvaluation::MoveToPlay {
+            move_to_play: move_str.to_string(),
+            eval: SerializableMoveEvaluation::Numeric(pawns),
+            fen: format!("fen after {}", move_str),
+        };
+        let picked = pick_move_to_play_from(vec![
+            move_to_play("a2-a3", -3.0),
+            move_to_play("e2-e4", 5.0),
+            move_to_play("h2-h3", -2.0),
+        ]);
+        assert_eq!(picked, move_to_play("e2-e4", 5.0));
+
+        let game_ended = SerializableGameEvaluation::GameEnded { msg: "stale mate".to_string() };
+        let picked = pick_move_to_play_from(vec![move_to_play("e2-e4", 5.0), game_ended]);
+        assert_eq!(picked, SerializableGameEvaluation::GameEnded { msg: "stale mate".to_string() });
+
+        assert!(matches!(pick_move_to_play_from(vec![]), SerializableGameEvaluation::Err { .. }));
+        let unparsable_move = SerializableGameEvaluation::MoveToPlay {
+            move_to_play: "e2".to_string(),
+            eval: SerializableMoveEvaluation::Numeric(0.0),
+            fen: "".to_string(),
+        };
+        assert_eq!(get_error_kind(pick_move_to_play_from(vec![unparsable_move])), Some(SerializableErrorKind::IllegalFormat));
+    }
+
+    fn get_error_kind(game_evaluation: SerializableGameEvaluation) -> Option<SerializableErrorKind> {
+        match game_evaluation {
+            SerializableGameEvaluation::Err { kind, .. } => Some(kind),
+            _ => None,
+        }
+    }
+
+    //♔♕♗♘♖♙♚♛♝♞♜♟
+
+    #[rstest(
+    game_config, move_str, eval_params_json, expected_error_kind,
+    case("e2-e4 e7", "e2-e4", "{}", SerializableErrorKind::IllegalFormat),
+    case("e2-e4 e2-e4", "e7-e5", "{}", SerializableErrorKind::IllegalMove),
+    case("white ♔e1 ♚e8 ♙", "e1-e2", "{}", SerializableErrorKind::IllegalFormat),
+    case("ä2-e4", "e1-e2", "{}", SerializableErrorKind::IllegalFormat),
+    case("", "e2-e5", "{}", SerializableErrorKind::IllegalMove),
+    case("", "e2", "{}", SerializableErrorKind::IllegalFormat),
+    case("", "€€€€€", "{}", SerializableErrorKind::IllegalFormat),
+    case("", "e2-e4", "{\"queen_value\": ", SerializableErrorKind::IllegalFormat),
+    case("", "e2-e4", "\"queen_value\"", SerializableErrorKind::IllegalFormat),
+    case("99999999999999999/8/8/8/8/8/8/8 w - - 0 1", "e2-e4", "{}", SerializableErrorKind::IllegalFormat),
+    case("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - x 1", "e2-e4", "{}", SerializableErrorKind::IllegalFormat),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_malformed_input_returns_error(game_config: &str, move_str: &str, eval_params_json: &str, expected_error_kind: SerializableErrorKind) {
+        assert_eq!(get_error_kind(evaluate_move_str_with_params_json(game_config, move_str, eval_params_json)), Some(expected_error_kind));
+        if eval_params_json == "{}" {
+            assert_eq!(get_error_kind(evaluate_move_str(game_config, move_str)), Some(expected_error_kind));
+        }
+    }
+
+    #[rstest(
+    game_config, expected_error_kind,
+    case("e2-e4 e7", SerializableErrorKind::IllegalFormat),
+    case("e2-e4 e2-e4", SerializableErrorKind::IllegalMove),
+    case("white ♔e1 ♚e8 X", SerializableErrorKind::IllegalFormat),
+    case("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR x KQkq - 0 1", SerializableErrorKind::IllegalFormat),
+    case("99999999999999999/8/8/8/8/8/8/8 w - - 0 1", SerializableErrorKind::IllegalFormat),
+    case("rnbqkbnr/pppppppp/08/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1", SerializableErrorKind::IllegalFormat),
+    case("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP w KQkq - 0 1", SerializableErrorKind::IllegalFormat),
+    case("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq e9 0 1", SerializableErrorKind::IllegalFormat),
+    case("white ♔e1 ♚e8 ♔e2", SerializableErrorKind::IllegalConfig),
+    case("b1-c3 b8-c6 c3-b1 c6-b8 b1-c3 b8-c6 c3-b1 c6-b8 b1-c3", SerializableErrorKind::GameEnded),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_malformed_game_config_returns_error(game_config: &str, expected_error_kind: SerializableErrorKind) {
+        let result = WasmResult::from(get_allowed_move_strs(game_config));
+        assert!(!result.is_ok);
+        assert_eq!(result.error.map(|err| err.kind), Some(expected_error_kind));
+        let fen_result = WasmResult::from(game_config.parse::<Game>().map(|game| game.get_fen()));
+        assert_eq!(fen_result.error.map(|err| err.kind), Some(expected_error_kind));
+    }
+
+    #[test]
+    fn test_wasm_result_json() {
+        let ok: WasmResult<String> = WasmResult::from(Ok::<String, ChessError>("a fen".to_string()));
+        assert_eq!(serde_json::to_string(&ok).unwrap(), r#"{"is_ok":true,"value":"a fen"}"#);
+        let err: WasmResult<String> = WasmResult::from(Err(ChessError { msg: "the msg".to_string(), kind: ErrorKind::IllegalMove }));
+        assert_eq!(serde_json::to_string(&err).unwrap(), r#"{"is_ok":false,"error":{"kind":"IllegalMove","msg":"the msg"}}"#);
+    }
+
+    #[rstest(
+    json,
+    case(r#"{"result_type":"MoveToPlay","move_to_play":"e2-e4"}"#),
+    case(r#"{"result_type":"Unknown","msg":"msg"}"#),
+    case(r#"{"move_to_play":"e2-e4","eval":{"Numeric":1.0},"fen":""}"#),
+    case(r#"{"result_type":"MoveToPlay","move_to_play":"e2-e4","eval":{"Numeric":"1"},"fen":""}"#),
+    case(r#"{"result_type":"MoveToPlay","move_to_play":"e2-e4","eval":{"Draw":"Forfeit"},"fen":""}"#),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_malformed_game_evaluation_is_rejected(json: &str) {
+        assert!(serde_json::from_str::<SerializableGameEvaluation>(json).is_err());
+    }
 }
@@ -0,0 +1,227 @@
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::base::Move;
+use crate::engine::evaluations::EvaluatedMove;
+use crate::engine::root_split::{self, RootSplit, WorkItem};
+use crate::game::Game;
+use crate::game_session::{EvaluateOptions, JsEvaluateOptions};
+use crate::{to_js, to_serializable, to_serializable_with_fen, GameEvaluationArray, JsGameEvaluation, SerializableErrorKind, SerializableGameEvaluation, WasmError};
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_TYPES: &'static str = r#"
+/** one legal move of a position, it can be evaluated on its own (e.g. in a web worker) */
+export interface WorkItem {
+    game_config: string;
+    move_to_evaluate: string;
+}
+
+/** GameEnded/Err if there is nothing to evaluate, else the moves to evaluate */
+export type RootSplit =
+    | { result_type: "WorkItems", work_items: WorkItem[] }
+    | { result_type: "GameEnded", msg: string }
+    | { result_type: "Err", kind: ErrorKind, msg: string };
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "RootSplit")]
+    pub type JsRootSplit;
+    #[wasm_bindgen(typescript_type = "WorkItem")]
+    pub type JsWorkItem;
+}
+
+/**
+ * splits the search of the position into one work item per legal move.
+ * Evaluate them with evaluate_work_item (in any order, on any worker) and hand the results to merge_evaluations.
+ */
+#[wasm_bindgen]
+pub fn split_position(game_config: &str) -> JsRootSplit {
+    to_js(&split_position_into(game_config))
+}
+
+/**
+ * evaluates the move of a work item (see split_position). The options are the ones of GameSession.evaluate without skill_level.
+ */
+#[wasm_bindgen]
+pub fn evaluate_work_item(work_item: JsWorkItem, opt_options: Option<JsEvaluateOptions>) -> JsGameEvaluation {
+    let game_evaluation = match (serde_wasm_bindgen::from_value::<SerializableWorkItem>(work_item.into()), EvaluateOptions::from_js(opt_options)) {
+        (Err(err), _) => SerializableGameEvaluation::from_error(WasmError {
+            kind: SerializableErrorKind::IllegalFormat,
+            msg: format!("expected a WorkItem: {}", err),
+        }),
+        (_, Err(err)) => SerializableGameEvaluation::from_error(err),
+        (Ok(work_item), Ok(options)) => evaluate_serializable_work_item(&work_item, &options),
+    };
+    to_js(&game_evaluation)
+}
+
+/**
+ * picks the move to play from the evaluations of all work items of split_position(game_config).
+ * The options have to be the ones the work items were evaluated with.
+ * If one of the evaluations is GameEnded or Err, that one is returned.
+ */
+#[wasm_bindgen]
+pub fn merge_evaluations(game_config: &str, game_evaluations: GameEvaluationArray, opt_options: Option<JsEvaluateOptions>) -> JsGameEvaluation {
+    let game_evaluation = match (serde_wasm_bindgen::from_value::<Vec<SerializableGameEvaluation>>(game_evaluations.into()), EvaluateOptions::from_js(opt_options)) {
+        (Err(err), _) => SerializableGameEvaluation::from_error(WasmError {
+            kind: SerializableErrorKind::IllegalFormat,
+            msg: format!("expected an array of GameEvaluations: {}", err),
+        }),
+        (_, Err(err)) => SerializableGameEvaluation::from_error(err),
+        (Ok(game_evaluations), Ok(options)) => merge_serializable_evaluations(game_config, game_evaluations, &options),
+    };
+    to_js(&game_evaluation)
+}
+
+fn split_position_into(game_config: &str) -> SerializableRootSplit {
+    match root_split::split_root_moves(game_config) {
+        RootSplit::WorkItems(work_items) => SerializableRootSplit::WorkItems {
+            work_items: work_items.into_iter().map(|work_item| SerializableWorkItem {
+                game_config: work_item.game_config,
+                move_to_evaluate: work_item.a_move.to_string(),
+            }).collect(),
+        },
+        RootSplit::Ended(game_evaluation) => match to_serializable(game_evaluation, game_config) {
+            SerializableGameEvaluation::GameEnded { msg } => SerializableRootSplit::GameEnded { msg },
+            SerializableGameEvaluation::Err { kind, msg } => SerializableRootSplit::Err { kind, msg },
+            SerializableGameEvaluation::MoveToPlay { .. } => unreachable!("a RootSplit only ends without a move to play"),
+        },
+    }
+}
+
+fn evaluate_serializable_work_item(work_item: &SerializableWorkItem, options: &EvaluateOptions) -> SerializableGameEvaluation {
+    let (a_move, config) = match (work_item.move_to_evaluate.parse::<Move>(), options.to_search_config()) {
+        (Err(err), _) | (_, Err(err)) => { return SerializableGameEvaluation::from_error(err); }
+        (Ok(a_move), Ok(config)) => (a_move, config),
+    };
+    let work_item = WorkItem { game_config: work_item.game_config.clone(), a_move };
+    match root_split::evaluate_work_item(&work_item, &config) {
+        Err(err) => SerializableGameEvaluation::from_error(err),
+        Ok(evaluated_move) => SerializableGameEvaluation::MoveToPlay {
+            move_to_play: evaluated_move.a_move.to_string(),
+            eval: evaluated_move.evaluation.into(),
+            fen: work_item.game_config.parse::<Game>()
+                .map(|game| game.get_fen_after(evaluated_move.a_move))
+                .expect("the game_config was already parsed successfully"),
+        },
+    }
+}
+
+fn merge_serializable_evaluations(game_config: &str, game_evaluations: Vec<SerializableGameEvaluation>, options: &EvaluateOptions) -> SerializableGameEvaluation {
+    let config = match options.to_search_config() {
+        Ok(config) => config,
+        Err(err) => { return SerializableGameEvaluation::from_error(err); }
+    };
+    let mut evaluated_moves: Vec<EvaluatedMove> = Vec::with_capacity(game_evaluations.len());
+    for game_evaluation in game_evaluations {
+        match game_evaluation {
+            SerializableGameEvaluation::MoveToPlay { move_to_play, eval, .. } => {
+                match move_to_play.parse::<Move>() {
+                    Ok(a_move) => evaluated_moves.push(EvaluatedMove { a_move, evaluation: eval.into() }),
+                    Err(err) => { return SerializableGameEvaluation::from_error(err); }
+                }
+            }
+            end_or_err => { return end_or_err; }
+        }
+    }
+    let game_evaluation = root_split::merge_evaluated_moves(game_config, evaluated_moves, &config);
+    to_serializable_with_fen(game_evaluation, |chosen_move| {
+        game_config.parse::<Game>().map(|game| game.get_fen_after(chosen_move))
+    })
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+struct SerializableWorkItem {
+    game_config: String,
+    move_to_evaluate: String,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(tag = "result_type")]
+enum SerializableRootSplit {
+    WorkItems {
+        work_items: Vec<SerializableWorkItem>,
+    },
+    GameEnded {
+        msg: String,
+    },
+    Err {
+        kind: SerializableErrorKind,
+        msg: String,
+    },
+}
+
+//------------------------------Tests------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+    use crate::SerializableMoveEvaluation;
+
+    //♔♕♗♘♖♙♚♛♝♞♜♟
+
+    fn get_work_items(game_config: &str) -> Vec<SerializableWorkItem> {
+        match split_position_into(game_config) {
+            SerializableRootSplit::WorkItems { work_items } => work_items,
+            root_split => panic!("unexpected split: {:?}", root_split),
+        }
+    }
+
+    #[rstest(
+    game_config, expected_json,
+    case("black ♔b6 ♙a7 ♚a8", r#"{"result_type":"GameEnded","msg":"stale mate"}"#),
+    case("e2-e4 e2-e4", r#"{"result_type":"Err","kind":"IllegalMove","msg":"unexpected error e2-e4 e2-e4, reason: move e2-e4 isn't possible in rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1"}"#),
+    case("white ♔e1 ♚g8 ♝c3 ♖h1", r#"{"result_type":"WorkItems","work_items":[{"game_config":"white ♔e1 ♚g8 ♝c3 ♖h1","move_to_evaluate":"e1-e2"},{"game_config":"white ♔e1 ♚g8 ♝c3 ♖h1","move_to_evaluate":"e1-f2"},{"game_config":"white ♔e1 ♚g8 ♝c3 ♖h1","move_to_evaluate":"e1-f1"},{"game_config":"white ♔e1 ♚g8 ♝c3 ♖h1","move_to_evaluate":"e1-d1"}]}"#),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_split_position_json(game_config: &str, expected_json: &str) {
+        let json = serde_json::to_string(&split_position_into(game_config)).unwrap();
+        assert_eq!(json, expected_json);
+    }
+
+    #[test]
+    fn test_split_evaluate_merge() {
+        let game_config = "white ♔g3 ♖d2 ♚g1 ♙c2 ♙d3";
+        let options = EvaluateOptions::default();
+        let game_evaluations: Vec<SerializableGameEvaluation> = get_work_items(game_config).iter().rev()
+            .map(|work_item| evaluate_serializable_work_item(work_item, &options))
+            .collect();
+        match merge_serializable_evaluations(game_config, game_evaluations, &options) {
+            SerializableGameEvaluation::MoveToPlay { move_to_play, eval, fen } => {
+                assert_eq!(move_to_play, "d2-d1");
+                assert_eq!(eval, SerializableMoveEvaluation::EngineCheckMatesIn(0));
+                assert_eq!(fen, "8/8/8/8/8/3P2K1/2P5/3R2k1 b - - 1 1");
+            }
+            game_evaluation => panic!("unexpected evaluation: {:?}", game_evaluation),
+        }
+    }
+
+    #[rstest(
+    move_to_evaluate, options_json,
+    case("e2-e5", "{}"),
+    case("e2e4", "{}"),
+    case("e2-e4", r#"{"skill_level": 3}"#),
+    ::trace //This leads to the arguments being printed in front of the test result.
+    )]
+    fn test_evaluate_malformed_work_item(move_to_evaluate: &str, options_json: &str) {
+        let work_item = SerializableWorkItem { game_config: "".to_string(), move_to_evaluate: move_to_evaluate.to_string() };
+        let options: EvaluateOptions = serde_json::from_str(options_json).unwrap();
+        let game_evaluation = evaluate_serializable_work_item(&work_item, &options);
+        assert!(matches!(game_evaluation, SerializableGameEvaluation::Err { .. }), "{:?}", game_evaluation);
+    }
+
+    #[test]
+    fn test_merge_incomplete_evaluations() {
+        let game_config = "white ♔g3 ♖d2 ♚g1 ♙c2 ♙d3";
+        let options = EvaluateOptions::default();
+        let mut game_evaluations: Vec<SerializableGameEvaluation> = get_work_items(game_config).iter()
+            .map(|work_item| evaluate_serializable_work_item(work_item, &options))
+            .collect();
+        game_evaluations.pop();
+        let game_evaluation = merge_serializable_evaluations(game_config, game_evaluations, &options);
+        assert!(matches!(game_evaluation, SerializableGameEvaluation::Err { kind: SerializableErrorKind::IllegalConfig, .. }), "{:?}", game_evaluation);
+    }
+}